@@ -1,26 +1,38 @@
-#![feature(test)]
-extern crate test;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use gitjournal::{parse_commit_message, Config, GitJournal, ParseOptions};
+use std::fs;
 
-use gitjournal::GitJournal;
-use test::Bencher;
-
-#[bench]
-fn verify_huge_message(b: &mut Bencher) {
+fn verify_huge_message(c: &mut Criterion) {
     let journal = GitJournal::new(".").unwrap();
-    b.iter(|| {
-        journal
-            .verify("./tests/commit_messages/success_huge")
-            .is_ok();
+    c.bench_function("verify_huge_message", |b| {
+        b.iter(|| journal.verify("./tests/commit_messages/success_huge"))
     });
 }
 
-#[bench]
-fn parse(b: &mut Bencher) {
-    let mut journal = GitJournal::new(".").unwrap();
-    journal.config.enable_debug = false;
-    b.iter(|| {
-        journal
-            .parse_log("HEAD", "rc", &0, &true, &false, None)
-            .is_ok();
+fn parse_huge_message(c: &mut Criterion) {
+    let config = Config::new();
+    let message =
+        fs::read_to_string("./tests/commit_messages/success_huge").unwrap();
+    c.bench_function("parse_huge_message", |b| {
+        b.iter(|| parse_commit_message(&message, &config))
     });
 }
+
+fn parse_log(c: &mut Criterion) {
+    let options = ParseOptions::new("HEAD").tag_skip_pattern("rc").all(true);
+    // The results accumulate, so every iteration parses into a new journal
+    c.bench_function("parse_log", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut journal = GitJournal::new(".").unwrap();
+                journal.config.enable_debug = false;
+                journal
+            },
+            |journal| journal.parse_log(&options).ok(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, verify_huge_message, parse_huge_message, parse_log);
+criterion_main!(benches);
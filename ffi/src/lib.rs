@@ -0,0 +1,6 @@
+//! The shared and static library of git-journal for non-Rust tooling, which
+//! exports the C interface of `gitjournal::ffi` and, if the `python` feature
+//! is enabled, the Python extension module `gitjournal`. The library crate
+//! itself is only built as `rlib` this way.
+
+pub use gitjournal::ffi::*;
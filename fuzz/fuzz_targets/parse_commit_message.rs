@@ -0,0 +1,12 @@
+#![no_main]
+use gitjournal::{parse_commit_message, Config};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(message) = std::str::from_utf8(data) {
+        let mut config = Config::new();
+        parse_commit_message(message, &config).ok();
+        config.parse_gitmoji = true;
+        parse_commit_message(message, &config).ok();
+    }
+});
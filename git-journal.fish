@@ -1,49 +1,81 @@
-function __fish_using_command
-    set cmd (commandline -opc)
-    if [ (count $cmd) -eq (count $argv) ]
-        for i in (seq (count $argv))
-            if [ $cmd[$i] != $argv[$i] ]
-                return 1
-            end
-        end
-        return 0
-    end
-    return 1
-end
-
-complete -c git-journal -n "__fish_using_command git-journal" -s p -l path -d "Sets a custom working path."
-complete -c git-journal -n "__fish_using_command git-journal" -s n -l tags-count -d "The number of tags until the parser stops when a single revision is given."
-complete -c git-journal -n "__fish_using_command git-journal" -s e -d "A pattern to exclude git tags from the processing."
-complete -c git-journal -n "__fish_using_command git-journal" -s t -l template -d "Use a custom output template."
-complete -c git-journal -n "__fish_using_command git-journal" -s o -l output -d "The output file for the changelog."
-complete -c git-journal -n "__fish_using_command git-journal" -s a -l all -d "Do not stop parsing at the first tag when a single revision is given. Overwrites '-n/--tags-count'."
-complete -c git-journal -n "__fish_using_command git-journal" -s g -l generate -d "Generate a fresh output template from a commit range."
-complete -c git-journal -n "__fish_using_command git-journal" -s s -l short -d "Print only the shortlog (summary) form."
-complete -c git-journal -n "__fish_using_command git-journal" -s u -l skip-unreleased -d "Skip entries without any relation to a git TAG."
-complete -c git-journal -n "__fish_using_command git-journal" -s h -l help -d "Prints help information"
-complete -c git-journal -n "__fish_using_command git-journal" -s V -l version -d "Prints version information"
-complete -c git-journal -n "__fish_using_command git-journal" -s h -l help -d "Prints help information"
-complete -c git-journal -n "__fish_using_command git-journal" -s V -l version -d "Prints version information"
-complete -c git-journal -n "__fish_using_command git-journal" -f -a "prepare"
-complete -c git-journal -n "__fish_using_command git-journal" -f -a "setup"
-complete -c git-journal -n "__fish_using_command git-journal" -f -a "verify"
-complete -c git-journal -n "__fish_using_command git-journal" -f -a "help"
-complete -c git-journal -n "__fish_using_command git-journal" -f -a "help"
-complete -c git-journal -n "__fish_using_command git-journal prepare" -s h -l help -d "Prints help information"
-complete -c git-journal -n "__fish_using_command git-journal prepare" -s V -l version -d "Prints version information"
-complete -c git-journal -n "__fish_using_command git-journal prepare" -s h -l help -d "Prints help information"
-complete -c git-journal -n "__fish_using_command git-journal prepare" -s V -l version -d "Prints version information"
-complete -c git-journal -n "__fish_using_command git-journal setup" -s h -l help -d "Prints help information"
-complete -c git-journal -n "__fish_using_command git-journal setup" -s V -l version -d "Prints version information"
-complete -c git-journal -n "__fish_using_command git-journal setup" -s h -l help -d "Prints help information"
-complete -c git-journal -n "__fish_using_command git-journal setup" -s V -l version -d "Prints version information"
-complete -c git-journal -n "__fish_using_command git-journal verify" -s h -l help -d "Prints help information"
-complete -c git-journal -n "__fish_using_command git-journal verify" -s V -l version -d "Prints version information"
-complete -c git-journal -n "__fish_using_command git-journal verify" -s h -l help -d "Prints help information"
-complete -c git-journal -n "__fish_using_command git-journal verify" -s V -l version -d "Prints version information"
-complete -c git-journal -n "__fish_using_command git-journal help" -s h -l help -d "Prints help information"
-complete -c git-journal -n "__fish_using_command git-journal help" -s V -l version -d "Prints version information"
-complete -c git-journal -n "__fish_using_command git-journal help" -s h -l help -d "Prints help information"
-complete -c git-journal -n "__fish_using_command git-journal help" -s V -l version -d "Prints version information"
-complete -c git-journal -n "__fish_using_command git-journal help" -s h -l help -d "Prints help information"
-complete -c git-journal -n "__fish_using_command git-journal help" -s V -l version -d "Prints version information"
+complete -c git-journal -n "__fish_use_subcommand" -s p -l path -d 'Sets a custom working path.'
+complete -c git-journal -n "__fish_use_subcommand" -l config-from -d 'Loads the configuration from a revision like "origin/master" instead of the working tree.'
+complete -c git-journal -n "__fish_use_subcommand" -s n -l tags-count -d 'The number of tags until the parser stops when a single revision is given.'
+complete -c git-journal -n "__fish_use_subcommand" -s e -d 'A pattern to exclude git tags from the processing.'
+complete -c git-journal -n "__fish_use_subcommand" -s t -l template -d 'Use a custom output template.'
+complete -c git-journal -n "__fish_use_subcommand" -s o -l output -d 'The output file for the changelog.'
+complete -c git-journal -n "__fish_use_subcommand" -l format -d 'The format of the output, where structured formats are printed without colors. The \'email\' format announces the release given by \'--release\' as message for \'sendmail -t\'. The \'mdbook\' format writes a page per release and a \'SUMMARY.md\' fragment into the \'--output\' directory, the \'hugo\' and \'zola\' formats write a page per release with a front matter into the \'--output\' content directory.' -r -f -a "markdown csv email hugo json mdbook toml tsv yaml zola"
+complete -c git-journal -n "__fish_use_subcommand" -l branches -d 'Print a separate log for every branch matching the pattern, e.g. \'release/*\', where cherry-picked commits are detected. The branch name is inserted before the extension of the \'--output\' file.'
+complete -c git-journal -n "__fish_use_subcommand" -l from-tag -d 'Parse the log starting after the given git TAG. Overwrites the revision range.'
+complete -c git-journal -n "__fish_use_subcommand" -l to-tag -d 'Parse the log up to the given git TAG instead of HEAD.'
+complete -c git-journal -n "__fish_use_subcommand" -l sort -d 'The comma separated sorting of the commits while walking through the history, like "time", "topological" or "reverse".'
+complete -c git-journal -n "__fish_use_subcommand" -l tag-sorting -d 'The sorting of the releases, either by commit "time" or "semver".'
+complete -c git-journal -n "__fish_use_subcommand" -l min-version -d 'Print only releases with at least the given semantic version.'
+complete -c git-journal -n "__fish_use_subcommand" -l major -d 'Print only releases of the given major version.'
+complete -c git-journal -n "__fish_use_subcommand" -s r -l release -d 'Print only the section of the given release (git TAG), without the sections of other releases.'
+complete -c git-journal -n "__fish_use_subcommand" -l inject -d 'Inject the generated notes of \'--release\' or the first release between the \'<!-- journal:start -->\' and \'<!-- journal:end -->\' markers of a file like the README instead of printing them.'
+complete -c git-journal -n "__fish_use_subcommand" -l header -d 'A text which will be printed once before the generated log.'
+complete -c git-journal -n "__fish_use_subcommand" -l footer -d 'A text which will be printed once after the generated log.'
+complete -c git-journal -n "__fish_use_subcommand" -l add-entry -d 'Add a manual entry to the unreleased section, which has to be a valid commit message. Can be given multiple times.'
+complete -c git-journal -n "__fish_use_subcommand" -s i -l ignore -d 'Ignore the given comma separated list of :tags:, like "internal"'
+complete -c git-journal -n "__fish_use_subcommand" -l search -d 'Print only the entries containing the keyword in their summary, body, footers or tags'
+complete -c git-journal -n "__fish_use_subcommand" -l only -d 'Print only the given comma separated list of categories, like "Fixed,Security"'
+complete -c git-journal -n "__fish_use_subcommand" -s a -l all -d 'Do not stop parsing at the first tag when a single revision is given. Overwrites \'-n/--tags-count\'.'
+complete -c git-journal -n "__fish_use_subcommand" -s g -l generate -d 'Generate a fresh output template from a commit range.'
+complete -c git-journal -n "__fish_use_subcommand" -s s -l short -d 'Print only the shortlog (summary) form.'
+complete -c git-journal -n "__fish_use_subcommand" -s u -l skip-unreleased -d 'Skip entries without any relation to a git TAG.'
+complete -c git-journal -n "__fish_use_subcommand" -l tui -d 'Browse the parsed log interactively within the terminal, which requires the \'tui\' feature.'
+complete -c git-journal -n "__fish_use_subcommand" -l no-pager -d 'Do not show the output within a pager.'
+complete -c git-journal -n "__fish_use_subcommand" -l reproducible -d 'Keep the output byte-stable for identical inputs, where the date of the unreleased section is taken from \'SOURCE_DATE_EPOCH\' or the commit time of HEAD and nothing is colored or paged.'
+complete -c git-journal -n "__fish_use_subcommand" -l first-parent -d 'Follow only the first parent of merge commits during parsing.'
+complete -c git-journal -n "__fish_use_subcommand" -l notify -d 'Announce the release given by \'--release\' via the configured \'webhook_url\', which requires the \'network\' feature.'
+complete -c git-journal -n "__fish_use_subcommand" -l github-actions -d 'Write the generated notes of \'--release\' or the whole log also as \'notes\' output and step summary of the GitHub Actions workflow.'
+complete -c git-journal -n "__fish_use_subcommand" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_use_subcommand" -s V -l version -d 'Prints version information'
+complete -c git-journal -n "__fish_use_subcommand" -f -a "bump" -d 'Print the diff setting the version of the \'Cargo.toml\' and its workspace members to the next version suggested by the unreleased changes.'
+complete -c git-journal -n "__fish_use_subcommand" -f -a "completions" -d 'Print the completion script for the given shell, which includes the tag names of the repository.'
+complete -c git-journal -n "__fish_use_subcommand" -f -a "highlights" -d 'Print the highlights of a release for posts on social media, which contain the entries of the \'highlight_categories\' by their priority within the \'highlight_count\' and \'highlight_width\'.'
+complete -c git-journal -n "__fish_use_subcommand" -f -a "pre-release" -d 'Write the changelog including the new version, which can be used as \'pre-release-hook\' of \'cargo release\'. The consumed changelog fragments are removed afterwards. Nothing will be written if the \'DRY_RUN\' environment variable is \'true\'.'
+complete -c git-journal -n "__fish_use_subcommand" -f -a "prepare" -d 'Prepare a commit message before the user can edit it.'
+complete -c git-journal -n "__fish_use_subcommand" -f -a "quality" -d 'Print a quality score of the commit hygiene for every release, based on the share of valid commit messages, of commits with bodies and references and on the quality of the summaries.'
+complete -c git-journal -n "__fish_use_subcommand" -f -a "rebase" -d 'Generate a todo list for \'git rebase -i\', which rewords all commits with an invalid commit message.'
+complete -c git-journal -n "__fish_use_subcommand" -f -a "schema" -d 'Print the JSON Schema of the structured output formats.'
+complete -c git-journal -n "__fish_use_subcommand" -f -a "serve" -d 'Serve the release notes via HTTP, where for example \'/changelog?from=v1.0.0&to=v2.0.0&format=json\' returns the changelog between two tags.'
+complete -c git-journal -n "__fish_use_subcommand" -f -a "setup" -d 'Creates all necessary git hooks and an initial configuration file. Shell completions for bash and fish will be available inside the current working directory.'
+complete -c git-journal -n "__fish_use_subcommand" -f -a "upgrade-guide" -d 'Print the skeleton of an upgrade guide, which lists the breaking changes between two releases together with their commits and bodies.'
+complete -c git-journal -n "__fish_use_subcommand" -f -a "verify" -d 'Verify the specified commit message or the commits of a range.'
+complete -c git-journal -n "__fish_use_subcommand" -f -a "help" -d 'Prints this message or the help of the given subcommand(s)'
+complete -c git-journal -n "__fish_seen_subcommand_from bump" -s w -l write -d 'Write the new version to the manifests as well.'
+complete -c git-journal -n "__fish_seen_subcommand_from bump" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_seen_subcommand_from bump" -s V -l version -d 'Prints version information'
+complete -c git-journal -n "__fish_seen_subcommand_from completions" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_seen_subcommand_from completions" -s V -l version -d 'Prints version information'
+complete -c git-journal -n "__fish_seen_subcommand_from highlights" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_seen_subcommand_from highlights" -s V -l version -d 'Prints version information'
+complete -c git-journal -n "__fish_seen_subcommand_from pre-release" -s o -l output -d 'The changelog file to be written.'
+complete -c git-journal -n "__fish_seen_subcommand_from pre-release" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_seen_subcommand_from pre-release" -s V -l version -d 'Prints version information'
+complete -c git-journal -n "__fish_seen_subcommand_from prepare" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_seen_subcommand_from prepare" -s V -l version -d 'Prints version information'
+complete -c git-journal -n "__fish_seen_subcommand_from quality" -l json -d 'Print the scores as JSON instead of a table.'
+complete -c git-journal -n "__fish_seen_subcommand_from quality" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_seen_subcommand_from quality" -s V -l version -d 'Prints version information'
+complete -c git-journal -n "__fish_seen_subcommand_from rebase" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_seen_subcommand_from rebase" -s V -l version -d 'Prints version information'
+complete -c git-journal -n "__fish_seen_subcommand_from schema" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_seen_subcommand_from schema" -s V -l version -d 'Prints version information'
+complete -c git-journal -n "__fish_seen_subcommand_from serve" -l http -d 'The address the HTTP server listens on.'
+complete -c git-journal -n "__fish_seen_subcommand_from serve" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_seen_subcommand_from serve" -s V -l version -d 'Prints version information'
+complete -c git-journal -n "__fish_seen_subcommand_from setup" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_seen_subcommand_from setup" -s V -l version -d 'Prints version information'
+complete -c git-journal -n "__fish_seen_subcommand_from upgrade-guide" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_seen_subcommand_from upgrade-guide" -s V -l version -d 'Prints version information'
+complete -c git-journal -n "__fish_seen_subcommand_from verify" -l range -d 'Verify the commit messages of the range instead, for example "origin/master..HEAD".'
+complete -c git-journal -n "__fish_seen_subcommand_from verify" -l annotations -d 'Print the invalid commits of \'--range\' as annotations of the CI, where \'github\' is detected within GitHub Actions and \'gitlab\' prints a Code Quality report.' -r -f -a "github gitlab"
+complete -c git-journal -n "__fish_seen_subcommand_from verify" -s f -l format -d 'Formats the commit message in place if it is valid, or already before the verification if \'wrap_body_lines\' is enabled.'
+complete -c git-journal -n "__fish_seen_subcommand_from verify" -l pre-merge -d 'Reject the transient \'fixup!\' and \'squash!\' commits, for example before merging a branch, regardless of the \'fixup_commits\' setting.'
+complete -c git-journal -n "__fish_seen_subcommand_from verify" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_seen_subcommand_from verify" -s V -l version -d 'Prints version information'
+complete -c git-journal -n "__fish_seen_subcommand_from help" -s h -l help -d 'Prints help information'
+complete -c git-journal -n "__fish_seen_subcommand_from help" -s V -l version -d 'Prints version information'
@@ -0,0 +1,59 @@
+#ifndef GITJOURNAL_H
+#define GITJOURNAL_H
+
+/* Generated via `cbindgen --config cbindgen.toml`, do not edit manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/*
+ * Parses a single commit message and returns the parsed commit as JSON. The
+ * configuration is read from the repository path or file `config_path`, or
+ * the default configuration is used if it is null.
+ *
+ * # Safety
+ * The arguments have to be null or valid null terminated strings.
+ */
+char *gj_parse_message(const char *message, const char *config_path);
+
+/*
+ * Verifies a single commit message with the configuration and rules of the
+ * repository at `path` and returns the reason why it is not valid, or an
+ * empty string if it is valid. Null is only returned if the repository could
+ * not be opened.
+ *
+ * # Safety
+ * The arguments have to be null or valid null terminated strings.
+ */
+char *gj_verify_message(const char *message, const char *path);
+
+/*
+ * Generates the changelog of the repository at `path` for the revision
+ * range, which defaults to "HEAD" if it is null, including all tags. The
+ * format is "md" for Markdown if it is null, or one of the export formats
+ * like "json".
+ *
+ * # Safety
+ * The arguments have to be null or valid null terminated strings.
+ */
+char *gj_generate_changelog(const char *path, const char *revision_range, const char *format);
+
+/*
+ * Returns the message of the last error within the current thread, or null
+ * if the last call succeeded. The message stays valid until the next call
+ * and must not be released.
+ */
+const char *gj_last_error(void);
+
+/*
+ * Releases a string returned by the library, where null is ignored.
+ *
+ * # Safety
+ * The string has to be returned by one of the functions of the library and
+ * must not be used afterwards.
+ */
+void gj_string_free(char *string);
+
+#endif /* GITJOURNAL_H */
@@ -0,0 +1,64 @@
+"""Smoke tests of the Python extension module against the shared library of
+the repository, which is built via `cargo build -p git-journal-ffi --features
+python` first:
+
+    python3 python/test_gitjournal.py
+"""
+
+import os
+import shutil
+import sys
+import tempfile
+import unittest
+
+ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+LIBRARY = {
+    "darwin": "libgitjournal_ffi.dylib",
+    "win32": "gitjournal_ffi.dll",
+}.get(sys.platform, "libgitjournal_ffi.so")
+MODULE = {"win32": "gitjournal.pyd"}.get(sys.platform, "gitjournal.so")
+
+# Python imports the extension module only by the name of the module
+MODULE_DIR = tempfile.mkdtemp()
+shutil.copy(
+    os.environ.get(
+        "GITJOURNAL_LIBRARY",
+        os.path.join(
+            os.environ.get("CARGO_TARGET_DIR", os.path.join(ROOT, "target")),
+            "debug",
+            LIBRARY,
+        ),
+    ),
+    os.path.join(MODULE_DIR, MODULE),
+)
+sys.path.insert(0, MODULE_DIR)
+
+import gitjournal  # noqa: E402
+
+
+class GitJournalTest(unittest.TestCase):
+    def test_parse(self):
+        commit = gitjournal.parse("Added some feature :ui:")
+        self.assertEqual(commit["summary"]["category"], "Added")
+        with self.assertRaises(gitjournal.GitJournalError):
+            gitjournal.parse("Invalid message")
+
+    def test_verify(self):
+        self.assertIsNone(gitjournal.verify("Added some feature", ROOT))
+        self.assertTrue(gitjournal.verify("Invalid message", ROOT))
+        with self.assertRaises(gitjournal.GitJournalError):
+            gitjournal.verify("Added some feature", os.devnull)
+
+    def test_generate(self):
+        changelog = gitjournal.generate(ROOT, format="json")
+        self.assertIn("releases", changelog)
+        self.assertIsInstance(gitjournal.generate(ROOT), str)
+        with self.assertRaises(gitjournal.GitJournalError):
+            gitjournal.generate(ROOT, format="unknown")
+
+
+if __name__ == "__main__":
+    try:
+        unittest.main()
+    finally:
+        shutil.rmtree(MODULE_DIR)
@@ -0,0 +1,58 @@
+//! Outputs for GitHub Actions workflows, which are appended to the files of
+//! the `GITHUB_OUTPUT` and `GITHUB_STEP_SUMMARY` environment variables.
+
+use crate::error::ErrorKind;
+use failure::{Error, ResultExt};
+use std::{fs::OpenOptions, io::Write};
+
+/// Returns the step output in the multi-line syntax of GitHub Actions, where
+/// the delimiter never occurs within the value.
+pub fn output(name: &str, value: &str) -> String {
+    let mut delimiter = "ghadelimiter".to_owned();
+    let mut index = 0;
+    while value.contains(&delimiter) {
+        index += 1;
+        delimiter = format!("ghadelimiter_{}", index);
+    }
+    format!("{}<<{}\n{}\n{}\n", name, delimiter, value, delimiter)
+}
+
+/// Appends the content to the file of a GitHub Actions environment variable.
+pub fn append(path: &str, content: &str) -> Result<(), Error> {
+    let file = || ErrorKind::File(path.to_owned());
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .context(file())?
+        .write_all(content.as_bytes())
+        .context(file())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs};
+
+    #[test]
+    fn step_outputs() {
+        assert_eq!(
+            output("notes", "# v1\n- [Added] a"),
+            "notes<<ghadelimiter\n# v1\n- [Added] a\nghadelimiter\n"
+        );
+        assert_eq!(
+            output("notes", "ghadelimiter ghadelimiter_1"),
+            "notes<<ghadelimiter_2\nghadelimiter ghadelimiter_1\n\
+             ghadelimiter_2\n"
+        );
+
+        let path = env::temp_dir().join("gitjournal_github_output");
+        let path = path.to_str().unwrap();
+        let _ = fs::remove_file(path);
+        append(path, "a=1\n").unwrap();
+        append(path, "b=2\n").unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "a=1\nb=2\n");
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,127 @@
+//! Annotations of the invalid commits found by `GitJournal::verify_range`,
+//! which surface the failures of a CI verification inline in the merge
+//! request or pull request.
+
+use crate::warnings::{Warning, Warnings};
+use serde_json::{json, Value};
+use std::env;
+
+/// Returns the annotation format of the current CI environment, which is
+/// "github" within GitHub Actions.
+pub fn detect() -> Option<&'static str> {
+    match env::var("GITHUB_ACTIONS") {
+        Ok(ref value) if value == "true" => Some("github"),
+        _ => None,
+    }
+}
+
+/// Returns the invalid commits as `::error` workflow commands of GitHub
+/// Actions, one per line. The annotations are placed at the first line of the
+/// first changed file of the commit, commits without changes are only
+/// annotated at the job.
+pub fn github(warnings: &Warnings) -> String {
+    let escape = |text: &str| {
+        text.replace('%', "%25")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    };
+    let property =
+        |text: &str| escape(text).replace(':', "%3A").replace(',', "%2C");
+    let mut annotations = String::new();
+    for (oid, reason, path) in invalid_commits(warnings) {
+        let location = match path {
+            Some(path) => format!("file={},line=1,", property(path)),
+            None => String::new(),
+        };
+        annotations.push_str(&format!(
+            "::error {}title={}::{}\n",
+            location,
+            property(&format!("Invalid commit message {:.7}", oid)),
+            escape(reason)
+        ));
+    }
+    annotations
+}
+
+/// Returns the invalid commits as GitLab Code Quality report, which are
+/// located at the first changed file of the commit or at the commit id for
+/// commits without changes.
+pub fn gitlab(warnings: &Warnings) -> String {
+    let issues = invalid_commits(warnings)
+        .map(|(oid, reason, path)| {
+            json!({
+                "description": format!("Invalid commit message: {}", reason),
+                "check_name": "git-journal",
+                "fingerprint": oid.to_string(),
+                "severity": "major",
+                "location": {
+                    "path": path.map_or_else(|| oid.clone(), str::to_owned),
+                    "lines": { "begin": 1 },
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+    format!("{:#}\n", Value::Array(issues))
+}
+
+/// Returns the ids, reasons and first changed files of the invalid commits
+/// within the warnings.
+fn invalid_commits(
+    warnings: &Warnings,
+) -> impl Iterator<Item = (String, &str, Option<&str>)> {
+    warnings.iter().filter_map(|warning| match *warning {
+        Warning::InvalidCommit {
+            ref oid,
+            ref reason,
+            ref path,
+        } => Some((oid.to_string(), reason.as_str(), path.as_deref())),
+        _ => None,
+    })
+}
+
+#[cfg(all(test, feature = "git"))]
+mod tests {
+    use super::*;
+    use crate::oid::Oid;
+
+    #[test]
+    fn annotations() {
+        let mut warnings = Warnings::default();
+        warnings.push(Warning::ShallowClone);
+        warnings.push(Warning::InvalidCommit {
+            oid: Oid::from_str("0123456789abcdef").unwrap(),
+            reason: "Summary: 100% invalid\nsyntax".to_owned(),
+            path: None,
+        });
+        assert_eq!(
+            github(&warnings),
+            "::error title=Invalid commit message 0123456::Summary: 100%25 \
+             invalid%0Asyntax\n"
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(&gitlab(&warnings)).unwrap()[0]
+                ["location"]["path"],
+            "0123456789abcdef000000000000000000000000"
+        );
+
+        let mut warnings = Warnings::default();
+        warnings.push(Warning::InvalidCommit {
+            oid: Oid::from_str("0123456789abcdef").unwrap(),
+            reason: "Summary: 100% invalid\nsyntax".to_owned(),
+            path: Some("src/a,b.rs".to_owned()),
+        });
+        assert_eq!(
+            github(&warnings),
+            "::error file=src/a%2Cb.rs,line=1,title=Invalid commit message \
+             0123456::Summary: 100%25 invalid%0Asyntax\n"
+        );
+        let report: Value = serde_json::from_str(&gitlab(&warnings)).unwrap();
+        assert_eq!(report.as_array().unwrap().len(), 1);
+        assert_eq!(
+            report[0]["description"],
+            "Invalid commit message: Summary: 100% invalid\nsyntax"
+        );
+        assert_eq!(report[0]["location"]["path"], "src/a,b.rs");
+        assert_eq!(report[0]["location"]["lines"]["begin"], 1);
+    }
+}
@@ -0,0 +1,106 @@
+//! Validation of the Azure Boards work items referenced like "AB#1234", which
+//! is only available if the `network` feature is enabled.
+
+use crate::{
+    encoding::base64, http::Client, links::RE_WORK_ITEM, parser::ParsedTag,
+};
+use failure::Error;
+use log::{info, warn};
+use std::{collections::BTreeSet, env, time::Duration};
+
+/// The environment variable containing the Azure DevOps personal access token.
+static ENV_TOKEN: &str = "AZURE_DEVOPS_TOKEN";
+
+/// Returns the references of the work items within the summaries and bodies
+/// of the commits, which could not be found via the API of the
+/// `azure_boards_url`. Nothing is validated without an API token and work
+/// items which could not be fetched are skipped with a warning.
+pub fn validate(
+    result: &[ParsedTag],
+    url: &str,
+    client: &Client,
+) -> Vec<String> {
+    match env::var(ENV_TOKEN) {
+        Ok(token) => {
+            validate_with(result, |id| fetch_exists(client, url, &token, id))
+        }
+        Err(_) => vec![],
+    }
+}
+
+/// Validates the work items via the `exists` function.
+fn validate_with<F>(result: &[ParsedTag], mut exists: F) -> Vec<String>
+where
+    F: FnMut(&str) -> Result<bool, Error>,
+{
+    let ids = result
+        .iter()
+        .flat_map(|tag| &tag.commits)
+        .flat_map(|commit| commit.texts())
+        .flat_map(|text| RE_WORK_ITEM.captures_iter(text))
+        .map(|captures| captures[1].to_owned())
+        .collect::<BTreeSet<_>>();
+    let mut unknown = vec![];
+    for id in &ids {
+        match exists(id) {
+            Ok(true) => {}
+            Ok(false) => unknown.push(format!("AB#{}", id)),
+            Err(e) => warn!("Could not fetch work item 'AB#{}': {}", id, e),
+        }
+    }
+    info!("Validated {} Azure Boards work items.", ids.len());
+    unknown
+}
+
+/// Checks the existence of a single work item via the Azure DevOps REST API.
+fn fetch_exists(
+    client: &Client,
+    url: &str,
+    token: &str,
+    id: &str,
+) -> Result<bool, Error> {
+    let request = ureq::get(&format!(
+        "{}/_apis/wit/workitems/{}",
+        url.trim_end_matches('/'),
+        id
+    ))
+    .query("api-version", "7.0")
+    .query("fields", "System.Id")
+    .set(
+        "Authorization",
+        &format!("Basic {}", base64(format!(":{}", token).as_bytes())),
+    )
+    .timeout(Duration::from_secs(10));
+    Ok(client.get_json(request)?.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use failure::bail;
+
+    #[test]
+    fn validate_work_items() {
+        let result = vec![ParsedTag::from_messages(
+            "v1",
+            &[
+                "Added a AB#1",
+                "Fixed b\n\n- Fixed AB#2 and AB#1",
+                "Fixed c AB#3",
+            ],
+            &Config::new(),
+        )];
+        let mut fetched = vec![];
+        let unknown = validate_with(&result, |id| {
+            fetched.push(id.to_owned());
+            match id {
+                "2" => Ok(false),
+                "3" => bail!("Unauthorized"),
+                _ => Ok(true),
+            }
+        });
+        assert_eq!(fetched, vec!["1", "2", "3"]);
+        assert_eq!(unknown, vec!["AB#2"]);
+    }
+}
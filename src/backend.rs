@@ -0,0 +1,51 @@
+//! Custom output formats, which can be implemented outside of the crate and
+//! registered at a `GitJournal` to be used like the built-in export formats.
+
+use crate::journal::ParsedJournal;
+use failure::Error;
+
+/// A renderer of the parsing results into a custom format, e.g. Confluence
+/// wiki markup or the Atlassian Document Format. Registered backends are
+/// dispatched via their name by `GitJournal::export`, where they take
+/// precedence over the built-in formats of the same name.
+///
+/// # Examples
+///
+/// ```
+/// use failure::Error;
+/// use gitjournal::{GitJournal, OutputBackend, ParseOptions, ParsedJournal};
+///
+/// struct Confluence;
+///
+/// impl OutputBackend for Confluence {
+///     fn name(&self) -> &str {
+///         "confluence"
+///     }
+///
+///     fn render(&self, journal: &ParsedJournal) -> Result<String, Error> {
+///         let mut output = String::new();
+///         for release in journal.releases() {
+///             output.push_str(&format!("h2. {}\n", release.name));
+///             for commit in &release.commits {
+///                 output.push_str(&format!("* {}\n", commit.summary.text));
+///             }
+///         }
+///         Ok(output)
+///     }
+/// }
+///
+/// let mut journal = GitJournal::new(".").unwrap();
+/// journal.register_backend(Box::new(Confluence));
+/// journal.parse_log(&ParseOptions::new("HEAD"));
+/// println!("{}", journal.export("confluence").unwrap());
+/// ```
+pub trait OutputBackend: Send + Sync {
+    /// The name of the format, which selects the backend for the export.
+    fn name(&self) -> &str;
+
+    /// Renders the parsing results into the format.
+    ///
+    /// # Errors
+    /// If the results could not be rendered.
+    fn render(&self, journal: &ParsedJournal) -> Result<String, Error>;
+}
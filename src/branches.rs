@@ -0,0 +1,286 @@
+//! Support for maintenance branches like `release/1.x`, where fixes are
+//! cherry-picked between the branches and detected via their patch ids.
+
+use crate::parser::{ParsedTag, RE_MERGE_REQUEST};
+use failure::Error;
+use git2::{Commit, DiffFormat, Oid, Repository};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{
+    collections::{
+        hash_map::{DefaultHasher, Entry},
+        HashMap,
+    },
+    hash::{Hash, Hasher},
+};
+
+lazy_static! {
+    static ref RE_MERGE: Regex = Regex::new(concat!(
+        r"^Merge (?:branch '([^']+)'",
+        r"|remote-tracking branch '[^/']+/([^']+)'",
+        r"|pull request #\d+ from [^/\s]+/(\S+))"
+    ))
+    .unwrap();
+}
+
+/// Checks if the name matches the pattern, where `*` matches any number of
+/// characters, e.g. "release/*".
+pub fn matches(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let mut rest = match name.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let parts = parts.collect::<Vec<_>>();
+    for (index, part) in parts.iter().enumerate() {
+        if index == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+    rest.is_empty()
+}
+
+/// Returns an identifier of the changes introduced by the commit, which stays
+/// the same if the commit is cherry-picked. Like `git patch-id`, the
+/// whitespace and the line numbers of the diff are ignored. Merge commits and
+/// commits without changes have no patch id.
+pub fn patch_id(
+    repo: &Repository,
+    commit: &Commit,
+) -> Result<Option<u64>, Error> {
+    if commit.parent_count() > 1 {
+        return Ok(None);
+    }
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let diff = repo.diff_tree_to_tree(
+        parent_tree.as_ref(),
+        Some(&commit.tree()?),
+        None,
+    )?;
+    let mut hasher = DefaultHasher::new();
+    let mut changed = false;
+    diff.print(DiffFormat::Patch, |delta, _, line| {
+        let origin = line.origin();
+        if origin == '+' || origin == '-' {
+            delta.new_file().path().hash(&mut hasher);
+            origin.hash(&mut hasher);
+            line.content()
+                .iter()
+                .filter(|c| !c.is_ascii_whitespace())
+                .for_each(|c| c.hash(&mut hasher));
+            changed = true;
+        }
+        true
+    })?;
+    Ok(if changed { Some(hasher.finish()) } else { None })
+}
+
+/// Finds the commits whose changes have been cherry-picked from an older
+/// commit within the given ones. Returns the cherry-picks together with the
+/// oldest commit containing the same change.
+pub fn find_cherry_picks(
+    repo: &Repository,
+    oids: &[Oid],
+) -> Result<HashMap<Oid, Oid>, Error> {
+    let mut commits = vec![];
+    for oid in oids {
+        let commit = repo.find_commit(*oid)?;
+        commits.push((commit.time().seconds(), commit));
+    }
+    commits.sort_by_key(|(time, _)| *time);
+
+    let mut originals: HashMap<u64, Oid> = HashMap::new();
+    let mut cherry_picks = HashMap::new();
+    for (_, commit) in commits {
+        if let Some(id) = patch_id(repo, &commit)? {
+            match originals.entry(id) {
+                Entry::Occupied(entry) => {
+                    cherry_picks.insert(commit.id(), *entry.get());
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(commit.id());
+                }
+            }
+        }
+    }
+    Ok(cherry_picks)
+}
+
+/// Returns the name of the merged branch of a merge commit summary, e.g.
+/// "fix/login" for "Merge branch 'fix/login'" or "Merge pull request #1 from
+/// user/fix/login".
+pub fn merged_branch(summary: &str) -> Option<&str> {
+    RE_MERGE
+        .captures(summary)?
+        .iter()
+        .skip(1)
+        .flatten()
+        .next()
+        .map(|name| name.as_str())
+}
+
+/// Finds the branches of the commits, which have been merged via one of the
+/// given merge commits. Commits of nested branches belong to the innermost
+/// merged branch.
+pub fn find_merged_branches(
+    repo: &Repository,
+    oids: &[Oid],
+) -> Result<HashMap<Oid, String>, Error> {
+    let mut branches = HashMap::new();
+    for commit in find_merges(repo, oids)? {
+        let name = match commit.summary().and_then(merged_branch) {
+            Some(name) => name.to_owned(),
+            None => continue,
+        };
+        for oid in merged_commits(repo, &commit)? {
+            branches.entry(oid).or_insert_with(|| name.clone());
+        }
+    }
+    Ok(branches)
+}
+
+/// Finds the GitLab merge requests of the commits, which are referenced like
+/// "See merge request group/project!123" within one of the given merge
+/// commits. The merge commit itself belongs to its merge request as well.
+pub fn find_merge_requests(
+    repo: &Repository,
+    oids: &[Oid],
+) -> Result<HashMap<Oid, u64>, Error> {
+    let mut merge_requests = HashMap::new();
+    for commit in find_merges(repo, oids)? {
+        let number = match commit
+            .message()
+            .and_then(|message| RE_MERGE_REQUEST.captures(message))
+            .and_then(|cap| cap[1].parse().ok())
+        {
+            Some(number) => number,
+            None => continue,
+        };
+        merge_requests.entry(commit.id()).or_insert(number);
+        for oid in merged_commits(repo, &commit)? {
+            merge_requests.entry(oid).or_insert(number);
+        }
+    }
+    Ok(merge_requests)
+}
+
+/// Returns the merge commits of the given ones, sorted from the oldest to the
+/// newest one, so that nested branches are processed first.
+fn find_merges<'a>(
+    repo: &'a Repository,
+    oids: &[Oid],
+) -> Result<Vec<Commit<'a>>, Error> {
+    let mut merges = vec![];
+    for oid in oids {
+        let commit = repo.find_commit(*oid)?;
+        if commit.parent_count() > 1 {
+            merges.push(commit);
+        }
+    }
+    merges.sort_by_key(|commit| commit.time().seconds());
+    Ok(merges)
+}
+
+/// Returns the commits of the branch merged via the merge commit.
+fn merged_commits(
+    repo: &Repository,
+    merge: &Commit,
+) -> Result<Vec<Oid>, Error> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(merge.parent_id(1)?)?;
+    revwalk.hide(merge.parent_id(0)?)?;
+    Ok(revwalk.collect::<Result<_, _>>()?)
+}
+
+/// Marks the commits which are cherry-picked between the parsed branches.
+/// Every commit gets the names of the other branches which contain the same
+/// change as a different commit.
+pub fn mark_cherry_picks(
+    repo: &Repository,
+    branches: &mut [(String, Vec<ParsedTag>)],
+) -> Result<(), Error> {
+    let mut patch_ids: HashMap<Oid, Option<u64>> = HashMap::new();
+    let mut changes: HashMap<u64, Vec<(&str, Oid)>> = HashMap::new();
+    for (branch, result) in branches.iter() {
+        for oid in result.iter().flat_map(|t| &t.commits).filter_map(|c| c.oid)
+        {
+            let id = match patch_ids.entry(oid) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => {
+                    *entry.insert(patch_id(repo, &repo.find_commit(oid)?)?)
+                }
+            };
+            if let Some(id) = id {
+                changes.entry(id).or_default().push((branch, oid));
+            }
+        }
+    }
+
+    let mut cherry_picks: HashMap<Oid, Vec<String>> = HashMap::new();
+    for commits in changes.values() {
+        for (_, oid) in commits {
+            let mut others = commits
+                .iter()
+                .filter(|(_, other)| other != oid)
+                .map(|(branch, _)| (*branch).to_owned())
+                .collect::<Vec<_>>();
+            others.sort();
+            others.dedup();
+            if !others.is_empty() {
+                cherry_picks.insert(*oid, others);
+            }
+        }
+    }
+    for (_, result) in branches.iter_mut() {
+        for commit in result.iter_mut().flat_map(|t| &mut t.commits) {
+            if let Some(others) = commit.oid.and_then(|o| cherry_picks.get(&o))
+            {
+                commit.cherry_picks = others.clone();
+                commit.summary.backported = true;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_branch_names() {
+        assert!(matches("release/*", "release/1.x"));
+        assert!(matches("release/*.x", "release/2.x"));
+        assert!(matches("*", "master"));
+        assert!(matches("master", "master"));
+        assert!(!matches("release/*", "feature/release"));
+        assert!(!matches("release/*.x", "release/2.0"));
+        assert!(!matches("master", "master2"));
+    }
+
+    #[test]
+    fn merged_branch_names() {
+        assert_eq!(merged_branch("Merge branch 'fix/a'"), Some("fix/a"));
+        assert_eq!(
+            merged_branch("Merge branch 'fix/a' into develop"),
+            Some("fix/a")
+        );
+        assert_eq!(
+            merged_branch("Merge remote-tracking branch 'origin/feature/b'"),
+            Some("feature/b")
+        );
+        assert_eq!(
+            merged_branch("Merge pull request #12 from user/feature/c"),
+            Some("feature/c")
+        );
+        assert_eq!(merged_branch("Merged the branch"), None);
+    }
+}
@@ -0,0 +1,182 @@
+//! Editing of the versions within Cargo manifests, which keeps the remaining
+//! formatting of the files untouched.
+
+use failure::Error;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::Value;
+
+/// A manifest whose package version will be changed.
+#[derive(Debug, PartialEq)]
+pub struct Edit {
+    /// The path to the `Cargo.toml`
+    pub path: PathBuf,
+
+    /// The path shown within the diff, relative to the repository
+    pub name: String,
+
+    /// The current content of the manifest
+    pub old: String,
+
+    /// The content containing the new version
+    pub new: String,
+}
+
+/// Returns the paths of the workspace members declared by the manifest,
+/// where trailing `*` wildcards like "crates/*" are expanded.
+pub fn members(root: &Path, manifest: &str) -> Result<Vec<PathBuf>, Error> {
+    let manifest: Value = toml::from_str(manifest)?;
+    let members = manifest
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(Value::as_array)
+        .map(|m| m.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let mut paths = vec![];
+    for member in members {
+        match member.strip_suffix('*') {
+            Some(prefix) => {
+                let mut dirs = fs::read_dir(root.join(prefix))?
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| path.join("Cargo.toml").is_file())
+                    .collect::<Vec<_>>();
+                dirs.sort();
+                paths.extend(dirs);
+            }
+            None => paths.push(root.join(member)),
+        }
+    }
+    Ok(paths)
+}
+
+/// Returns the version of the `[package]` table, or the one of the
+/// `[workspace.package]` table if it is inherited via
+/// `version.workspace = true` or the manifest contains no package.
+pub fn version(manifest: &Value) -> Option<&str> {
+    match manifest.get("package").and_then(|p| p.get("version")) {
+        Some(Value::String(version)) => Some(version.as_str()),
+        Some(Value::Table(_)) | None => manifest
+            .get("workspace")
+            .and_then(|w| w.get("package"))
+            .and_then(|p| p.get("version"))
+            .and_then(Value::as_str),
+        _ => None,
+    }
+}
+
+/// Replaces the version of the `[package]` and `[workspace.package]` tables.
+/// Inherited versions like `version.workspace = true` stay untouched.
+/// Returns `None` if no version has been replaced.
+pub fn set_version(manifest: &str, version: &str) -> Option<String> {
+    let mut in_package = false;
+    let mut changed = false;
+    let mut lines = vec![];
+    for line in manifest.split('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_package =
+                trimmed == "[package]" || trimmed == "[workspace.package]";
+        }
+        let value = trimmed
+            .strip_prefix("version")
+            .map(str::trim_start)
+            .and_then(|rest| rest.strip_prefix('='))
+            .map(str::trim);
+        match value {
+            Some(value) if in_package && value.starts_with('"') => {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                let comment = value[1..]
+                    .find('"')
+                    .map(|end| &value[end + 2..])
+                    .unwrap_or("");
+                lines.push(format!(
+                    "{}version = \"{}\"{}",
+                    indent, version, comment
+                ));
+                changed = true;
+            }
+            _ => lines.push(line.to_owned()),
+        }
+    }
+    if changed {
+        Some(lines.join("\n"))
+    } else {
+        None
+    }
+}
+
+/// Returns the changes of the edit as unified diff without context lines.
+pub fn diff(edit: &Edit) -> String {
+    let mut diff = format!("--- a/{}\n+++ b/{}\n", edit.name, edit.name);
+    let old = edit.old.split('\n');
+    let new = edit.new.split('\n');
+    for (index, (old, new)) in old.zip(new).enumerate() {
+        if old != new {
+            let line = index + 1;
+            diff.push_str(&format!(
+                "@@ -{} +{} @@\n-{}\n+{}\n",
+                line, line, old, new
+            ));
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static MANIFEST: &str = "[package]\nname = \"a\"\nversion = \"1.0.0\" \
+                             # the version\n\n[dependencies]\n\
+                             b = { version = \"1.0\" }\nversion = \"2\"\n";
+
+    #[test]
+    fn set_manifest_version() {
+        assert_eq!(
+            set_version(MANIFEST, "1.1.0").unwrap(),
+            MANIFEST.replace("\"1.0.0\"", "\"1.1.0\"")
+        );
+        assert_eq!(
+            set_version("[workspace.package]\n  version=\"1.0.0\"", "2.0.0"),
+            Some("[workspace.package]\n  version = \"2.0.0\"".to_owned())
+        );
+        assert_eq!(
+            set_version("[package]\nversion.workspace = true\n", "2.0.0"),
+            None
+        );
+    }
+
+    #[test]
+    fn manifest_version() {
+        let version_of = |manifest: &str| {
+            version(&toml::from_str(manifest).unwrap()).map(str::to_owned)
+        };
+        assert_eq!(version_of(MANIFEST), Some("1.0.0".to_owned()));
+        assert_eq!(
+            version_of(
+                "[package]\nversion.workspace = true\n\
+                 [workspace.package]\nversion = \"2.0.0\"\n"
+            ),
+            Some("2.0.0".to_owned())
+        );
+        assert_eq!(version_of("[package]\nversion.workspace = true\n"), None);
+    }
+
+    #[test]
+    fn diff_of_edit() {
+        let edit = Edit {
+            path: PathBuf::from("Cargo.toml"),
+            name: "Cargo.toml".to_owned(),
+            old: MANIFEST.to_owned(),
+            new: set_version(MANIFEST, "1.1.0").unwrap(),
+        };
+        assert_eq!(
+            diff(&edit),
+            "--- a/Cargo.toml\n+++ b/Cargo.toml\n@@ -3 +3 @@\n\
+             -version = \"1.0.0\" # the version\n\
+             +version = \"1.1.0\" # the version\n"
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Queries for the changes introduced by single commits, e.g. to find the
+//! releases which touched a file.
+
+use failure::Error;
+use git2::{Commit, Diff, DiffFormat, Repository};
+
+/// Returns the changes of the commit compared to its first parent.
+fn diff<'a>(repo: &'a Repository, commit: &Commit) -> Result<Diff<'a>, Error> {
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    Ok(repo.diff_tree_to_tree(
+        parent_tree.as_ref(),
+        Some(&commit.tree()?),
+        None,
+    )?)
+}
+
+/// Returns the path of the first file changed by the commit, e.g. to locate
+/// the annotations of an invalid commit message.
+pub fn first_changed_path(
+    repo: &Repository,
+    commit: &Commit,
+) -> Result<Option<String>, Error> {
+    Ok(diff(repo, commit)?.deltas().find_map(|delta| {
+        delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .map(|path| path.to_string_lossy().into_owned())
+    }))
+}
+
+/// Returns whether the commit touched the given query, which is either a file
+/// or directory path relative to the repository root, or any other string
+/// like a function name which is part of the added or removed lines. Merge
+/// commits are compared to their first parent.
+pub fn touches(
+    repo: &Repository,
+    commit: &Commit,
+    query: &str,
+) -> Result<bool, Error> {
+    let diff = diff(repo, commit)?;
+    let path_matches = diff.deltas().any(|delta| {
+        [delta.old_file().path(), delta.new_file().path()]
+            .iter()
+            .filter_map(|path| *path)
+            .any(|path| path.starts_with(query))
+    });
+    if path_matches {
+        return Ok(true);
+    }
+    let mut found = false;
+    diff.print(DiffFormat::Patch, |_, _, line| {
+        let origin = line.origin();
+        if (origin == '+' || origin == '-')
+            && String::from_utf8_lossy(line.content()).contains(query)
+        {
+            found = true;
+        }
+        !found
+    })
+    .or_else(|e| if found { Ok(()) } else { Err(e) })?;
+    Ok(found)
+}
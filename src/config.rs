@@ -3,48 +3,445 @@
 
 use toml;
 
-use failure::{format_err, Error};
+use crate::error::ErrorKind;
+use failure::{format_err, Error, ResultExt};
 use log::info;
 use serde_derive::{Deserialize, Serialize};
-use std::{fs::File, io::prelude::*, path::PathBuf};
+use std::{collections::BTreeMap, fs::File, io::prelude::*, path::PathBuf};
+
+/// The file name of the configuration within the repository.
+pub(crate) static CONFIG_FILE_NAME: &str = ".gitjournal.toml";
 
 /// The configuration structure for git-journal.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Config {
+    /// The Azure Boards project for linking work item references like
+    /// "AB#1234", e.g. "https://dev.azure.com/organization/project"
+    pub azure_boards_url: Option<String>,
+
+    /// The directory of the cached rendered logs relative to the repository,
+    /// e.g. ".git/gitjournal-cache", which are reused as long as the history
+    /// and the configuration are unchanged. The responses of web services
+    /// are cached there as well as fallback if they are not reachable.
+    pub cache_dir: Option<String>,
+
+    /// Name the unreleased section after the version within the `Cargo.toml`
+    /// of the repository, where "{version}" will be replaced, e.g.
+    /// "v{version}"
+    pub cargo_version: Option<String>,
+
     /// Specifies the available categories for the commit message
     pub categories: Vec<String>,
 
     /// Set the characters where the categories are wrapped in
     pub category_delimiters: Vec<String>,
 
+    /// The display order of the categories, e.g. `["Security", "Fixed"]`,
+    /// where unlisted categories are printed afterwards
+    #[serde(default)]
+    pub category_order: Vec<String>,
+
+    /// Text which will be printed once before the generated log, e.g. an
+    /// introduction to the changelog
+    pub changelog_header: Option<String>,
+
+    /// Text which will be printed once after the generated log, e.g. license
+    /// information or links
+    pub changelog_footer: Option<String>,
+
     /// Set to false if the output should not be colored
     pub colored_output: bool,
 
+    /// The format of the release dates in `strftime` notation, e.g. "%Y-%m-%d"
+    #[serde(default = "Config::get_default_date_format")]
+    pub date_format: String,
+
+    /// Report commits whose change has been cherry-picked within the parsed
+    /// range only once, identified via their patch id
+    #[serde(default)]
+    pub deduplicate_cherry_picks: bool,
+
     /// Specifies the default template. Will be used for tag validation and
     /// printing.
     pub default_template: Option<String>,
 
+    /// The sender of the release announcements, e.g.
+    /// "Release Bot <bot@example.com>"
+    pub email_from: Option<String>,
+
+    /// Add an HTML alternative to the plain text release announcements
+    #[serde(default)]
+    pub email_html: bool,
+
+    /// The recipients of the release announcements, e.g. "team@example.com"
+    pub email_to: Option<String>,
+
+    /// The handling of releases without any valid commit, which are omitted
+    /// from the output, either "warn" (default), "error" or "off"
+    #[serde(default = "Config::get_default_empty_releases")]
+    pub empty_releases: String,
+
+    /// The placeholder printed for template sections without any entries,
+    /// e.g. "None.". Empty sections are omitted if not set.
+    pub empty_section_placeholder: Option<String>,
+
     /// Show or hide the debug messages like `[OKAY] ...` or `[INFO] ...`
     pub enable_debug: bool,
 
+    /// Regular expressions for the commit summaries to be excluded from the
+    /// parsing results, e.g. "^fixup!"
+    #[serde(default = "Config::get_default_exclude_patterns")]
+    pub exclude_patterns: Vec<String>,
+
     /// Excluded tags in an array, e.g. "internal"
     pub excluded_commit_tags: Vec<String>,
 
     /// Enable or disable the output and accumulation of commit footers
     pub enable_footers: bool,
 
+    /// The remote to fetch the tags from if `fetch_tags` is enabled
+    #[serde(default = "Config::get_default_fetch_remote")]
+    pub fetch_remote: String,
+
+    /// Fetch the tags from the `fetch_remote` before parsing, which avoids
+    /// missing recently pushed releases on stale checkouts
+    #[serde(default)]
+    pub fetch_tags: bool,
+
+    /// Follow only the first parent of merge commits during parsing, which
+    /// skips the commits of merged branches
+    #[serde(default)]
+    pub first_parent: bool,
+
+    /// The verification of transient `fixup!`, `squash!` and `amend!`
+    /// commits, which are either accepted without further checks by "allow"
+    /// (default) or rejected by "reject", e.g. before merging a branch. With
+    /// "allow" they also pass `verify --range` unless `--pre-merge` is given
+    #[serde(default = "Config::get_default_fixup_commits")]
+    pub fixup_commits: String,
+
+    /// Fold commits with identical summaries into a single entry, which
+    /// contains the count or the commit hashes if `show_commit_hash` is set
+    #[serde(default)]
+    pub fold_duplicates: bool,
+
+    /// Fold pre-release tags like `v1.2.0-rc1` into the section of their final
+    /// release `v1.2.0` if it already exists
+    #[serde(default)]
+    pub fold_prereleases: bool,
+
+    /// A directory with changelog fragments like `123.fixed.md`, which are
+    /// added to the unreleased section and removed by `pre-release`
+    pub fragments_dir: Option<String>,
+
+    /// A link to the full log, which will be added to the line summarizing
+    /// the entries exceeding `max_entries_per_category`
+    pub full_log_url: Option<String>,
+
+    /// Group the entries of every release by their first tag with this prefix
+    /// into subsections, e.g. "epic/" for tags like `:epic/auth:`
+    pub group_tag_prefix: Option<String>,
+
+    /// The categories of the entries selected as highlights of a release by
+    /// their priority, entries of other categories are never highlighted
+    #[serde(default = "Config::get_default_highlight_categories")]
+    pub highlight_categories: Vec<String>,
+
+    /// The maximum number of entries within the highlights of a release
+    #[serde(default = "Config::get_default_highlight_count")]
+    pub highlight_count: usize,
+
+    /// The tag which pins an entry to the "Highlights" at the top of its
+    /// release like `:highlight:`, where an empty tag disables the pinning.
+    /// Tagged list items pin their entry unless the output is compact, and
+    /// pinned entries count towards the `max_entries_per_category`
+    #[serde(default = "Config::get_default_highlight_tag")]
+    pub highlight_tag: String,
+
+    /// The maximum width of the highlights of a release, e.g. the character
+    /// limit of a post on social media
+    #[serde(default = "Config::get_default_highlight_width")]
+    pub highlight_width: usize,
+
+    /// The number of retries of rate limited or failed requests to web
+    /// services like JIRA, with an exponential backoff starting at 500ms
+    #[serde(default = "Config::get_default_http_retries")]
+    pub http_retries: u32,
+
+    /// The URL of a JIRA instance, e.g. "https://company.atlassian.net", to
+    /// fetch the title and status of the issues referenced by the commit
+    /// prefixes. Requires the `network` feature, the credentials are taken
+    /// from the `JIRA_USER` and `JIRA_TOKEN` environment variables.
+    pub jira_url: Option<String>,
+
+    /// The categories of breaking changes, which lead to a major version bump
+    /// like the `:breaking:` tag
+    #[serde(default = "Config::get_default_major_categories")]
+    pub major_categories: Vec<String>,
+
+    /// Restrict the output to the releases of a single major version, e.g. 1
+    pub major_version: Option<u64>,
+
+    /// The categories of new features, which lead to a minor version bump
+    #[serde(default = "Config::get_default_minor_categories")]
+    pub minor_categories: Vec<String>,
+
+    /// The minimum semantic version of the releases to be printed, e.g.
+    /// "1.0.0"
+    pub min_version: Option<String>,
+
+    /// A file with manual entries for the unreleased section, which do not
+    /// correspond to any commit, like `entries = ["Added the docs site"]`
+    pub manual_entries_file: Option<String>,
+
+    /// The maximum width of the body lines during the verification, where
+    /// comments and single words like URLs are skipped and 0 disables the
+    /// check
+    #[serde(default = "Config::get_default_max_body_line_length")]
+    pub max_body_line_length: usize,
+
+    /// The maximum number of entries per category within a release, where
+    /// the remaining ones will be summarized in a single line
+    pub max_entries_per_category: Option<usize>,
+
+    /// The GitLab merge requests referenced like "See merge request
+    /// group/project!123" within merge commits, which are either ignored by
+    /// "off" (default), assigned to the merged commits by "link" or
+    /// additionally fetched for their title and description by "fetch",
+    /// which requires the `network` feature
+    #[serde(default = "Config::get_default_merge_requests")]
+    pub merge_requests: String,
+
+    /// The width at which the paragraphs are wrapped when formatting a commit
+    /// message
+    #[serde(default = "Config::get_default_message_width")]
+    pub message_width: usize,
+
+    /// How multiple release tags on the same commit are printed, either
+    /// "merged" (default) into a single section like "v1.0.0 / v1.0.1" or
+    /// "separate", where the newer tags get their own empty sections
+    #[serde(default = "Config::get_default_multiple_tags")]
+    pub multiple_tags: String,
+
+    /// Skip all requests to web services like JIRA and use only the
+    /// responses cached within the `cache_dir`
+    #[serde(default)]
+    pub offline: bool,
+
+    /// The command of the pager if `use_pager` is set, which defaults to the
+    /// `core.pager` of git like `git log`, where `$GIT_PAGER` takes precedence
+    pub pager: Option<String>,
+
+    /// Parse a leading gitmoji of the commit summary like "✨" or
+    /// ":sparkles:" into its category, e.g. "Added"
+    #[serde(default)]
+    pub parse_gitmoji: bool,
+
+    /// The hosting provider of the `repository_url` for the shapes of the
+    /// links, one of "github", "gitlab", "bitbucket", "bitbucket-server" or
+    /// "gitea" (alias "forgejo"), which is detected from the host if unset
+    pub provider: Option<String>,
+
+    /// The link to a pull request without its number, e.g.
+    /// "https://github.com/user/project/pull/", which defaults to the one of
+    /// the `provider`
+    pub pull_request_url: Option<String>,
+
+    /// Regular expressions for sensitive content like passwords or internal
+    /// host names, which will be replaced by "[redacted]" within the texts
+    /// and footers of all entries and the tag titles before any output, even
+    /// after the entry hooks
+    #[serde(default)]
+    pub redact: Vec<String>,
+
+    /// Patterns of branches like "wip/*", where `*` matches any characters,
+    /// on which the verification of new commit messages is relaxed
+    #[serde(default)]
+    pub relaxed_branches: Vec<String>,
+
+    /// The verification on the `relaxed_branches`, where invalid commit
+    /// messages are either only reported by "warn" (default) or not verified
+    /// at all by "off"
+    #[serde(default = "Config::get_default_relaxed_verification")]
+    pub relaxed_verification: String,
+
+    /// The web interface of the repository for linking the commit hashes,
+    /// e.g. "https://github.com/user/project", which is derived from the
+    /// `fetch_remote` if only the `provider` is set
+    pub repository_url: Option<String>,
+
+    /// Keep the output byte-stable for identical inputs, where the date of
+    /// the unreleased section is taken from `SOURCE_DATE_EPOCH` or the
+    /// commit time of `HEAD`, the dates are printed in UTC and the command
+    /// line prints without colors
+    #[serde(default)]
+    pub reproducible: bool,
+
+    /// The sorting of the commits while walking through the history, a
+    /// combination of "time" (default), "topological" and "reverse"
+    #[serde(default = "Config::get_default_revwalk_sorting")]
+    pub revwalk_sorting: Vec<String>,
+
+    /// The maximum display width of the summaries in the short output, longer
+    /// summaries will be truncated with an ellipsis
+    pub short_summary_width: Option<usize>,
+
+    /// Wrap the summaries in the short output at `short_summary_width`
+    /// instead of truncating them
+    #[serde(default)]
+    pub short_summary_wrap: bool,
+
+    /// Annotate the entries whose change has been cherry-picked onto another
+    /// commit with "(backported)"
+    #[serde(default)]
+    pub show_backports: bool,
+
     /// Show or hide the commit hash for every entry
     pub show_commit_hash: bool,
 
     /// Show or hide the commit message prefix, e.g. JIRA-1234
     pub show_prefix: bool,
 
+    /// Show or hide the pull request numbers taken from summary suffixes
+    /// like "(#1234)", which are linked if `pull_request_url` is set
+    #[serde(default = "Config::get_default_show_pull_requests")]
+    pub show_pull_requests: bool,
+
+    /// Show the title of annotated tags after the release name, e.g.
+    /// "v1.3.0 — The Speedy Release"
+    #[serde(default)]
+    pub show_tag_titles: bool,
+
+    /// Sign the changelog written to a file with GPG, either "off" (default),
+    /// "detached" for an ASCII armored signature next to it like
+    /// "CHANGELOG.md.asc" or "trailer" for a signature block after the
+    /// written content. Both sign the whole file, even if the output is
+    /// appended to it. Structured exports like JSON are always signed
+    /// detached, since a trailer would make them invalid
+    #[serde(default = "Config::get_default_sign_output")]
+    pub sign_output: String,
+
+    /// The GPG key used for signing the output, which defaults to the
+    /// `user.signingkey` of the git configuration
+    pub signing_key: Option<String>,
+
+    /// The `Signed-off-by: Name <email>` trailer of the Developer Certificate
+    /// of Origin during the verification, either "off" (default), "required"
+    /// or "author", where the trailer has to match the author
+    #[serde(default = "Config::get_default_signoff")]
+    pub signoff: String,
+
     /// Sort the commits during the output by "date" (default) or "name"
     pub sort_by: String,
 
+    /// The spell check of the summaries and bodies during the verification,
+    /// either "warn" (default), "error" or "off", which requires the
+    /// `spellcheck` feature
+    #[serde(default = "Config::get_default_spellcheck")]
+    pub spellcheck: String,
+
+    /// A word list with one word per line used for the spell check
+    #[serde(default = "Config::get_default_spellcheck_dictionary")]
+    pub spellcheck_dictionary: String,
+
+    /// Additional words of the project for the spell check, e.g. "serde"
+    #[serde(default)]
+    pub spellcheck_words: Vec<String>,
+
+    /// The enabled checks of the summary wording during the verification,
+    /// out of "capitalized", "imperative", "no_period" and "no_wip"
+    #[serde(default)]
+    pub style_checks: Vec<String>,
+
+    /// The sorting of the releases, either by commit "time" (default) or by
+    /// "semver", where tags which are no semantic versions come last
+    #[serde(default = "Config::get_default_tag_sorting")]
+    pub tag_sorting: String,
+
     /// Commit message template prefix which will be added during commit
-    /// preparation
+    /// preparation, where the variables `{branch}`, `{ticket-from-branch}`
+    /// and `{author}` are expanded, e.g. "{ticket-from-branch}"
     pub template_prefix: String,
+
+    /// The regular expression of the ticket ids within the branch names for
+    /// the `{ticket-from-branch}` variable, where the first group is used if
+    /// available
+    #[serde(default = "Config::get_default_ticket_pattern")]
+    pub ticket_pattern: String,
+
+    /// The timezone of the release dates, either "utc" (default) or "local",
+    /// where the JSON, YAML and TOML exports always contain the dates in UTC
+    #[serde(default = "Config::get_default_timezone")]
+    pub timezone: String,
+
+    /// A shell command the entry texts are piped through during the output,
+    /// e.g. "my-translator --to de". A `{language}` placeholder is replaced
+    /// by the language of the `translation_profiles`, where the main output
+    /// stays untranslated.
+    pub translate_cmd: Option<String>,
+
+    /// Deepen a shallow clone including its tags via `git fetch --unshallow`
+    /// before parsing, otherwise only a warning is shown
+    #[serde(default)]
+    pub unshallow: bool,
+
+    /// Show the output within `$GIT_PAGER`, the `pager`, `$PAGER` or `less` if
+    /// it does not fit into the terminal
+    #[serde(default = "Config::get_default_use_pager")]
+    pub use_pager: bool,
+
+    /// The format of the `webhook_url` payload, either "slack" (default),
+    /// "teams" or "discord"
+    #[serde(default = "Config::get_default_webhook_format")]
+    pub webhook_format: String,
+
+    /// A chat webhook to announce releases via `--notify`, which requires the
+    /// `network` feature
+    pub webhook_url: Option<String>,
+
+    /// Wrap the paragraphs and list items exceeding the
+    /// `max_body_line_length` before the verification via `verify --format`,
+    /// instead of failing the verification
+    #[serde(default)]
+    pub wrap_body_lines: bool,
+
+    /// The categories of the branch name patterns, e.g. `"fix/*" = "Fixed"`,
+    /// which are the default category of the prepared commit messages. The
+    /// commits of merged branches without a valid category are parsed as if
+    /// they started with it.
+    #[serde(default)]
+    pub branch_categories: BTreeMap<String, String>,
+
+    /// Emojis printed in front of the categories, e.g. `Added = "✨"`, which
+    /// will be parsed as well if `parse_gitmoji` is enabled
+    #[serde(default)]
+    pub category_emojis: BTreeMap<String, String>,
+
+    /// Regular expression replacements of the entry texts after parsing,
+    /// e.g. `"[a-z]+\\.corp\\.example\\.com" = "<internal>"`
+    #[serde(default)]
+    pub entry_replacements: BTreeMap<String, String>,
+
+    /// The requirement of issue references like "#123" or "ABC-123" per
+    /// category during the verification, either "error" or "warn", e.g.
+    /// `Fixed = "error"`
+    #[serde(default)]
+    pub issue_requirements: BTreeMap<String, String>,
+
+    /// Regular expression replacements of the raw commit messages before
+    /// parsing, e.g. `"(?m)^Reviewed-on: .*$" = ""`
+    #[serde(default)]
+    pub message_replacements: BTreeMap<String, String>,
+
+    /// Translations of the category and section names used for the output,
+    /// e.g. `Added = "Hinzugefügt"`
+    #[serde(default)]
+    pub translations: BTreeMap<String, String>,
+
+    /// Additional translation profiles, e.g. `[translation_profiles.de]`,
+    /// which will be used to write one translated output file per language
+    #[serde(default)]
+    pub translation_profiles: BTreeMap<String, BTreeMap<String, String>>,
 }
 
 impl Config {
@@ -58,20 +455,221 @@ impl Config {
     /// ```
     pub fn new() -> Self {
         Config {
+            azure_boards_url: None,
+            cache_dir: None,
+            cargo_version: None,
             categories: Self::get_default_categories(),
             category_delimiters: vec!["[".to_owned(), "]".to_owned()],
+            category_order: vec![],
+            changelog_header: None,
+            changelog_footer: None,
             colored_output: true,
+            date_format: Self::get_default_date_format(),
+            deduplicate_cherry_picks: false,
             default_template: None,
+            email_from: None,
+            email_html: false,
+            email_to: None,
+            empty_releases: Self::get_default_empty_releases(),
+            empty_section_placeholder: None,
             enable_debug: true,
+            exclude_patterns: Self::get_default_exclude_patterns(),
             excluded_commit_tags: vec![],
             enable_footers: false,
+            fetch_remote: Self::get_default_fetch_remote(),
+            fetch_tags: false,
+            first_parent: false,
+            fixup_commits: Self::get_default_fixup_commits(),
+            fold_duplicates: false,
+            fold_prereleases: false,
+            fragments_dir: None,
+            full_log_url: None,
+            group_tag_prefix: None,
+            highlight_categories: Self::get_default_highlight_categories(),
+            highlight_count: Self::get_default_highlight_count(),
+            highlight_tag: Self::get_default_highlight_tag(),
+            highlight_width: Self::get_default_highlight_width(),
+            http_retries: Self::get_default_http_retries(),
+            jira_url: None,
+            major_categories: Self::get_default_major_categories(),
+            major_version: None,
+            minor_categories: Self::get_default_minor_categories(),
+            manual_entries_file: None,
+            max_body_line_length: Self::get_default_max_body_line_length(),
+            max_entries_per_category: None,
+            merge_requests: Self::get_default_merge_requests(),
+            message_width: Self::get_default_message_width(),
+            min_version: None,
+            multiple_tags: Self::get_default_multiple_tags(),
+            offline: false,
+            pager: None,
+            parse_gitmoji: false,
+            provider: None,
+            pull_request_url: None,
+            redact: vec![],
+            relaxed_branches: vec![],
+            relaxed_verification: Self::get_default_relaxed_verification(),
+            repository_url: None,
+            reproducible: false,
+            revwalk_sorting: Self::get_default_revwalk_sorting(),
+            short_summary_width: None,
+            short_summary_wrap: false,
+            show_backports: false,
             show_commit_hash: false,
             show_prefix: false,
+            show_pull_requests: Self::get_default_show_pull_requests(),
+            show_tag_titles: false,
+            sign_output: Self::get_default_sign_output(),
+            signing_key: None,
+            signoff: Self::get_default_signoff(),
             sort_by: "date".to_owned(),
+            spellcheck: Self::get_default_spellcheck(),
+            spellcheck_dictionary: Self::get_default_spellcheck_dictionary(),
+            spellcheck_words: vec![],
+            style_checks: vec![],
+            tag_sorting: Self::get_default_tag_sorting(),
             template_prefix: "JIRA-1234".to_owned(),
+            ticket_pattern: Self::get_default_ticket_pattern(),
+            timezone: Self::get_default_timezone(),
+            translate_cmd: None,
+            unshallow: false,
+            use_pager: Self::get_default_use_pager(),
+            webhook_format: Self::get_default_webhook_format(),
+            webhook_url: None,
+            wrap_body_lines: false,
+            branch_categories: BTreeMap::new(),
+            category_emojis: BTreeMap::new(),
+            entry_replacements: BTreeMap::new(),
+            issue_requirements: BTreeMap::new(),
+            message_replacements: BTreeMap::new(),
+            translations: BTreeMap::new(),
+            translation_profiles: BTreeMap::new(),
         }
     }
 
+    fn get_default_date_format() -> String {
+        "%Y-%m-%d".to_owned()
+    }
+
+    fn get_default_exclude_patterns() -> Vec<String> {
+        vec![
+            "^Merge ".to_owned(),
+            "^fixup!".to_owned(),
+            "^squash!".to_owned(),
+            "^amend!".to_owned(),
+            "\\[skip changelog\\]".to_owned(),
+        ]
+    }
+
+    fn get_default_fetch_remote() -> String {
+        "origin".to_owned()
+    }
+
+    fn get_default_major_categories() -> Vec<String> {
+        vec!["Removed".to_owned()]
+    }
+
+    fn get_default_empty_releases() -> String {
+        "warn".to_owned()
+    }
+
+    fn get_default_fixup_commits() -> String {
+        "allow".to_owned()
+    }
+
+    fn get_default_max_body_line_length() -> usize {
+        72
+    }
+
+    fn get_default_merge_requests() -> String {
+        "off".to_owned()
+    }
+
+    fn get_default_message_width() -> usize {
+        72
+    }
+
+    fn get_default_minor_categories() -> Vec<String> {
+        vec!["Added".to_owned()]
+    }
+
+    fn get_default_highlight_categories() -> Vec<String> {
+        vec![
+            "Added".to_owned(),
+            "Changed".to_owned(),
+            "Improved".to_owned(),
+            "Fixed".to_owned(),
+        ]
+    }
+
+    fn get_default_highlight_count() -> usize {
+        3
+    }
+
+    fn get_default_highlight_tag() -> String {
+        "highlight".to_owned()
+    }
+
+    fn get_default_highlight_width() -> usize {
+        280
+    }
+
+    fn get_default_http_retries() -> u32 {
+        3
+    }
+
+    fn get_default_multiple_tags() -> String {
+        "merged".to_owned()
+    }
+
+    fn get_default_relaxed_verification() -> String {
+        "warn".to_owned()
+    }
+
+    fn get_default_revwalk_sorting() -> Vec<String> {
+        vec!["time".to_owned()]
+    }
+
+    fn get_default_sign_output() -> String {
+        "off".to_owned()
+    }
+
+    fn get_default_signoff() -> String {
+        "off".to_owned()
+    }
+
+    fn get_default_spellcheck() -> String {
+        "warn".to_owned()
+    }
+
+    fn get_default_spellcheck_dictionary() -> String {
+        "/usr/share/dict/words".to_owned()
+    }
+
+    fn get_default_tag_sorting() -> String {
+        "time".to_owned()
+    }
+
+    fn get_default_ticket_pattern() -> String {
+        "[A-Z][A-Z0-9]+-[0-9]+".to_owned()
+    }
+
+    fn get_default_timezone() -> String {
+        "utc".to_owned()
+    }
+
+    fn get_default_show_pull_requests() -> bool {
+        true
+    }
+
+    fn get_default_use_pager() -> bool {
+        true
+    }
+
+    fn get_default_webhook_format() -> String {
+        "slack".to_owned()
+    }
+
     fn get_default_categories() -> Vec<String> {
         vec![
             "Added".to_owned(),
@@ -107,8 +705,10 @@ impl Config {
             .ok_or_else(|| format_err!("Cannot convert path to string"))?;
 
         // Write the path to string
-        let mut file = File::create(&path_buf)?;
-        file.write_all(toml_string.as_bytes())?;
+        let mut file = File::create(&path_buf)
+            .context(ErrorKind::File(path_string.to_owned()))?;
+        file.write_all(toml_string.as_bytes())
+            .context(ErrorKind::File(path_string.to_owned()))?;
         Ok(path_string.to_owned())
     }
 
@@ -125,12 +725,36 @@ impl Config {
     /// When toml decoding or file opening failed.
     pub fn load(&mut self, path: &str) -> Result<(), Error> {
         let path_buf = self.get_path_with_filename(path);
-        let mut file = File::open(&path_buf)?;
+        let file = || ErrorKind::File(path_buf.display().to_string());
         let mut toml_string = String::new();
-        file.read_to_string(&mut toml_string)?;
+        File::open(&path_buf)
+            .context(file())?
+            .read_to_string(&mut toml_string)
+            .context(file())?;
+        self.load_str(&toml_string).context(file())?;
+        Ok(())
+    }
 
+    /// Load a configuration from its toml representation, e.g. read from a
+    /// git revision without any working tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::Config;
+    ///
+    /// let mut config = Config::new();
+    /// let toml = toml::to_string(&config).unwrap();
+    /// config.load_str(&toml.replace("use_pager = true", "use_pager = false"))
+    ///     .expect("Could not load config.");
+    /// assert!(!config.use_pager);
+    /// ```
+    ///
+    /// # Errors
+    /// When toml decoding failed.
+    pub fn load_str(&mut self, toml_string: &str) -> Result<(), Error> {
         // Deserialize the toml string
-        *self = toml::from_str(&toml_string)?;
+        *self = toml::from_str(toml_string)?;
 
         // If the categories are not found within the toml it will return an
         // empty array which will break the parser. So use the default
@@ -141,6 +765,24 @@ impl Config {
         Ok(())
     }
 
+    /// Translates a category or section name for the output. Returns the
+    /// given name if no translation is available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::Config;
+    /// let mut config = Config::new();
+    /// config
+    ///     .translations
+    ///     .insert("Added".to_owned(), "Hinzugefügt".to_owned());
+    /// assert_eq!(config.translate("Added"), "Hinzugefügt");
+    /// assert_eq!(config.translate("Fixed"), "Fixed");
+    /// ```
+    pub fn translate<'a>(&'a self, name: &'a str) -> &'a str {
+        self.translations.get(name).map_or(name, |t| t.as_str())
+    }
+
     /// Check if the configuration matches with the default one.
     ///
     /// # Examples
@@ -155,7 +797,7 @@ impl Config {
 
     fn get_path_with_filename(&self, path: &str) -> PathBuf {
         let mut path_buf = PathBuf::from(path);
-        path_buf.push(".gitjournal.toml");
+        path_buf.push(CONFIG_FILE_NAME);
         path_buf
     }
 }
@@ -0,0 +1,347 @@
+//! Structured comparisons of two parsed releases, e.g. as input for upgrade
+//! guides.
+
+// The comparisons are only made of the parsing results of a `GitJournal`
+#![cfg_attr(not(feature = "git"), allow(dead_code))]
+
+use crate::{
+    config::Config,
+    parser::{
+        BodyElement, ParsedCommit, ParsedTag, Print, MERGED_TAGS_SEPARATOR,
+    },
+};
+use failure::{bail, Error};
+use serde_derive::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The number of entries of a category within both compared releases, which
+/// includes the categorized list items of the commit bodies.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CategoryDiff {
+    /// The category, e.g. "Added"
+    pub category: String,
+
+    /// The number of entries within the older release
+    pub from: usize,
+
+    /// The number of entries within the newer release
+    pub to: usize,
+}
+
+/// The comparison of two releases.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ReleaseDiff {
+    /// The name of the older release, e.g. "v1.0.0"
+    pub from: String,
+
+    /// The name of the newer release, e.g. "v2.0.0"
+    pub to: String,
+
+    /// The entry counts of all categories of both releases, ordered like the
+    /// configured `categories`
+    pub categories: Vec<CategoryDiff>,
+
+    /// The categories with entries only within the newer release
+    pub gained_categories: Vec<String>,
+
+    /// The categories with entries only within the older release
+    pub lost_categories: Vec<String>,
+
+    /// The breaking changes of all releases after the older one up to the
+    /// newer one, see `is_breaking`
+    pub breaking_changes: Vec<ParsedCommit>,
+}
+
+/// Compares the release `from` with the newer release `to`, where both have
+/// to be part of the parsing results.
+///
+/// # Errors
+/// If one of the releases could not be found or `from` is newer than `to`.
+pub fn diff(
+    result: &[ParsedTag],
+    from: &str,
+    to: &str,
+    config: &Config,
+) -> Result<ReleaseDiff, Error> {
+    let position = |release: &str| {
+        result.iter().position(|tag| {
+            tag.name
+                .split(MERGED_TAGS_SEPARATOR)
+                .any(|name| name == release)
+        })
+    };
+    let (from_index, to_index) = match (position(from), position(to)) {
+        (Some(from_index), Some(to_index)) => (from_index, to_index),
+        (None, _) => bail!("Release '{}' not found in the parsed log.", from),
+        (_, None) => bail!("Release '{}' not found in the parsed log.", to),
+    };
+    if result[from_index].date > result[to_index].date {
+        bail!("Release '{}' is newer than '{}'.", from, to);
+    }
+
+    // The releases after the older one up to the newer one, independently of
+    // the sorting of the results
+    let introduced = if from_index > to_index {
+        &result[to_index..from_index]
+    } else {
+        &result[from_index + 1..=to_index]
+    };
+    let breaking_changes = introduced
+        .iter()
+        .flat_map(|tag| &tag.commits)
+        .filter(|commit| is_breaking(commit, config))
+        .cloned()
+        .collect();
+
+    let counts = |tag: &ParsedTag| {
+        let mut counts = BTreeMap::new();
+        for category in tag.commits.iter().flat_map(|c| c.categories()) {
+            *counts.entry(category.to_owned()).or_insert(0) += 1;
+        }
+        counts
+    };
+    let from_counts = counts(&result[from_index]);
+    let to_counts = counts(&result[to_index]);
+    let mut names = from_counts
+        .keys()
+        .chain(to_counts.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    names.sort_by_key(|name| {
+        config
+            .categories
+            .iter()
+            .position(|category| category == *name)
+            .unwrap_or(usize::MAX)
+    });
+    let categories = names
+        .into_iter()
+        .map(|name| CategoryDiff {
+            category: name.clone(),
+            from: from_counts.get(name).cloned().unwrap_or_default(),
+            to: to_counts.get(name).cloned().unwrap_or_default(),
+        })
+        .collect::<Vec<_>>();
+    let select = |f: fn(&CategoryDiff) -> bool| {
+        categories
+            .iter()
+            .filter(|diff| f(diff))
+            .map(|diff| diff.category.clone())
+            .collect()
+    };
+    Ok(ReleaseDiff {
+        from: result[from_index].name.clone(),
+        to: result[to_index].name.clone(),
+        gained_categories: select(|diff| diff.from == 0),
+        lost_categories: select(|diff| diff.to == 0),
+        categories,
+        breaking_changes,
+    })
+}
+
+/// Renders the skeleton of an upgrade guide from the breaking changes of the
+/// comparison, where every change gets its own section with the link to its
+/// commit via the `commit_url` function, the body of the commit message and
+/// placeholders for the migration steps. The section is titled by the
+/// summary, or by the first breaking list item if only the body contains
+/// breaking changes.
+pub fn upgrade_guide<F>(
+    diff: &ReleaseDiff,
+    config: &Config,
+    commit_url: F,
+) -> String
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut guide =
+        format!("# Upgrading from {} to {}\n\n", diff.from, diff.to);
+    if diff.breaking_changes.is_empty() {
+        guide += "There are no breaking changes.\n";
+        return guide;
+    }
+    guide += "<!-- An overview of the upgrade. -->\n";
+    for commit in &diff.breaking_changes {
+        let message = commit.to_message(config);
+        let mut parts = message.splitn(2, "\n\n");
+        let (category, text) = breaking_item(commit, config)
+            .unwrap_or((&commit.summary.category, &commit.summary.text));
+        guide += &format!("\n## {} {}\n\n", config.translate(category), text);
+        if let Some(oid) = commit.oid {
+            let oid = oid.to_string();
+            match commit_url(&oid) {
+                Some(url) => {
+                    guide +=
+                        &format!("Introduced by [{:.7}]({}).\n\n", oid, url)
+                }
+                None => guide += &format!("Introduced by {:.7}.\n\n", oid),
+            }
+        }
+        if let Some(body) = parts.nth(1) {
+            guide += body;
+            guide += "\n\n";
+        }
+        guide += "<!-- The steps to migrate. -->\n";
+    }
+    guide
+}
+
+/// Returns whether the commit is a breaking change, where its summary or one
+/// of its list items is either part of the `major_categories` or tagged as
+/// `:breaking:`.
+pub fn is_breaking(commit: &ParsedCommit, config: &Config) -> bool {
+    commit.categories().iter().any(|category| {
+        config
+            .major_categories
+            .iter()
+            .any(|major| major == category)
+    }) || commit.contains_tag(Some("breaking"))
+}
+
+/// Returns the category and text of the first breaking list item of the
+/// commit, if its summary is not breaking on its own.
+fn breaking_item<'a>(
+    commit: &'a ParsedCommit,
+    config: &Config,
+) -> Option<(&'a String, &'a String)> {
+    let breaking = |category: &String, tags: &[String]| {
+        config.major_categories.contains(category)
+            || tags.iter().any(|tag| tag == "breaking")
+    };
+    if breaking(&commit.summary.category, &commit.summary.tags) {
+        return None;
+    }
+    commit
+        .body
+        .iter()
+        .flat_map(|element| match element {
+            BodyElement::List(list) => list.as_slice(),
+            BodyElement::Paragraph(_) => &[],
+        })
+        .find(|item| breaking(&item.category, &item.tags))
+        .map(|item| (&item.category, &item.text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{oid::Oid, parser::Parser};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn diff_releases() {
+        let config = Config::new();
+        let tag = |name: &str, messages: &[&str]| {
+            ParsedTag::from_messages(name, messages, &config)
+        };
+        let mut result = vec![
+            tag("v3", &["Removed the old API", "Fixed the login"]),
+            tag("v2", &["Changed the config :breaking:", "Added a"]),
+            tag("v1", &["Added the login", "Added b", "Improved c"]),
+        ];
+        for (tag, seconds) in result.iter_mut().zip(&[3000, 2000, 1000]) {
+            tag.date = Utc.timestamp(*seconds, 0);
+        }
+        let diff = diff(&result, "v1", "v3", &config).unwrap();
+        assert_eq!(diff.from, "v1");
+        assert_eq!(diff.to, "v3");
+        assert_eq!(
+            diff.categories,
+            vec![
+                CategoryDiff {
+                    category: "Added".to_owned(),
+                    from: 2,
+                    to: 0,
+                },
+                CategoryDiff {
+                    category: "Fixed".to_owned(),
+                    from: 0,
+                    to: 1,
+                },
+                CategoryDiff {
+                    category: "Improved".to_owned(),
+                    from: 1,
+                    to: 0,
+                },
+                CategoryDiff {
+                    category: "Removed".to_owned(),
+                    from: 0,
+                    to: 1,
+                },
+            ]
+        );
+        assert_eq!(diff.gained_categories, vec!["Fixed", "Removed"]);
+        assert_eq!(diff.lost_categories, vec!["Added", "Improved"]);
+        let breaking = diff
+            .breaking_changes
+            .iter()
+            .map(|commit| commit.summary.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(breaking, vec!["the old API", "the config"]);
+
+        let mut reversed = result.clone();
+        reversed.reverse();
+        let reversed_diff = super::diff(&reversed, "v1", "v3", &config);
+        assert_eq!(reversed_diff.unwrap().breaking_changes.len(), 2);
+        assert!(super::diff(&result, "v0", "v3", &config).is_err());
+        assert!(super::diff(&result, "v3", "v1", &config).is_err());
+
+        // The categorized list items are entries as well
+        result[0] = tag("v3", &["Fixed the login\n\n- [Removed] the flag"]);
+        let diff = super::diff(&result, "v2", "v3", &config).unwrap();
+        assert_eq!(diff.gained_categories, vec!["Fixed", "Removed"]);
+        assert_eq!(diff.breaking_changes[0].summary.text, "the login");
+    }
+
+    #[test]
+    fn render_upgrade_guide() {
+        let config = Config::new();
+        let parser = Parser {
+            config: config.clone(),
+            result: vec![],
+        };
+        let oid = Oid::from_str(&format!("{:040}", 1)).unwrap();
+        let commit = parser
+            .parse_commit_message(
+                "Removed the old API\n\nUse the new one.\n\n- [Added] b",
+                Some(oid),
+            )
+            .unwrap();
+        let mut diff = ReleaseDiff {
+            from: "v1".to_owned(),
+            to: "v2".to_owned(),
+            categories: vec![],
+            gained_categories: vec![],
+            lost_categories: vec![],
+            breaking_changes: vec![commit],
+        };
+        let guide = upgrade_guide(&diff, &config, |oid| {
+            Some(format!("https://github.com/u/p/commit/{}", oid))
+        });
+        assert_eq!(
+            guide,
+            "# Upgrading from v1 to v2\n\n\
+             <!-- An overview of the upgrade. -->\n\n\
+             ## Removed the old API\n\n\
+             Introduced by [0000000](https://github.com/u/p/commit/\
+             0000000000000000000000000000000000000001).\n\n\
+             Use the new one\n\n\
+             - [Added] b\n\n\
+             <!-- The steps to migrate. -->\n"
+        );
+
+        // Commits with breaking list items are titled by the first one
+        diff.breaking_changes = vec![parser
+            .parse_commit_message(
+                "Added the new API\n\n- [Added] c\n- [Removed] the old one",
+                None,
+            )
+            .unwrap()];
+        assert!(upgrade_guide(&diff, &config, |_| None)
+            .contains("\n## Removed the old one\n\n- [Added] c\n"));
+
+        diff.breaking_changes.clear();
+        assert!(upgrade_guide(&diff, &config, |_| None)
+            .ends_with("There are no breaking changes.\n"));
+    }
+}
@@ -0,0 +1,198 @@
+//! Release announcements as RFC 5322 messages, which can be piped into
+//! `sendmail -t`.
+
+use crate::{config::Config, encoding::base64, width};
+use chrono::{DateTime, Utc};
+use std::fmt::Write;
+
+/// The maximum line width of the plain text body.
+static LINE_WIDTH: usize = 72;
+
+/// The boundary between the plain text and the HTML part.
+static BOUNDARY: &str = "=_git-journal-alternative";
+
+/// Creates the message for the rendered section of a release. The subject is
+/// taken from the release header, falling back to the release name.
+pub fn message(
+    config: &Config,
+    release: &str,
+    section: &str,
+    date: DateTime<Utc>,
+) -> String {
+    let mut lines = section.lines().skip_while(|l| l.trim().is_empty());
+    let (subject, body) = match lines.clone().next() {
+        Some(line) if line.starts_with('#') => {
+            lines.next();
+            let subject = line.trim_start_matches('#').trim();
+            (subject.trim_end_matches(':').to_owned(), lines)
+        }
+        _ => (release.to_owned(), lines),
+    };
+    let body = body.collect::<Vec<_>>().join("\n");
+    let body = body.trim();
+
+    let mut message = String::new();
+    if let Some(ref from) = config.email_from {
+        message.push_str(&format!("From: {}\n", from));
+    }
+    if let Some(ref to) = config.email_to {
+        message.push_str(&format!("To: {}\n", to));
+    }
+    message.push_str(&format!("Date: {}\n", date.to_rfc2822()));
+    message.push_str(&format!("Subject: {}\n", encode_header(&subject)));
+    message.push_str("MIME-Version: 1.0\n");
+    if config.email_html {
+        message.push_str(&format!(
+            "Content-Type: multipart/alternative; boundary=\"{}\"\n\n",
+            BOUNDARY
+        ));
+        message.push_str(&format!("--{}\n", BOUNDARY));
+        message.push_str(&text_part(body));
+        message.push_str(&format!("\n--{}\n", BOUNDARY));
+        message.push_str(
+            "Content-Type: text/html; charset=utf-8\n\
+             Content-Transfer-Encoding: 8bit\n\n",
+        );
+        message.push_str(&html(&subject, body));
+        message.push_str(&format!("\n--{}--\n", BOUNDARY));
+    } else {
+        message.push_str(&text_part(body));
+    }
+    message
+}
+
+/// Encodes a header value according to RFC 2047 if it is not plain ASCII.
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        value.to_owned()
+    } else {
+        format!("=?utf-8?B?{}?=", base64(value.as_bytes()))
+    }
+}
+
+/// Returns the plain text part including its headers, where long lines are
+/// wrapped and continued below the text of list items.
+fn text_part(body: &str) -> String {
+    let mut part = "Content-Type: text/plain; charset=utf-8\n\
+                    Content-Transfer-Encoding: 8bit\n\n"
+        .to_owned();
+    for line in body.lines() {
+        let content = line.trim_start();
+        let mut indent = line.len() - content.len();
+        if content.starts_with("- ") {
+            indent += 2;
+        }
+        if content.is_empty() || width::display_width(line) <= LINE_WIDTH {
+            let _ = writeln!(part, "{}", line);
+            continue;
+        }
+        let wrapped = width::wrap(
+            &line[indent..],
+            LINE_WIDTH.saturating_sub(indent).max(1),
+        );
+        for (index, wrapped_line) in wrapped.iter().enumerate() {
+            let prefix = if index == 0 { &line[..indent] } else { "" };
+            let _ = writeln!(
+                part,
+                "{:indent$}{}",
+                prefix,
+                wrapped_line,
+                indent = indent
+            );
+        }
+    }
+    part
+}
+
+/// Escapes the text for HTML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Converts the Markdown of the rendered section to simple HTML, consisting
+/// of headings, lists and paragraphs.
+fn html(subject: &str, body: &str) -> String {
+    let mut html = format!("<html>\n<body>\n<h1>{}</h1>\n", escape(subject));
+    let mut in_list = false;
+    for line in body.lines() {
+        let line = line.trim();
+        let item = line.strip_prefix("- ");
+        if in_list && item.is_none() {
+            html.push_str("</ul>\n");
+            in_list = false;
+        }
+        if let Some(item) = item {
+            if !in_list {
+                html.push_str("<ul>\n");
+                in_list = true;
+            }
+            let _ = writeln!(html, "<li>{}</li>", escape(item));
+        } else if line.starts_with('#') {
+            let level = line.chars().take_while(|c| *c == '#').count().min(6);
+            let _ = writeln!(
+                html,
+                "<h{level}>{}</h{level}>",
+                escape(line[level..].trim()),
+                level = level
+            );
+        } else if !line.is_empty() {
+            let _ = writeln!(html, "<p>{}</p>", escape(line));
+        }
+    }
+    if in_list {
+        html.push_str("</ul>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    static SECTION: &str = "\n# v1 (2016-09-12):\n## Auth\n- [Added] login \
+                            & logout for all of the users with a very long \
+                            summary line\n- [Fixed] <tokens>\n";
+
+    #[test]
+    fn plain_text_message() {
+        let mut config = Config::new();
+        config.email_to = Some("team@example.com".to_owned());
+        let date = Utc.ymd(2016, 9, 12).and_hms(23, 30, 0);
+        let message = message(&config, "v1", SECTION, date);
+        assert_eq!(
+            message,
+            "To: team@example.com\n\
+             Date: Mon, 12 Sep 2016 23:30:00 +0000\n\
+             Subject: v1 (2016-09-12)\n\
+             MIME-Version: 1.0\n\
+             Content-Type: text/plain; charset=utf-8\n\
+             Content-Transfer-Encoding: 8bit\n\n\
+             ## Auth\n\
+             - [Added] login & logout for all of the users with a very long \
+             summary\n  line\n\
+             - [Fixed] <tokens>\n"
+        );
+    }
+
+    #[test]
+    fn html_message() {
+        let mut config = Config::new();
+        config.email_html = true;
+        let date = Utc.ymd(2016, 9, 12).and_hms(23, 30, 0);
+        let message = message(&config, "v1", "- [Added] ✨", date);
+        assert!(message.contains("Subject: v1\n"));
+        assert!(message.contains("multipart/alternative"));
+        assert!(message.contains("<ul>\n<li>[Added] ✨</li>\n</ul>\n"));
+        assert!(message.ends_with(&format!("--{}--\n", BOUNDARY)));
+        assert_eq!(
+            html("v1", "## Auth\n- [Fixed] <tokens>").lines().nth(5),
+            Some("<li>[Fixed] &lt;tokens&gt;</li>")
+        );
+        assert_eq!(encode_header("Größe"), "=?utf-8?B?R3LDtsOfZQ==?=");
+    }
+}
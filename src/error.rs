@@ -0,0 +1,181 @@
+//! The kinds of errors attached as context to the failures, which tell which
+//! commit, file or configuration key caused them, and a report of the whole
+//! chain implementing the standard error trait, which can be serialized for
+//! structured error output.
+
+use failure::{Context, Error, Fail};
+use serde_derive::Serialize;
+use std::{error, fmt};
+
+/// The origin of an error, which is attached as context to the underlying
+/// failure and can be found via `Report::kind`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "content")]
+pub enum ErrorKind {
+    /// A commit, given by its id, could not be read
+    Commit(String),
+
+    /// A file, given by its path, could not be read, parsed or written
+    File(String),
+
+    /// A configuration key has an invalid value
+    Config(String),
+
+    /// The operation has been aborted via a `Cancellation`
+    Cancelled,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ErrorKind::Commit(ref oid) => write!(f, "Commit {}", oid),
+            ErrorKind::File(ref path) => write!(f, "File '{}'", path),
+            ErrorKind::Config(ref key) => {
+                write!(f, "Configuration key '{}'", key)
+            }
+            ErrorKind::Cancelled => write!(f, "Operation cancelled"),
+        }
+    }
+}
+
+impl Fail for ErrorKind {}
+
+impl ErrorKind {
+    /// Attaches the kind as context to the error.
+    pub(crate) fn wrap<E: Into<Error>>(self, error: E) -> Error {
+        error.into().context(self).into()
+    }
+}
+
+/// An error together with its causes, which can be used by applications
+/// expecting a `std::error::Error`. Every cause is available via `source`,
+/// and is serialized with its `message`, `kind` and `source` as well.
+///
+/// # Examples
+///
+/// ```
+/// use gitjournal::{ErrorKind, GitJournal, Report};
+///
+/// let mut journal = GitJournal::new(".").unwrap();
+/// if let Err(error) = journal.config.load("/dev/null/missing") {
+///     let report = Report::from(error);
+///     assert!(report.kind().is_some());
+///     println!("{:#}", report);
+/// }
+/// ```
+#[derive(Debug, Serialize)]
+pub struct Report {
+    message: String,
+    kind: Option<ErrorKind>,
+    source: Option<Box<Report>>,
+}
+
+impl Report {
+    /// Returns the first kind within the chain of causes.
+    pub fn kind(&self) -> Option<&ErrorKind> {
+        match self.kind {
+            Some(ref kind) => Some(kind),
+            None => self.source.as_ref().and_then(|source| source.kind()),
+        }
+    }
+}
+
+impl From<Error> for Report {
+    fn from(error: Error) -> Self {
+        let causes = error.iter_chain().collect::<Vec<_>>();
+        let mut report = None;
+        for cause in causes.into_iter().rev() {
+            let kind = match cause.downcast_ref::<Context<ErrorKind>>() {
+                Some(context) => Some(context.get_context().clone()),
+                None => cause.downcast_ref::<ErrorKind>().cloned(),
+            };
+            report = Some(Report {
+                message: cause.to_string(),
+                kind,
+                source: report.map(Box::new),
+            });
+        }
+        report.unwrap_or_else(|| Report {
+            message: error.to_string(),
+            kind: None,
+            source: None,
+        })
+    }
+}
+
+impl fmt::Display for Report {
+    /// Prints only the message of the error, or the whole chain of causes
+    /// separated by colons in the alternate form `{:#}`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if f.alternate() {
+            let mut source = &self.source;
+            while let Some(ref report) = *source {
+                write!(f, ": {}", report.message)?;
+                source = &report.source;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for Report {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| source.as_ref() as &(dyn error::Error + 'static))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use failure::{format_err, ResultExt};
+    use std::error::Error as _;
+
+    #[test]
+    fn report_chain() {
+        let result: Result<(), Error> = Err(format_err!("Invalid value"));
+        let error: Error = result
+            .context(ErrorKind::Config("tag_sorting".to_owned()))
+            .unwrap_err()
+            .into();
+        let report = Report::from(error);
+        assert_eq!(
+            report.kind(),
+            Some(&ErrorKind::Config("tag_sorting".to_owned()))
+        );
+        assert_eq!(report.to_string(), "Configuration key 'tag_sorting'");
+        assert_eq!(
+            format!("{:#}", report),
+            "Configuration key 'tag_sorting': Invalid value"
+        );
+        let source = report.source().unwrap();
+        assert_eq!(source.to_string(), "Invalid value");
+        assert!(source.source().is_none());
+
+        let report = Report::from(
+            ErrorKind::File("a.toml".to_owned()).wrap(format_err!("Missing")),
+        );
+        assert_eq!(format!("{:#}", report), "File 'a.toml': Missing");
+        assert!(Report::from(format_err!("Plain")).kind().is_none());
+    }
+
+    #[test]
+    fn serialize_report() {
+        let report = Report::from(
+            ErrorKind::File("a.toml".to_owned()).wrap(format_err!("Missing")),
+        );
+        assert_eq!(
+            serde_json::to_string(&report).unwrap(),
+            "{\"message\":\"File 'a.toml'\",\
+             \"kind\":{\"type\":\"File\",\"content\":\"a.toml\"},\
+             \"source\":{\"message\":\"Missing\",\"kind\":null,\
+             \"source\":null}}"
+        );
+        assert_eq!(
+            serde_json::to_string(&ErrorKind::Cancelled).unwrap(),
+            "{\"type\":\"Cancelled\"}"
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! A minimal C interface for tooling which links against the library instead
+//! of running the binary, which is only available if the `ffi` feature is
+//! enabled. The corresponding header is `include/gitjournal.h`, which can be
+//! regenerated via `cbindgen --config cbindgen.toml`. The shared and static
+//! library exporting it are built from the `git-journal-ffi` crate.
+//!
+//! All functions take null terminated UTF-8 strings and return newly
+//! allocated strings, which have to be released via `gj_string_free`. On
+//! failure they return a null pointer and the message of the error is
+//! available via `gj_last_error` within the same thread. Panics never unwind
+//! into the caller and are reported like errors.
+
+use crate::{
+    error::Report, parse_commit_message, Config, GitJournal, ParseOptions,
+};
+use failure::{format_err, Error};
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    panic::{self, UnwindSafe},
+    ptr,
+};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Converts an optional C string argument, where null is `None`.
+unsafe fn to_str<'a>(
+    name: &str,
+    value: *const c_char,
+) -> Result<Option<&'a str>, Error> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(value).to_str().map(Some).map_err(|e| {
+        format_err!("Argument '{}' is not valid UTF-8: {}", name, e)
+    })
+}
+
+/// Converts a required C string argument.
+unsafe fn to_required_str<'a>(
+    name: &str,
+    value: *const c_char,
+) -> Result<&'a str, Error> {
+    to_str(name, value)?
+        .ok_or_else(|| format_err!("Argument '{}' must not be null", name))
+}
+
+/// Returns the message of a caught panic.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map_or("Unknown panic", |message| message)
+            .to_owned(),
+    }
+}
+
+/// Runs the function and converts its result into a newly allocated C
+/// string, or stores the error or panic for `gj_last_error` and returns null.
+fn into_raw<F>(function: F) -> *mut c_char
+where
+    F: FnOnce() -> Result<String, Error> + UnwindSafe,
+{
+    let result = panic::catch_unwind(function)
+        .unwrap_or_else(|payload| {
+            Err(format_err!("Panicked: {}", panic_message(payload)))
+        })
+        .and_then(|s| Ok(CString::new(s)?));
+    LAST_ERROR.with(|last_error| match result {
+        Ok(string) => {
+            *last_error.borrow_mut() = None;
+            string.into_raw()
+        }
+        Err(e) => {
+            let message = format!("{:#}", Report::from(e)).replace('\0', "");
+            *last_error.borrow_mut() = CString::new(message).ok();
+            ptr::null_mut()
+        }
+    })
+}
+
+/// Parses a single commit message and returns the parsed commit as JSON. The
+/// configuration is read from the repository path or file `config_path`, or
+/// the default configuration is used if it is null.
+///
+/// # Safety
+/// The arguments have to be null or valid null terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn gj_parse_message(
+    message: *const c_char,
+    config_path: *const c_char,
+) -> *mut c_char {
+    into_raw(|| {
+        let message = to_required_str("message", message)?;
+        let mut config = Config::new();
+        if let Some(config_path) = to_str("config_path", config_path)? {
+            config.load(config_path)?;
+        }
+        let commit = parse_commit_message(message, &config)?;
+        Ok(serde_json::to_string(&commit)?)
+    })
+}
+
+/// Verifies a single commit message with the configuration and rules of the
+/// repository at `path` and returns the reason why it is not valid, or an
+/// empty string if it is valid. Null is only returned if the repository could
+/// not be opened.
+///
+/// # Safety
+/// The arguments have to be null or valid null terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn gj_verify_message(
+    message: *const c_char,
+    path: *const c_char,
+) -> *mut c_char {
+    into_raw(|| {
+        let message = to_required_str("message", message)?;
+        let journal = GitJournal::new(to_required_str("path", path)?)?;
+        Ok(match journal.verify_message(message) {
+            Ok(()) => String::new(),
+            Err(e) => format!("{:#}", Report::from(e)),
+        })
+    })
+}
+
+/// Generates the changelog of the repository at `path` for the revision
+/// range, which defaults to "HEAD" if it is null, including all tags. The
+/// format is "md" for Markdown if it is null, or one of the export formats
+/// like "json".
+///
+/// # Safety
+/// The arguments have to be null or valid null terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn gj_generate_changelog(
+    path: *const c_char,
+    revision_range: *const c_char,
+    format: *const c_char,
+) -> *mut c_char {
+    into_raw(|| {
+        let mut journal = GitJournal::new(to_required_str("path", path)?)?;
+        let revision_range =
+            to_str("revision_range", revision_range)?.unwrap_or("HEAD");
+        journal.parse_log(&ParseOptions::new(revision_range).all(true))?;
+        match to_str("format", format)?.unwrap_or("md") {
+            "md" | "markdown" => journal.parsed().render(false, None),
+            format => journal.export(format),
+        }
+    })
+}
+
+/// Returns the message of the last error within the current thread, or null
+/// if the last call succeeded. The message stays valid until the next call
+/// and must not be released.
+#[no_mangle]
+pub extern "C" fn gj_last_error() -> *const c_char {
+    panic::catch_unwind(|| {
+        LAST_ERROR.with(|last_error| {
+            last_error
+                .borrow()
+                .as_ref()
+                .map_or(ptr::null(), |message| message.as_ptr())
+        })
+    })
+    .unwrap_or(ptr::null())
+}
+
+/// Releases a string returned by the library, where null is ignored.
+///
+/// # Safety
+/// The string has to be returned by one of the functions of the library and
+/// must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn gj_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        panic::catch_unwind(|| drop(CString::from_raw(string))).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unsafe fn take(string: *mut c_char) -> String {
+        assert!(!string.is_null());
+        let owned = CStr::from_ptr(string).to_string_lossy().into_owned();
+        gj_string_free(string);
+        owned
+    }
+
+    unsafe fn last_error() -> String {
+        CStr::from_ptr(gj_last_error())
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn parse_message() {
+        unsafe {
+            let message = CString::new("Added some feature :ui:").unwrap();
+            let json = take(gj_parse_message(message.as_ptr(), ptr::null()));
+            let commit: serde_json::Value =
+                serde_json::from_str(&json).unwrap();
+            assert_eq!(commit["summary"]["category"], "Added");
+            assert!(gj_last_error().is_null());
+
+            let message = CString::new("Invalid message").unwrap();
+            assert!(gj_parse_message(message.as_ptr(), ptr::null()).is_null());
+            assert!(!last_error().is_empty());
+
+            assert!(gj_parse_message(ptr::null(), ptr::null()).is_null());
+            assert_eq!(last_error(), "Argument 'message' must not be null");
+            gj_string_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn catch_panics() {
+        assert!(into_raw(|| panic!("Some {}", "panic")).is_null());
+        unsafe {
+            assert_eq!(last_error(), "Panicked: Some panic");
+        }
+        assert!(into_raw(|| panic!("Another panic")).is_null());
+        unsafe {
+            assert_eq!(last_error(), "Panicked: Another panic");
+        }
+    }
+
+    #[test]
+    fn verify_message() {
+        unsafe {
+            let path = CString::new(".").unwrap();
+            let message = CString::new("Added some feature").unwrap();
+            let reason =
+                take(gj_verify_message(message.as_ptr(), path.as_ptr()));
+            assert_eq!(reason, "");
+            assert!(gj_last_error().is_null());
+
+            let message = CString::new("Invalid message").unwrap();
+            let reason =
+                take(gj_verify_message(message.as_ptr(), path.as_ptr()));
+            assert!(!reason.is_empty());
+            assert!(gj_last_error().is_null());
+
+            let path = CString::new("/dev/null").unwrap();
+            assert!(
+                gj_verify_message(message.as_ptr(), path.as_ptr()).is_null()
+            );
+            assert!(!last_error().is_empty());
+            assert!(gj_verify_message(message.as_ptr(), ptr::null()).is_null());
+            assert_eq!(last_error(), "Argument 'path' must not be null");
+        }
+    }
+
+    #[test]
+    fn generate_changelog() {
+        unsafe {
+            let path = CString::new(".").unwrap();
+            let format = CString::new("json").unwrap();
+            let json = take(gj_generate_changelog(
+                path.as_ptr(),
+                ptr::null(),
+                format.as_ptr(),
+            ));
+            assert!(json.contains("\"format_version\""));
+            take(gj_generate_changelog(
+                path.as_ptr(),
+                ptr::null(),
+                ptr::null(),
+            ));
+
+            let format = CString::new("unknown").unwrap();
+            assert!(gj_generate_changelog(
+                path.as_ptr(),
+                ptr::null(),
+                format.as_ptr()
+            )
+            .is_null());
+            assert!(!last_error().is_empty());
+        }
+    }
+}
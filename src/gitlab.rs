@@ -0,0 +1,220 @@
+//! Enrichment of the parsed commits with the merge requests of a GitLab
+//! project, which is only available if the `network` feature is enabled.
+
+use crate::{
+    config::Config,
+    http::Client,
+    links::Provider,
+    parser::{BodyElement, Issue, ParagraphElement, ParsedTag},
+};
+use failure::{format_err, Error};
+use log::{info, warn};
+use serde_json::Value;
+use std::{collections::BTreeMap, env, time::Duration};
+
+/// The environment variable containing the GitLab personal access token.
+static ENV_TOKEN: &str = "GITLAB_TOKEN";
+
+/// A merge request fetched via the GitLab API.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct MergeRequest {
+    /// The title and the state of the merge request, e.g. "merged"
+    issue: Issue,
+
+    /// The first paragraph of the description, if any
+    description: Option<String>,
+}
+
+/// Adds the title and state of the merge request to every commit with a
+/// merge request, if the `merge_requests` are fetched from the GitLab project
+/// of the `repository_url`. Commits without a body additionally get the
+/// first paragraph of the description. Every merge request is only fetched
+/// once and merge requests which could not be fetched are skipped with a
+/// warning.
+pub fn enrich(result: &mut [ParsedTag], config: &Config, client: &Client) {
+    if config.merge_requests != "fetch" {
+        return;
+    }
+    match Provider::from_config(config) {
+        Ok(Some((Provider::GitLab, url))) => enrich_with(result, |number| {
+            fetch_merge_request(client, url, number)
+        }),
+        _ => warn!(
+            "Not fetching the merge requests, the 'repository_url' is no \
+             GitLab project."
+        ),
+    }
+}
+
+/// Adds the merge requests retrieved by the `fetch` function to the commits.
+fn enrich_with<F>(result: &mut [ParsedTag], mut fetch: F)
+where
+    F: FnMut(u64) -> Result<MergeRequest, Error>,
+{
+    let mut merge_requests: BTreeMap<u64, Option<MergeRequest>> =
+        BTreeMap::new();
+    for tag in result.iter_mut() {
+        for commit in &mut tag.commits {
+            let number = match commit.summary.merge_request {
+                Some(number) => number,
+                None => continue,
+            };
+            let merge_request =
+                merge_requests.entry(number).or_insert_with(|| {
+                    match fetch(number) {
+                        Ok(merge_request) => Some(merge_request),
+                        Err(e) => {
+                            warn!(
+                                "Could not fetch merge request '!{}': {}",
+                                number, e
+                            );
+                            None
+                        }
+                    }
+                });
+            let merge_request = match merge_request {
+                Some(merge_request) => merge_request,
+                None => continue,
+            };
+            if commit.summary.issue.is_none() {
+                commit.summary.issue = Some(merge_request.issue.clone());
+            }
+            if let Some(ref description) = merge_request.description {
+                if commit.body.is_empty() {
+                    commit.body.push(BodyElement::Paragraph(
+                        ParagraphElement {
+                            oid: commit.summary.oid,
+                            text: description.clone(),
+                            tags: vec![],
+                        },
+                    ));
+                }
+            }
+        }
+    }
+    info!(
+        "Fetched {} GitLab merge requests.",
+        merge_requests.values().flatten().count()
+    );
+}
+
+/// Fetches a single merge request via the GitLab REST API.
+fn fetch_merge_request(
+    client: &Client,
+    url: &str,
+    number: u64,
+) -> Result<MergeRequest, Error> {
+    let api_url = api_url(url, number)
+        .ok_or_else(|| format_err!("Invalid repository URL '{}'", url))?;
+    let mut request = ureq::get(&api_url).timeout(Duration::from_secs(10));
+    if let Ok(token) = env::var(ENV_TOKEN) {
+        request = request.set("PRIVATE-TOKEN", &token);
+    }
+    match client.get_json(request)? {
+        Some(json) => parse_merge_request(&json),
+        None => Err(format_err!("Merge request not found")),
+    }
+}
+
+/// Returns the API endpoint of the merge request of the project, e.g.
+/// "https://gitlab.com/api/v4/projects/group%2Fproject/merge_requests/1" for
+/// "https://gitlab.com/group/project".
+fn api_url(url: &str, number: u64) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let (host, project) = rest.split_once('/')?;
+    Some(format!(
+        "{}://{}/api/v4/projects/{}/merge_requests/{}",
+        scheme,
+        host,
+        project.trim_matches('/').replace('/', "%2F"),
+        number
+    ))
+}
+
+/// Parses the merge request from the JSON response of the GitLab REST API.
+fn parse_merge_request(json: &Value) -> Result<MergeRequest, Error> {
+    let title = json["title"]
+        .as_str()
+        .ok_or_else(|| format_err!("Missing merge request title"))?;
+    let state = json["state"]
+        .as_str()
+        .ok_or_else(|| format_err!("Missing merge request state"))?;
+    let description = json["description"]
+        .as_str()
+        .and_then(|text| {
+            text.split("\n\n").map(str::trim).find(|p| !p.is_empty())
+        })
+        .map(|paragraph| paragraph.replace("\r\n", "\n"));
+    Ok(MergeRequest {
+        issue: Issue {
+            title: title.to_owned(),
+            status: state.to_owned(),
+        },
+        description,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use failure::bail;
+
+    #[test]
+    fn parse_merge_request_response() {
+        assert_eq!(
+            api_url("https://gitlab.com/group/sub/project", 12).unwrap(),
+            "https://gitlab.com/api/v4/projects/group%2Fsub%2Fproject/\
+             merge_requests/12"
+        );
+        assert!(api_url("gitlab.com", 12).is_none());
+
+        let json = serde_json::from_str(
+            r#"{"iid": 12, "title": "Login page", "state": "merged",
+                "description": "\n\nAdds the login.\n\n## Checklist"}"#,
+        )
+        .unwrap();
+        let merge_request = parse_merge_request(&json).unwrap();
+        assert_eq!(merge_request.issue.title, "Login page");
+        assert_eq!(merge_request.issue.status, "merged");
+        assert_eq!(merge_request.description.unwrap(), "Adds the login.");
+        assert!(parse_merge_request(&Value::Null).is_err());
+    }
+
+    #[test]
+    fn enrich_commits() {
+        let mut config = Config::new();
+        config.merge_requests = "fetch".to_owned();
+        let mut result = vec![ParsedTag::from_messages(
+            "v1",
+            &[
+                "Added a\n\nSee merge request group/project!1",
+                "Fixed b\n\nText\n\nSee merge request group/project!1",
+                "Fixed c\n\nSee merge request group/project!2",
+                "Fixed d (#3)",
+            ],
+            &config,
+        )];
+        let mut fetched = vec![];
+        enrich_with(&mut result, |number| {
+            fetched.push(number);
+            if number == 2 {
+                bail!("Not found");
+            }
+            Ok(MergeRequest {
+                issue: Issue {
+                    title: "Some merge request".to_owned(),
+                    status: "merged".to_owned(),
+                },
+                description: Some("Some description".to_owned()),
+            })
+        });
+        assert_eq!(fetched, vec![1, 2]);
+        let commits = &result[0].commits;
+        assert_eq!(commits[0].summary.issue, commits[1].summary.issue);
+        assert!(commits[0].summary.issue.is_some());
+        assert_eq!(commits[0].body.len(), 1);
+        assert_eq!(commits[1].texts(), vec!["b", "Text"]);
+        assert!(commits[2].summary.issue.is_none());
+        assert!(commits[3].summary.issue.is_none());
+    }
+}
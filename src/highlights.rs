@@ -0,0 +1,117 @@
+//! Compact highlights of a release for posts on social media or preview
+//! texts, which are selected from the parsed commits by the priority of
+//! their categories.
+
+// The highlights are only made of the parsing results of a `GitJournal`
+#![cfg_attr(not(feature = "git"), allow(dead_code))]
+
+use crate::{
+    config::Config,
+    parser::{ParsedTag, Print},
+    width,
+};
+
+/// Returns the highlights of the release like "v2: Added the login, Fixed
+/// the crash and 2 more changes", which contain up to `highlight_count`
+/// entries of the `highlight_categories` in their order of priority within
+/// `highlight_width`. Entries pinned by the `highlight_tag` come first. Entries
+/// are dropped if they do not fit anymore, a single entry is truncated
+/// instead.
+pub fn highlights(tag: &ParsedTag, config: &Config) -> String {
+    let mut entries = tag
+        .commits
+        .iter()
+        .filter_map(|commit| {
+            let category = &commit.summary.category;
+            let pinned = !config.highlight_tag.is_empty()
+                && commit.summary.contains_tag(Some(&config.highlight_tag));
+            let priority = if pinned {
+                Some(0)
+            } else {
+                config
+                    .highlight_categories
+                    .iter()
+                    .position(|c| c == category)
+                    .map(|priority| priority + 1)
+            };
+            priority.map(|priority| {
+                let entry = format!(
+                    "{} {}",
+                    config.translate(category),
+                    commit.summary.text
+                );
+                (priority, entry)
+            })
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|(priority, _)| *priority);
+
+    let prefix = format!("{}: ", tag.name);
+    let remaining = |count: usize| match tag.commits.len() - count {
+        0 => String::new(),
+        1 => " and 1 more change".to_owned(),
+        n => format!(" and {} more changes", n),
+    };
+    let mut selected: Vec<&str> = vec![];
+    for (_, entry) in entries.iter().take(config.highlight_count) {
+        let mut candidate = selected.clone();
+        candidate.push(entry);
+        let text = prefix.clone()
+            + &candidate.join(", ")
+            + &remaining(candidate.len());
+        if width::display_width(&text) > config.highlight_width {
+            break;
+        }
+        selected = candidate;
+    }
+
+    match (selected.is_empty(), entries.first()) {
+        (true, Some((_, entry))) => {
+            width::truncate(&(prefix + entry), config.highlight_width)
+        }
+        (true, None) => tag.name.clone(),
+        _ => prefix + &selected.join(", ") + &remaining(selected.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn release_highlights() {
+        let mut config = Config::new();
+        let messages = [
+            "Fixed the crash",
+            "Removed the old API",
+            "Added the login",
+            "Improved the speed",
+            "Fixed the typo",
+        ];
+        let tag = ParsedTag::from_messages("v2", &messages, &config);
+        assert_eq!(
+            highlights(&tag, &config),
+            "v2: Added the login, Improved the speed, Fixed the crash and 2 \
+             more changes"
+        );
+
+        config.highlight_width = 50;
+        assert_eq!(
+            highlights(&tag, &config),
+            "v2: Added the login and 4 more changes"
+        );
+        config.highlight_width = 12;
+        assert_eq!(highlights(&tag, &config), "v2: Added t…");
+        config.highlight_categories = vec!["Other".to_owned()];
+        assert_eq!(highlights(&tag, &config), "v2");
+
+        let mut messages = messages;
+        messages[4] = "Fixed the typo :highlight:";
+        let tag = ParsedTag::from_messages("v2", &messages, &config);
+        config.highlight_width = 280;
+        assert_eq!(
+            highlights(&tag, &config),
+            "v2: Fixed the typo and 4 more changes"
+        );
+    }
+}
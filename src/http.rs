@@ -0,0 +1,235 @@
+//! The shared HTTP client of the integrations with web services, which
+//! retries rate limited or failed requests with an exponential backoff and
+//! falls back to the cached responses if a service is not reachable. Only
+//! available if the `network` feature is enabled.
+
+// The errors of ureq contain the whole response, which is needed for retries
+#![allow(clippy::result_large_err)]
+
+use crate::{config::Config, error::ErrorKind};
+use failure::{format_err, Error, ResultExt};
+use log::{info, warn};
+use serde_json::Value;
+use std::{
+    cell::RefCell,
+    collections::{hash_map::DefaultHasher, HashMap},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+use ureq::{Request, Response};
+
+/// The delay before the first retry, which doubles for every further retry.
+static BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// The maximum delay between two retries, also for the `Retry-After` header.
+static MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// A client for JSON APIs, which caches the responses in memory and within
+/// the `http` directory of the `cache_dir`, if configured.
+pub struct Client {
+    cache_dir: Option<PathBuf>,
+    offline: bool,
+    retries: u32,
+    responses: RefCell<HashMap<String, Option<Value>>>,
+}
+
+impl Client {
+    /// Constructs a new client for the repository at the path.
+    pub fn new(config: &Config, path: &str) -> Self {
+        Client {
+            cache_dir: config
+                .cache_dir
+                .as_ref()
+                .map(|dir| Path::new(path).join(dir).join("http")),
+            offline: config.offline,
+            retries: config.http_retries,
+            responses: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Sends the GET request and returns the JSON response, where `None`
+    /// represents a missing resource. Every URL is only requested once and
+    /// the cached response is used if the request finally failed or the
+    /// client is offline.
+    ///
+    /// # Errors
+    /// If the request failed and there is no cached response.
+    pub fn get_json(&self, request: Request) -> Result<Option<Value>, Error> {
+        let url = request.url().to_owned();
+        if let Some(response) = self.responses.borrow().get(&url) {
+            return Ok(response.clone());
+        }
+        let response = if self.offline {
+            self.read_cache(&url).ok_or_else(|| {
+                format_err!("No cached response for '{}' while offline", url)
+            })?
+        } else {
+            let result = send_with_retries(self.retries, BASE_DELAY, || {
+                request.clone().call()
+            });
+            match result {
+                Ok(response) => {
+                    let json: Value = response.into_json()?;
+                    self.write_cache(&url, Some(&json));
+                    Some(json)
+                }
+                Err(ureq::Error::Status(404, _)) => {
+                    self.write_cache(&url, None);
+                    None
+                }
+                Err(e) => match self.read_cache(&url) {
+                    Some(response) => {
+                        warn!("Using the cached response of '{}': {}", url, e);
+                        response
+                    }
+                    None => return Err(e.into()),
+                },
+            }
+        };
+        self.responses.borrow_mut().insert(url, response.clone());
+        Ok(response)
+    }
+
+    /// Returns the path of the cached response of the URL.
+    fn cache_path(&self, url: &str) -> Option<PathBuf> {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        self.cache_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{:016x}.json", hasher.finish())))
+    }
+
+    /// Reads the cached response of the URL, where `Some(None)` represents a
+    /// missing resource.
+    fn read_cache(&self, url: &str) -> Option<Option<Value>> {
+        let content = fs::read_to_string(self.cache_path(url)?).ok()?;
+        match serde_json::from_str(&content).ok()? {
+            Value::Null => Some(None),
+            json => Some(Some(json)),
+        }
+    }
+
+    /// Writes the response to the cache, where failures only lead to a
+    /// warning since the response itself is valid.
+    fn write_cache(&self, url: &str, json: Option<&Value>) {
+        let path = match self.cache_path(url) {
+            Some(path) => path,
+            None => return,
+        };
+        let write = || -> Result<(), Error> {
+            let file = || ErrorKind::File(path.display().to_string());
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).context(file())?;
+            }
+            fs::write(&path, serde_json::to_string(&json)?).context(file())?;
+            Ok(())
+        };
+        if let Err(e) = write() {
+            warn!("Could not cache the response of '{}': {}", url, e);
+        }
+    }
+}
+
+/// Sends the request via the function and retries rate limited requests,
+/// server errors and transport failures with an exponential backoff, where
+/// the `Retry-After` header of the response takes precedence.
+fn send_with_retries<F>(
+    retries: u32,
+    base_delay: Duration,
+    mut send: F,
+) -> Result<Response, ureq::Error>
+where
+    F: FnMut() -> Result<Response, ureq::Error>,
+{
+    let mut attempt = 0;
+    loop {
+        let backoff = base_delay * 2u32.saturating_pow(attempt);
+        let delay = match send() {
+            Err(ureq::Error::Status(status, response))
+                if (status == 429 || status >= 500) && attempt < retries =>
+            {
+                response
+                    .header("Retry-After")
+                    .and_then(|seconds| seconds.trim().parse().ok())
+                    .map(Duration::from_secs)
+                    .unwrap_or(backoff)
+            }
+            Err(ureq::Error::Transport(_)) if attempt < retries => backoff,
+            result => return result,
+        };
+        let delay = delay.min(MAX_DELAY);
+        info!("Retrying the request in {:?}.", delay);
+        thread::sleep(delay);
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn response(status: u16, retry_after: Option<&str>) -> ureq::Error {
+        let mut raw = format!("HTTP/1.1 {} Status\r\n", status);
+        if let Some(seconds) = retry_after {
+            raw += &format!("Retry-After: {}\r\n", seconds);
+        }
+        raw += "\r\n";
+        ureq::Error::Status(status, raw.parse().unwrap())
+    }
+
+    #[test]
+    fn retried_requests() {
+        let mut statuses = vec![429, 503, 200].into_iter();
+        let result = send_with_retries(3, Duration::from_millis(1), || {
+            match statuses.next().unwrap() {
+                200 => Ok(Response::new(200, "OK", "{}").unwrap()),
+                status => Err(response(status, Some("0"))),
+            }
+        });
+        assert_eq!(result.unwrap().status(), 200);
+
+        let mut count = 0;
+        let result = send_with_retries(2, Duration::from_millis(1), || {
+            count += 1;
+            Err(response(500, None))
+        });
+        assert_eq!(count, 3);
+        assert!(matches!(result, Err(ureq::Error::Status(500, _))));
+
+        let mut count = 0;
+        let result = send_with_retries(2, Duration::from_millis(1), || {
+            count += 1;
+            Err(response(401, None))
+        });
+        assert_eq!(count, 1);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn offline_cache() {
+        let path = env::temp_dir().join("gitjournal_http_cache");
+        let _ = fs::remove_dir_all(&path);
+        let mut config = Config::new();
+        config.cache_dir = Some("cache".to_owned());
+        config.offline = true;
+        let client = Client::new(&config, path.to_str().unwrap());
+        let request = ureq::get("http://localhost:1/issue").query("a", "b");
+        assert!(client.get_json(request.clone()).is_err());
+
+        let json = serde_json::json!({"fields": {"summary": "Issue"}});
+        client.write_cache(request.url(), Some(&json));
+        client.write_cache("http://localhost:1/missing", None);
+        assert_eq!(client.get_json(request).unwrap(), Some(json));
+        assert_eq!(
+            client
+                .get_json(ureq::get("http://localhost:1/missing"))
+                .unwrap(),
+            None
+        );
+        fs::remove_dir_all(path).unwrap();
+    }
+}
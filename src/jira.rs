@@ -0,0 +1,153 @@
+//! Enrichment of the parsed commits with the issues of a JIRA instance, which
+//! is only available if the `network` feature is enabled.
+
+use crate::{
+    config::Config,
+    encoding::base64,
+    http::Client,
+    parser::{Issue, ParsedTag},
+};
+use failure::{format_err, Error};
+use log::{info, warn};
+use serde_json::Value;
+use std::{collections::BTreeMap, env, time::Duration};
+
+/// The environment variable containing the JIRA user name or email address.
+static ENV_USER: &str = "JIRA_USER";
+
+/// The environment variable containing the JIRA API token or password.
+static ENV_TOKEN: &str = "JIRA_TOKEN";
+
+/// Adds the title and status of the referenced JIRA issue to every commit
+/// with a prefix like "JIRA-1234". Every issue is only fetched once and
+/// issues which could not be fetched are skipped with a warning.
+pub fn enrich(result: &mut [ParsedTag], config: &Config, client: &Client) {
+    if let Some(ref url) = config.jira_url {
+        enrich_with(result, |key| fetch_issue(client, url, key));
+    }
+}
+
+/// Adds the issues retrieved by the `fetch` function to the commits.
+fn enrich_with<F>(result: &mut [ParsedTag], mut fetch: F)
+where
+    F: FnMut(&str) -> Result<Issue, Error>,
+{
+    let mut issues: BTreeMap<String, Option<Issue>> = BTreeMap::new();
+    for tag in result.iter_mut() {
+        for commit in &mut tag.commits {
+            let key = commit.summary.prefix.clone();
+            if key.is_empty() {
+                continue;
+            }
+            let issue =
+                issues
+                    .entry(key)
+                    .or_insert_with_key(|key| match fetch(key) {
+                        Ok(issue) => Some(issue),
+                        Err(e) => {
+                            warn!(
+                                "Could not fetch JIRA issue '{}': {}",
+                                key, e
+                            );
+                            None
+                        }
+                    });
+            commit.summary.issue = issue.clone();
+        }
+    }
+    info!("Fetched {} JIRA issues.", issues.values().flatten().count());
+}
+
+/// Fetches a single issue via the JIRA REST API.
+fn fetch_issue(client: &Client, url: &str, key: &str) -> Result<Issue, Error> {
+    let request = ureq::get(&format!(
+        "{}/rest/api/2/issue/{}",
+        url.trim_end_matches('/'),
+        key
+    ))
+    .query("fields", "summary,status")
+    .timeout(Duration::from_secs(10));
+
+    // Cloud instances use basic authentication with an API token, while
+    // personal access tokens of server instances are used as bearer tokens
+    let request = match (env::var(ENV_USER), env::var(ENV_TOKEN)) {
+        (Ok(user), Ok(token)) => request.set(
+            "Authorization",
+            &format!(
+                "Basic {}",
+                base64(format!("{}:{}", user, token).as_bytes())
+            ),
+        ),
+        (Err(_), Ok(token)) => {
+            request.set("Authorization", &format!("Bearer {}", token))
+        }
+        _ => request,
+    };
+    match client.get_json(request)? {
+        Some(json) => parse_issue(&json),
+        None => Err(format_err!("Issue not found")),
+    }
+}
+
+/// Parses the issue from the JSON response of the JIRA REST API.
+fn parse_issue(json: &Value) -> Result<Issue, Error> {
+    let fields = &json["fields"];
+    let title = fields["summary"]
+        .as_str()
+        .ok_or_else(|| format_err!("Missing issue summary"))?;
+    let status = fields["status"]["name"]
+        .as_str()
+        .ok_or_else(|| format_err!("Missing issue status"))?;
+    Ok(Issue {
+        title: title.to_owned(),
+        status: status.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use failure::bail;
+
+    #[test]
+    fn parse_issue_response() {
+        let json = serde_json::from_str(
+            r#"{"key": "JIRA-1", "fields": {"summary": "Some issue",
+                "status": {"name": "Done"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            parse_issue(&json).unwrap(),
+            Issue {
+                title: "Some issue".to_owned(),
+                status: "Done".to_owned(),
+            }
+        );
+        assert!(parse_issue(&Value::Null).is_err());
+    }
+
+    #[test]
+    fn enrich_commits() {
+        let mut result = vec![ParsedTag::from_messages(
+            "v1",
+            &["JIRA-1 Added a", "JIRA-1 Fixed b", "JIRA-2 Fixed c"],
+            &Config::new(),
+        )];
+        let mut fetched = vec![];
+        enrich_with(&mut result, |key| {
+            fetched.push(key.to_owned());
+            if key == "JIRA-2" {
+                bail!("Not found");
+            }
+            Ok(Issue {
+                title: "Some issue".to_owned(),
+                status: "Done".to_owned(),
+            })
+        });
+        assert_eq!(fetched, vec!["JIRA-1", "JIRA-2"]);
+        let commits = &result[0].commits;
+        assert_eq!(commits[0].summary.issue, commits[1].summary.issue);
+        assert!(commits[0].summary.issue.is_some());
+        assert!(commits[2].summary.issue.is_none());
+    }
+}
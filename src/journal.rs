@@ -0,0 +1,106 @@
+//! A read-only snapshot of the parsing results, which can be shared between
+//! threads for rendering.
+
+use crate::{
+    config::Config,
+    parser::{ParsedTag, Parser},
+};
+use failure::Error;
+
+/// The parsing results of a `GitJournal` together with the configuration used
+/// for rendering. Since a `ParsedJournal` does not refer to the repository
+/// anymore, it is `Send` and `Sync` and can be rendered concurrently, for
+/// example by wrapping it into an `Arc`.
+///
+/// # Examples
+///
+/// ```
+/// use gitjournal::{GitJournal, ParseOptions};
+/// use std::{sync::Arc, thread};
+///
+/// let mut journal = GitJournal::new(".").unwrap();
+/// journal.parse_log(&ParseOptions::new("HEAD"));
+/// let parsed = Arc::new(journal.parsed());
+///
+/// let handle = {
+///     let parsed = parsed.clone();
+///     thread::spawn(move || parsed.render(true, None))
+/// };
+/// println!("{}", parsed.render(false, None).unwrap());
+/// handle.join().unwrap().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct ParsedJournal {
+    parser: Parser,
+    template: Option<String>,
+}
+
+impl ParsedJournal {
+    /// Constructs a new `ParsedJournal` from the parsing results. The
+    /// template will be used if no other one is given during rendering.
+    #[cfg(feature = "git")]
+    pub(crate) fn new(parser: Parser, template: Option<String>) -> Self {
+        ParsedJournal { parser, template }
+    }
+
+    /// Returns the configuration used for rendering.
+    pub fn config(&self) -> &Config {
+        &self.parser.config
+    }
+
+    /// Returns the parsed releases, starting with the newest one.
+    pub fn releases(&self) -> &[ParsedTag] {
+        &self.parser.result
+    }
+
+    /// Renders the whole log in a short or detailed variant like
+    /// `GitJournal::print_log`, but returns it instead of printing it.
+    ///
+    /// # Errors
+    /// If some commit message could not be rendered.
+    pub fn render(
+        &self,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<String, Error> {
+        let output_vec = self.parser.render(
+            &compact,
+            template.or(self.template.as_deref()),
+            &self.parser.config,
+        )?;
+        Ok(String::from_utf8(output_vec)?)
+    }
+
+    /// Renders only the section of a single release like
+    /// `GitJournal::render_release`.
+    ///
+    /// # Errors
+    /// If the release is not part of the parsing results or rendering failed.
+    pub fn render_release(
+        &self,
+        release: &str,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<String, Error> {
+        let parser =
+            self.parser.select_release(release, &self.parser.config)?;
+        let output_vec = parser.render(
+            &compact,
+            template.or(self.template.as_deref()),
+            &parser.config,
+        )?;
+        Ok(String::from_utf8(output_vec)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn parsed_journal_is_send_and_sync() {
+        assert_send_sync::<ParsedJournal>();
+    }
+}
@@ -9,9 +9,9 @@
 //! ### Example usage
 //!
 //! ```
-//! use gitjournal::GitJournal;
+//! use gitjournal::{GitJournal, ParseOptions};
 //! let mut journal = GitJournal::new(".").unwrap();
-//! journal.parse_log("HEAD", "rc", &1, &false, &true, None);
+//! journal.parse_log(&ParseOptions::new("HEAD").skip_unreleased(true));
 //! journal
 //!     .print_log(true, None, None)
 //!     .expect("Could not print short log.");
@@ -25,33 +25,183 @@
 //! (contains `"rc"`). After that parsing the log will be printed in the
 //! shortest possible format.
 
+pub use crate::backend::OutputBackend;
 pub use crate::config::Config;
-use crate::parser::{ParsedTag, Parser, Print, Tags};
-use chrono::{offset::Utc, TimeZone};
-use failure::{bail, Error};
+#[cfg(feature = "git")]
+use crate::config::CONFIG_FILE_NAME;
+pub use crate::diff::{CategoryDiff, ReleaseDiff};
+pub use crate::error::{ErrorKind, Report};
+pub use crate::journal::ParsedJournal;
+#[cfg(feature = "git")]
+use crate::links::Provider;
+pub use crate::options::{Cancellation, ParseOptions};
+use crate::parser::Parser;
+pub use crate::parser::{
+    BodyElement, FooterElement, Issue, ListElement, ParagraphElement,
+    ParsedCommit, ParsedTag, SearchResult, SummaryElement,
+};
+#[cfg(feature = "git")]
+use crate::parser::{Print, Tags, MERGED_TAGS_SEPARATOR};
+pub use crate::quality::ReleaseQuality;
+pub use crate::rules::Rule;
+pub use crate::warnings::{Warning, Warnings};
+#[cfg(feature = "git")]
+use chrono::{offset::Utc, DateTime, TimeZone};
+#[cfg(feature = "git")]
+use failure::{bail, format_err};
+use failure::{Error, ResultExt};
+#[cfg(feature = "git")]
 use git2::{ObjectType, Oid, Repository};
-use log::{info, warn, LevelFilter};
+#[cfg(feature = "logger")]
+use log::LevelFilter;
+#[cfg(feature = "git")]
+use log::{info, warn};
+#[cfg(feature = "git")]
 use rayon::prelude::*;
+#[cfg(feature = "git")]
+use regex::Regex;
+#[cfg(feature = "git")]
+use semver::Version;
+#[cfg(feature = "git")]
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "git")]
 use std::{
+    cmp::Reverse,
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap, HashSet},
     env,
     fs::{self, File, OpenOptions},
+    hash::{Hash, Hasher},
     io::prelude::*,
+    mem,
     path::{Path, PathBuf},
+    process::Command,
+    str, thread,
+    time::Duration,
 };
+#[cfg(feature = "git")]
 use toml::{map::Map, Value};
 
+#[cfg(feature = "git")]
+mod actions;
+pub mod annotations;
+#[cfg(feature = "network")]
+mod azure;
+mod backend;
+#[cfg(feature = "git")]
+mod branches;
+#[cfg(feature = "git")]
+mod cargo;
+#[cfg(feature = "git")]
+mod changes;
 pub mod config;
+mod diff;
+#[cfg(feature = "git")]
+mod email;
+#[cfg(feature = "git")]
+mod encoding;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "network")]
+mod gitlab;
+mod highlights;
+#[cfg(feature = "network")]
+mod http;
+#[cfg(feature = "network")]
+mod jira;
+mod journal;
+mod links;
+#[cfg(feature = "git")]
+mod markers;
+#[cfg(feature = "network")]
+mod notify;
+mod oid;
+mod options;
+pub mod output;
+#[cfg(feature = "git")]
+mod pages;
 mod parser;
+#[cfg(feature = "python")]
+mod python;
+pub mod quality;
+mod rules;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "git")]
+mod signing;
+#[cfg(feature = "spellcheck")]
+mod spellcheck;
+#[cfg(feature = "tui")]
+pub mod tui;
+mod warnings;
+mod width;
+
+/// The version of the structured output, which is increased on incompatible
+/// changes of the data model.
+pub static FORMAT_VERSION: u32 = 1;
+
+/// The JSON Schema of the structured output like `GitJournal::to_json`.
+pub static JSON_SCHEMA: &str = include_str!("../schema/journal.schema.json");
+
+/// A release as tuple of the tagged commit, the tag names and the commit date.
+#[cfg(feature = "git")]
+type Release = (Oid, Vec<String>, DateTime<Utc>);
+
+/// The root of the structured output.
+#[cfg(feature = "git")]
+#[derive(Serialize)]
+struct Document<'a> {
+    format_version: u32,
+    releases: &'a [ParsedTag],
+}
+
+/// The number of rendered logs kept within the `cache_dir`.
+#[cfg(feature = "git")]
+static CACHED_LOGS: usize = 16;
+
+/// A log rendered by `render_cached`, which is stored as JSON within the
+/// `cache_dir`.
+#[cfg(feature = "git")]
+#[derive(Deserialize, Serialize)]
+struct CachedLog {
+    log: String,
+    output: Vec<output::Segment>,
+    warnings: Vec<String>,
+}
+
+/// A transformation of the raw commit messages before parsing.
+#[cfg(feature = "git")]
+type MessageHook = Box<dyn Fn(&str) -> String + Send + Sync>;
+
+/// A transformation of the parsed commits.
+#[cfg(feature = "git")]
+type EntryHook = Box<dyn Fn(&mut ParsedCommit) + Send + Sync>;
+
+/// The compiled `entry_replacements` and `redact` patterns.
+#[cfg(feature = "git")]
+type Replacements = (Vec<(Regex, String)>, Vec<Regex>);
 
 /// The main structure of git-journal.
+#[cfg(feature = "git")]
 pub struct GitJournal {
     /// The configuration structure
     pub config: Config,
     parser: Parser,
     path: String,
     tags: Vec<(Oid, String)>,
+    tag_titles: HashMap<String, String>,
+    repo_warnings: Vec<Warning>,
+    manual_entries: Vec<ParsedCommit>,
+    backends: Vec<Box<dyn OutputBackend>>,
+    rules: Vec<Box<dyn Rule>>,
+    message_hooks: Vec<MessageHook>,
+    entry_hooks: Vec<EntryHook>,
+    /// Counts the registered rules, hooks and backends, which invalidates
+    /// the cached logs
+    generation: usize,
 }
 
+#[cfg(feature = "git")]
 impl GitJournal {
     /// Constructs a new `GitJournal`. Searches upwards if the given path does
     /// not contain the `.git` directory.
@@ -68,34 +218,145 @@ impl GitJournal {
     /// When not providing a path with a valid git repository ('.git' folder or
     /// the initial parsing of the git tags failed.
     pub fn new(path: &str) -> Result<Self, Error> {
-        // Search upwards for the .git directory
         let mut path_buf = if path != "." {
             PathBuf::from(path)
         } else {
             env::current_dir()?
         };
-        'git_search: loop {
-            for dir in fs::read_dir(&path_buf)? {
-                let dir_path = dir?.path();
-                if dir_path.ends_with(".git") {
-                    break 'git_search;
+
+        // Open the repository, which can also be a bare one without any
+        // working tree. Otherwise search upwards for the .git directory.
+        let repo = match Repository::open(&path_buf) {
+            Ok(repo) if repo.is_bare() => repo,
+            _ => {
+                'git_search: loop {
+                    for dir in fs::read_dir(&path_buf)? {
+                        let dir_path = dir?.path();
+                        if dir_path.ends_with(".git") {
+                            break 'git_search;
+                        }
+                    }
+                    if !path_buf.pop() {
+                        break;
+                    }
+                }
+                Repository::open(&path_buf)?
+            }
+        };
+
+        // Search for config in path and load, where bare repositories use the
+        // configuration of their HEAD
+        let mut new_config = Config::new();
+        let loaded = if repo.is_bare() {
+            Self::read_config(&repo, "HEAD").map(|config| new_config = config)
+        } else {
+            new_config.load(path)
+        };
+        if let Err(e) = loaded {
+            println!("Can't load configuration file, using default one: {}", e);
+        }
+
+        // Setup the logger if not already set
+        #[cfg(feature = "logger")]
+        {
+            if new_config.enable_debug {
+                if new_config.colored_output {
+                    if mowl::init_with_level(LevelFilter::Info).is_err() {
+                        warn!("Logger already set.");
+                    };
+                } else {
+                    if mowl::init_with_level_and_without_colors(
+                        LevelFilter::Info,
+                    )
+                    .is_err()
+                    {
+                        warn!("Logger already set.");
+                    };
                 }
             }
-            if !path_buf.pop() {
-                break;
+        }
+
+        // Deepen a shallow clone including its tags, since the history before
+        // its boundary is missing otherwise
+        if new_config.unshallow && repo.is_shallow() {
+            if let Err(e) = Self::unshallow(&path_buf) {
+                warn!("Could not deepen the shallow clone: {}", e);
+            }
+        }
+
+        // Update the tags from the remote, where a failure should not prevent
+        // the parsing of the local ones
+        if new_config.fetch_tags {
+            if let Err(e) = Self::fetch_tags(&repo, &new_config.fetch_remote) {
+                warn!(
+                    "Could not fetch the tags from '{}': {}",
+                    new_config.fetch_remote, e
+                );
             }
         }
 
-        // Open the repository
-        let repo = Repository::open(&path_buf)?;
+        // Page the output like `git log` if no pager is configured
+        if new_config.pager.is_none() {
+            new_config.pager = repo
+                .config()
+                .and_then(|config| config.get_string("core.pager"))
+                .ok();
+        }
+
+        // Complete the links to the repository from the configured provider
+        // and the URL of the remote
+        Self::detect_repository_links(&repo, &mut new_config);
+
+        // Create a new parser with empty results
+        let new_parser = Parser {
+            config: new_config.clone(),
+            result: vec![],
+        };
+
+        // Return the git journal object
+        let mut journal = GitJournal {
+            config: new_config,
+            parser: new_parser,
+            path: path_buf.to_str().unwrap_or("").to_owned(),
+            tags: vec![],
+            tag_titles: HashMap::new(),
+            repo_warnings: vec![],
+            manual_entries: vec![],
+            backends: vec![],
+            rules: vec![],
+            message_hooks: vec![],
+            entry_hooks: vec![],
+            generation: 0,
+        };
+        journal.load_tags(&repo)?;
+        Ok(journal)
+    }
 
-        // Get all available tags in some vector of tuples
+    /// Loads all available tags of the repository, where the tags are peeled
+    /// to the commits they point to, together with the titles of annotated
+    /// tags and the warnings about the repository.
+    fn load_tags(&mut self, repo: &Repository) -> Result<(), Error> {
         let mut new_tags = vec![];
-        for name in repo.tag_names(None)?.iter() {
-            let name = name.ok_or_else(|| {
-                git2::Error::from_str("Could not receive tag name")
-            })?;
+        let mut tag_titles = HashMap::new();
+        let mut repo_warnings = vec![];
+        if repo.is_shallow() {
+            repo_warnings.push(Warning::ShallowClone);
+        }
+        for name in repo.tag_names(None)?.iter_bytes() {
+            let name = match str::from_utf8(name) {
+                Ok(name) => name,
+                Err(_) => {
+                    repo_warnings.push(Warning::InvalidTagName(
+                        String::from_utf8_lossy(name).into_owned(),
+                    ));
+                    continue;
+                }
+            };
             let obj = repo.revparse_single(name)?;
+            let commit = match obj.peel(ObjectType::Commit) {
+                Ok(commit) => commit.id(),
+                Err(_) => continue,
+            };
             if let Ok(tag) = obj.into_tag() {
                 let tag_name = tag
                     .name()
@@ -103,44 +364,181 @@ impl GitJournal {
                         git2::Error::from_str("Could not parse tag name")
                     })?
                     .to_owned();
-                new_tags.push((tag.target_id(), tag_name));
+                let title = tag
+                    .message()
+                    .and_then(|message| message.lines().next())
+                    .map(|title| title.trim())
+                    .filter(|title| !title.is_empty());
+                if let Some(title) = title {
+                    tag_titles.insert(tag_name.clone(), title.to_owned());
+                }
+                new_tags.push((commit, tag_name));
             }
         }
 
-        // Search for config in path and load
-        let mut new_config = Config::new();
-        if let Err(e) = new_config.load(path) {
-            println!("Can't load configuration file, using default one: {}", e);
-        }
+        self.tags = new_tags;
+        self.tag_titles = tag_titles;
+        self.repo_warnings = repo_warnings;
+        Ok(())
+    }
 
-        // Setup the logger if not already set
-        if new_config.enable_debug {
-            if new_config.colored_output {
-                if mowl::init_with_level(LevelFilter::Info).is_err() {
-                    warn!("Logger already set.");
-                };
+    /// Reads the configuration file from the tree of the given revision.
+    fn read_config(repo: &Repository, revision: &str) -> Result<Config, Error> {
+        let file =
+            || ErrorKind::File(format!("{}:{}", revision, CONFIG_FILE_NAME));
+        let tree = repo.revparse_single(revision)?.peel_to_tree()?;
+        let entry = tree
+            .get_name(CONFIG_FILE_NAME)
+            .ok_or_else(|| format_err!("No such file"))
+            .context(file())?;
+        let blob = entry.to_object(repo)?.peel_to_blob()?;
+        let toml_string = str::from_utf8(blob.content()).context(file())?;
+        let mut config = Config::new();
+        config.load_str(toml_string).context(file())?;
+        Ok(config)
+    }
+
+    /// Loads the configuration from the tree of a revision instead of the
+    /// working tree, e.g. to generate the notes of a fetched ref like
+    /// "origin/master" without checking it out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.load_config_from("HEAD").expect("Could not load config.");
+    /// ```
+    ///
+    /// # Errors
+    /// When the revision does not contain a valid configuration file.
+    pub fn load_config_from(&mut self, revision: &str) -> Result<(), Error> {
+        let repo = Repository::open(&self.path)?;
+        self.config = Self::read_config(&repo, revision)?;
+        self.parser.config = self.config.clone();
+        Ok(())
+    }
+
+    /// Fetches all tags from the remote, where the credentials are taken from
+    /// the SSH agent or the configured git credential helper.
+    fn fetch_tags(repo: &Repository, remote: &str) -> Result<(), Error> {
+        let config = repo.config()?;
+        let mut callbacks = git2::RemoteCallbacks::new();
+        callbacks.credentials(|url, username, allowed| {
+            if allowed.contains(git2::CredentialType::SSH_KEY) {
+                git2::Cred::ssh_key_from_agent(username.unwrap_or("git"))
             } else {
-                if mowl::init_with_level_and_without_colors(LevelFilter::Info)
-                    .is_err()
-                {
-                    warn!("Logger already set.");
-                };
+                git2::Cred::credential_helper(&config, url, username)
             }
-        }
+        });
+        let mut options = git2::FetchOptions::new();
+        options.remote_callbacks(callbacks);
+        repo.find_remote(remote)?.fetch(
+            &["+refs/tags/*:refs/tags/*"],
+            Some(&mut options),
+            None,
+        )?;
+        info!("Fetched the tags from '{}'.", remote);
+        Ok(())
+    }
 
-        // Create a new parser with empty results
-        let new_parser = Parser {
-            config: new_config.clone(),
-            result: vec![],
+    /// Completes the `repository_url` from the URL of the `fetch_remote` if
+    /// only the `provider` is configured. Links are disabled with a warning
+    /// if the provider is unknown or can not be detected.
+    fn detect_repository_links(repo: &Repository, config: &mut Config) {
+        let provider = match config.provider {
+            Some(ref name) => match Provider::from_name(name) {
+                Ok(provider) => Some(provider),
+                Err(e) => {
+                    warn!(
+                        "Disabling the repository links: {}",
+                        Report::from(e)
+                    );
+                    config.provider = None;
+                    config.repository_url = None;
+                    return;
+                }
+            },
+            None => None,
         };
+        if config.repository_url.is_none() && provider.is_some() {
+            config.repository_url = repo
+                .find_remote(&config.fetch_remote)
+                .ok()
+                .and_then(|remote| remote.url().map(str::to_owned))
+                .and_then(|url| links::web_url(&url, provider));
+            if config.repository_url.is_none() {
+                warn!(
+                    "Could not detect the repository URL from the remote '{}', \
+                     please set `repository_url`.",
+                    config.fetch_remote
+                );
+            }
+        }
+        if let Some(ref url) = config.repository_url {
+            if provider.is_none() && Provider::detect(url).is_none() {
+                warn!(
+                    "Could not detect the provider of '{}', please set \
+                     `provider`.",
+                    url
+                );
+            }
+        }
+    }
 
-        // Return the git journal object
-        Ok(GitJournal {
-            config: new_config,
-            parser: new_parser,
-            path: path_buf.to_str().unwrap_or("").to_owned(),
-            tags: new_tags,
-        })
+    /// Returns the link to the commit within the web interface of the
+    /// `repository_url`, if configured.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.config.provider = Some("gitea".to_owned());
+    /// journal.config.repository_url =
+    ///     Some("https://codeberg.org/user/project".to_owned());
+    /// assert_eq!(
+    ///     journal.commit_url("0123456").unwrap().unwrap(),
+    ///     "https://codeberg.org/user/project/commit/0123456",
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// When the configured `provider` is unknown.
+    pub fn commit_url(&self, oid: &str) -> Result<Option<String>, Error> {
+        Ok(Provider::from_config(&self.config)?
+            .map(|(provider, url)| provider.commit_url(url, oid)))
+    }
+
+    /// Returns the link to the changes between two revisions like tags
+    /// within the web interface of the `repository_url`, if configured.
+    ///
+    /// # Errors
+    /// When the configured `provider` is unknown.
+    pub fn compare_url(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<String>, Error> {
+        Ok(Provider::from_config(&self.config)?
+            .map(|(provider, url)| provider.compare_url(url, from, to)))
+    }
+
+    /// Fetches the complete history and all tags of a shallow clone. Deepening
+    /// is not supported by libgit2, which is why the git command line is used.
+    fn unshallow(path: &Path) -> Result<(), Error> {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(path)
+            .args(["fetch", "--unshallow", "--tags"])
+            .status()?;
+        if !status.success() {
+            bail!("git fetch --unshallow failed with {}", status);
+        }
+        info!("Deepened the shallow clone.");
+        Ok(())
     }
 
     /// Does the setup on the target git repository.
@@ -158,39 +556,383 @@ impl GitJournal {
     /// path, which looks like:
     ///
     /// ```toml
+    /// # The Azure Boards project for linking work item references like
+    /// # "AB#1234", whose existence is validated if the `network` feature is
+    /// # enabled and the `AZURE_DEVOPS_TOKEN` environment variable is set.
+    /// # Can be removed from the configuration file as well.
+    /// azure_boards_url = "https://dev.azure.com/organization/project"
+    ///
+    /// # The directory of the cached logs relative to the repository, which
+    /// # are reused while the history and the configuration are unchanged.
+    /// # Can be removed from the configuration file as well.
+    /// cache_dir = ".git/gitjournal-cache"
+    ///
+    /// # Name the unreleased section after the version within the
+    /// # `Cargo.toml` of the repository, which is used by the `pre-release`
+    /// # subcommand as well. Can be removed from the configuration file.
+    /// cargo_version = "v{version}"
+    ///
     /// # Specifies the available categories for the commit message, allowed regular expressions.
     /// categories = ["Added", "Changed", "Fixed", "Improved", "Removed"]
     ///
     /// # Set the characters where the categories are wrapped in
     /// category_delimiters = ["[", "]"]
     ///
+    /// # The display order of the categories, where unlisted categories are
+    /// # printed afterwards
+    /// category_order = ["Fixed", "Added"]
+    ///
+    /// # Text which will be printed once before or after the generated log.
+    /// # Can be removed from the configuration file as well.
+    /// changelog_header = "# Changelog"
+    /// changelog_footer = "Released under the MIT license."
+    ///
     /// # Set to false if the output should not be colored
     /// colored_output = true
     ///
+    /// # The format of the release dates in `strftime` notation, e.g. "%Y-%m-%d"
+    /// date_format = "%Y-%m-%d"
+    ///
+    /// # Report commits whose change has been cherry-picked within the parsed
+    /// # range only once, identified via their patch id
+    /// deduplicate_cherry_picks = false
+    ///
     /// # Specifies the default template. Will be used for tag validation and printing. Can be
     /// # removed from the configuration file as well.
     /// default_template = "CHANGELOG.toml"
     ///
+    /// # The sender and recipients of the release announcements via
+    /// # `--format email`. Can be removed from the configuration file as well.
+    /// email_from = "Release Bot <bot@example.com>"
+    /// email_to = "team@example.com"
+    ///
+    /// # Add an HTML alternative to the plain text release announcements
+    /// email_html = false
+    ///
+    /// # The handling of releases without any valid commit, which are omitted
+    /// # from the output, either "warn" (default), "error" or "off"
+    /// empty_releases = "warn"
+    ///
+    /// # The placeholder printed for template sections without any entries.
+    /// # Empty sections are omitted if removed from the configuration file.
+    /// empty_section_placeholder = "None."
+    ///
     /// # Show or hide the debug messages like `[OKAY] ...` or `[INFO] ...`
     /// enable_debug = true
     ///
+    /// # Regular expressions for the commit summaries to be excluded
+    /// exclude_patterns = ["^Merge ", "^fixup!", "^squash!", "^amend!", "\\[skip changelog\\]"]
+    ///
     /// # Excluded tags in an array, e.g. "internal"
     /// excluded_commit_tags = []
     ///
     /// # Enable or disable the output and accumulation of commit footers.
     /// enable_footers = false
     ///
+    /// # The remote to fetch the tags from if `fetch_tags` is enabled
+    /// fetch_remote = "origin"
+    ///
+    /// # Fetch the tags from the `fetch_remote` before parsing, which avoids
+    /// # missing recently pushed releases on stale checkouts
+    /// fetch_tags = false
+    ///
+    /// # Follow only the first parent of merge commits during parsing, which
+    /// # skips the commits of merged branches
+    /// first_parent = false
+    ///
+    /// # The verification of transient `fixup!`, `squash!` and `amend!`
+    /// # commits, which are either accepted without further checks by "allow"
+    /// # (default) or rejected by "reject", e.g. before merging a branch. With
+    /// # "allow" they also pass `verify --range` unless `--pre-merge` is given
+    /// fixup_commits = "allow"
+    ///
+    /// # Fold commits with identical summaries into a single entry, which
+    /// # contains the count or the commit hashes if `show_commit_hash` is set
+    /// fold_duplicates = false
+    ///
+    /// # Fold pre-release tags like `v1.2.0-rc1` into the section of their
+    /// # final release `v1.2.0` if it already exists
+    /// fold_prereleases = false
+    ///
+    /// # A directory with changelog fragments like `123.fixed.md`, which are
+    /// # added to the unreleased section and removed by `pre-release`. The
+    /// # category is taken from the file name, otherwise the file has to
+    /// # contain a valid commit message. Can be removed from the configuration
+    /// # file as well.
+    /// fragments_dir = "changelog.d"
+    ///
+    /// # A link to the full log, which will be added to the line summarizing
+    /// # the entries exceeding `max_entries_per_category`. Can be removed from
+    /// # the configuration file as well.
+    /// full_log_url = "https://github.com/user/project/commits/master"
+    ///
+    /// # Group the entries of every release by their first tag with this
+    /// # prefix into subsections, e.g. `:epic/auth:` becomes "Auth". Can be
+    /// # removed from the configuration file as well.
+    /// group_tag_prefix = "epic/"
+    ///
+    /// # The categories of the entries selected as highlights of a release by
+    /// # their priority, entries of other categories are never highlighted
+    /// highlight_categories = ["Added", "Changed", "Improved", "Fixed"]
+    ///
+    /// # The maximum number of entries within the highlights of a release
+    /// highlight_count = 3
+    ///
+    /// # The tag which pins an entry to the "Highlights" at the top of its
+    /// # release like `:highlight:`, where an empty tag disables the pinning
+    /// highlight_tag = "highlight"
+    ///
+    /// # The maximum width of the highlights of a release, e.g. the character
+    /// # limit of a post on social media
+    /// highlight_width = 280
+    ///
+    /// # The number of retries of rate limited or failed requests to web
+    /// # services like JIRA, with an exponential backoff
+    /// http_retries = 3
+    ///
+    /// # The URL of a JIRA instance to fetch the title and status of the
+    /// # issues referenced by the commit prefixes. Requires the `network`
+    /// # feature, the credentials are taken from the `JIRA_USER` and
+    /// # `JIRA_TOKEN` environment variables. Can be removed from the
+    /// # configuration file as well.
+    /// jira_url = "https://company.atlassian.net"
+    ///
+    /// # The categories of breaking changes, which lead to a major version bump
+    /// # like the `:breaking:` tag
+    /// major_categories = ["Removed"]
+    ///
+    /// # Restrict the output to the releases of a single major version. Can be
+    /// # removed from the configuration file as well.
+    /// major_version = 1
+    ///
+    /// # A file with manual entries for the unreleased section, which do not
+    /// # correspond to any commit, like `entries = ["Added the docs site"]`.
+    /// # Can be removed from the configuration file as well.
+    /// manual_entries_file = "journal.toml"
+    ///
+    /// # The maximum width of the body lines during the verification, where
+    /// # comments and single words like URLs are skipped and 0 disables the
+    /// # check
+    /// max_body_line_length = 72
+    ///
+    /// # The maximum number of entries per category within a release, where
+    /// # the remaining ones will be summarized in a single line. Can be
+    /// # removed from the configuration file as well.
+    /// max_entries_per_category = 10
+    ///
+    /// # The GitLab merge requests referenced like "See merge request
+    /// # group/project!123" within merge commits, which are either ignored by
+    /// # "off" (default), assigned to the merged commits by "link" or
+    /// # additionally fetched for their title and description by "fetch",
+    /// # which requires the `network` feature and a `GITLAB_TOKEN` for
+    /// # private projects
+    /// merge_requests = "off"
+    ///
+    /// # The width at which the paragraphs are wrapped when formatting a
+    /// # commit message
+    /// message_width = 72
+    ///
+    /// # The categories of new features, which lead to a minor version bump
+    /// minor_categories = ["Added"]
+    ///
+    /// # The minimum semantic version of the releases to be printed. Can be
+    /// # removed from the configuration file as well.
+    /// min_version = "1.0.0"
+    ///
+    /// # How multiple release tags on the same commit are printed, either
+    /// # "merged" (default) into a single section or "separate"
+    /// multiple_tags = "merged"
+    ///
+    /// # Skip all requests to web services like JIRA and use only the
+    /// # responses cached within the `cache_dir`
+    /// offline = false
+    ///
+    /// # The command of the pager if `use_pager` is set, which defaults to the
+    /// # `core.pager` of git like `git log`, where `$GIT_PAGER` takes
+    /// # precedence. Can be removed from the configuration file as well.
+    /// pager = "less -S"
+    ///
+    /// # Parse a leading gitmoji of the commit summary like "✨" or
+    /// # ":sparkles:" into its category, e.g. "Added"
+    /// parse_gitmoji = false
+    ///
+    /// # The hosting provider of the `repository_url`, one of "github",
+    /// # "gitlab", "bitbucket", "bitbucket-server" or "gitea" (alias "forgejo"),
+    /// # which is detected from the host if unset. Can be removed from the
+    /// # configuration file as well.
+    /// provider = "gitea"
+    ///
+    /// # The link to a pull request without its number, which defaults to the
+    /// # one of the `provider`. Can be removed from the configuration file as
+    /// # well.
+    /// pull_request_url = "https://github.com/user/project/pull/"
+    ///
+    /// # Regular expressions for sensitive content like passwords or internal
+    /// # host names, e.g. "internal\\.corp\\.\\S+", which will be replaced by
+    /// # "[redacted]" within the texts and footers of all entries and the tag
+    /// # titles before any output
+    /// redact = []
+    ///
+    /// # Patterns of branches like "wip/*", where `*` matches any characters,
+    /// # on which the verification of new commit messages is relaxed
+    /// relaxed_branches = []
+    ///
+    /// # The verification on the `relaxed_branches`, where invalid commit
+    /// # messages are either only reported by "warn" (default) or not verified
+    /// # at all by "off"
+    /// relaxed_verification = "warn"
+    ///
+    /// # The web interface of the repository for linking the commit hashes,
+    /// # which is derived from the `fetch_remote` if only the `provider` is
+    /// # set. Can be removed from the configuration file as well.
+    /// repository_url = "https://codeberg.org/user/project"
+    ///
+    /// # Keep the output byte-stable for identical inputs, where the date of
+    /// # the unreleased section is taken from `SOURCE_DATE_EPOCH` or the
+    /// # commit time of `HEAD`, the dates are printed in UTC and the command
+    /// # line prints without colors
+    /// reproducible = false
+    ///
+    /// # The sorting of the commits while walking through the history, a
+    /// # combination of "time" (default), "topological" and "reverse"
+    /// revwalk_sorting = ["time"]
+    ///
+    /// # The maximum display width of the summaries in the short output, longer
+    /// # summaries will be truncated with an ellipsis
+    /// short_summary_width = 72
+    ///
+    /// # Wrap the summaries in the short output at `short_summary_width`
+    /// # instead of truncating them
+    /// short_summary_wrap = false
+    ///
+    /// # Annotate the entries whose change has been cherry-picked onto another
+    /// # commit with "(backported)"
+    /// show_backports = false
+    ///
     /// # Show or hide the commit hash for every entry
     /// show_commit_hash = false
     ///
     /// # Show or hide the commit message prefix, e.g. JIRA-1234
     /// show_prefix = false
     ///
+    /// # Show or hide the pull request numbers taken from summary suffixes
+    /// # like "(#1234)", which are linked if `pull_request_url` is set
+    /// show_pull_requests = true
+    ///
+    /// # Show the title of annotated tags after the release name, e.g.
+    /// # "v1.3.0 — The Speedy Release"
+    /// show_tag_titles = false
+    ///
+    /// # Sign the changelog written to a file with GPG, either "off"
+    /// # (default), "detached" for a signature file like "CHANGELOG.md.asc"
+    /// # or "trailer" for a signature block after the written Markdown
+    /// sign_output = "off"
+    ///
+    /// # The GPG key for signing the output, which defaults to the
+    /// # `user.signingkey` of git. Can be removed from the configuration file
+    /// # as well.
+    /// signing_key = "0123456789ABCDEF"
+    ///
+    /// # The `Signed-off-by: Name <email>` trailer of the Developer
+    /// # Certificate of Origin during the verification, either "off"
+    /// # (default), "required" or "author", where the trailer has to match
+    /// # the author
+    /// signoff = "off"
+    ///
     /// # Sort the commits during the output by "date" (default) or "name"
     /// sort_by = "date"
     ///
+    /// # The spell check of the summaries and bodies during the verification,
+    /// # either "warn" (default), "error" or "off", which requires the
+    /// # `spellcheck` feature
+    /// spellcheck = "warn"
+    ///
+    /// # A word list with one word per line used for the spell check
+    /// spellcheck_dictionary = "/usr/share/dict/words"
+    ///
+    /// # Additional words of the project for the spell check, e.g. "serde"
+    /// spellcheck_words = []
+    ///
+    /// # The enabled checks of the summary wording during the verification,
+    /// # out of "capitalized", "imperative", "no_period" and "no_wip"
+    /// style_checks = []
+    ///
+    /// # The sorting of the releases, either by commit "time" (default) or by
+    /// # "semver", where tags which are no semantic versions come last
+    /// tag_sorting = "time"
+    ///
     /// # Commit message template prefix which will be added during commit preparation.
+    /// # The variables `{branch}`, `{ticket-from-branch}` and `{author}` are
+    /// # expanded, e.g. "{ticket-from-branch}".
     /// template_prefix = "JIRA-1234"
+    ///
+    /// # The regular expression of the ticket ids within the branch names for
+    /// # the `{ticket-from-branch}` variable, where the first group is used if
+    /// # available
+    /// ticket_pattern = "[A-Z][A-Z0-9]+-[0-9]+"
+    ///
+    /// # The timezone of the release dates, either "utc" (default) or "local",
+    /// # where the JSON, YAML and TOML exports always contain the dates in UTC
+    /// timezone = "utc"
+    ///
+    /// # A shell command the entry texts are piped through during the output.
+    /// # A `{language}` placeholder is replaced by the language of the
+    /// # `translation_profiles`, where the main output stays untranslated.
+    /// # Can be removed from the configuration file as well.
+    /// translate_cmd = "my-translator --to {language}"
+    ///
+    /// # Deepen a shallow clone including its tags via `git fetch --unshallow`
+    /// # before parsing, otherwise only a warning is shown
+    /// unshallow = false
+    ///
+    /// # Show the output within `$GIT_PAGER`, the `pager`, `$PAGER` or `less`
+    /// # if it does not fit into the terminal
+    /// use_pager = true
+    ///
+    /// # The format of the `webhook_url` payload, either "slack" (default),
+    /// # "teams" or "discord"
+    /// webhook_format = "slack"
+    ///
+    /// # A chat webhook to announce releases via `--notify`, which requires
+    /// # the `network` feature. Can be removed from the configuration file as
+    /// # well.
+    /// webhook_url = "https://hooks.slack.com/services/T00/B00/XXX"
+    ///
+    /// # Wrap the paragraphs and list items exceeding the
+    /// # `max_body_line_length` before the verification via `verify --format`,
+    /// # instead of failing the verification
+    /// wrap_body_lines = false
+    ///
+    /// # The categories of the branch name patterns, e.g. `"fix/*" = "Fixed"`,
+    /// # which are the default category of the prepared commit messages. The
+    /// # commits of merged branches without a valid category are parsed as if
+    /// # they started with it.
+    /// [branch_categories]
+    ///
+    /// # Emojis printed in front of the categories, e.g. `Added = "✨"`, which
+    /// # will be parsed as well if `parse_gitmoji` is enabled
+    /// [category_emojis]
+    ///
+    /// # Regular expression replacements of the entry texts after parsing,
+    /// # e.g. `"[a-z]+\\.corp\\.example\\.com" = "<internal>"`
+    /// [entry_replacements]
+    ///
+    /// # The requirement of issue references like "#123" or "ABC-123" per
+    /// # category during the verification, either "error" or "warn", e.g.
+    /// # `Fixed = "error"`
+    /// [issue_requirements]
+    ///
+    /// # Regular expression replacements of the raw commit messages before
+    /// # parsing, e.g. `"(?m)^Reviewed-on: .*$" = ""`
+    /// [message_replacements]
+    ///
+    /// # Translations of the category and section names used for the output,
+    /// # e.g. `Added = "Hinzugefügt"`
+    /// [translations]
+    ///
+    /// # Additional translation profiles, e.g. `[translation_profiles.de]`,
+    /// # which will be used to write one translated output file per language
+    /// [translation_profiles]
     /// ```
     ///
     /// It also creates a symlinks for the commit message validation and
@@ -213,10 +955,31 @@ impl GitJournal {
         Ok(())
     }
 
+    /// Returns the directory of the git hooks, which is either the configured
+    /// `core.hooksPath` or the `hooks` directory of the common git directory,
+    /// shared between all linked worktrees.
+    fn hooks_dir(&self) -> Result<PathBuf, Error> {
+        let repo = Repository::open(&self.path)?;
+        let base = repo.workdir().unwrap_or_else(|| repo.path()).to_owned();
+        if let Ok(hooks_path) = repo.config()?.get_path("core.hooksPath") {
+            return Ok(base.join(hooks_path));
+        }
+
+        // Linked worktrees refer to the common directory via a file
+        let commondir = repo.path().join("commondir");
+        if repo.is_worktree() && commondir.is_file() {
+            let common = fs::read_to_string(&commondir)
+                .context(ErrorKind::File(commondir.display().to_string()))?;
+            return Ok(repo.path().join(common.trim()).join("hooks"));
+        }
+        Ok(repo.path().join("hooks"))
+    }
+
     fn install_git_hook(&self, name: &str, content: &str) -> Result<(), Error> {
-        let mut hook_path = PathBuf::from(&self.path);
-        hook_path.push(".git/hooks");
-        hook_path.push(name);
+        let hooks_dir = self.hooks_dir()?;
+        fs::create_dir_all(&hooks_dir)
+            .context(ErrorKind::File(hooks_dir.display().to_string()))?;
+        let hook_path = hooks_dir.join(name);
         let mut hook_file: File;
 
         if hook_path.exists() {
@@ -293,9 +1056,12 @@ impl GitJournal {
             }
 
             // Read the file contents to get the actual commit message string
-            let mut read_file = File::open(path)?;
+            let mut read_file =
+                File::open(path).context(ErrorKind::File(path.to_owned()))?;
             let mut commit_message = String::new();
-            read_file.read_to_string(&mut commit_message)?;
+            read_file
+                .read_to_string(&mut commit_message)
+                .context(ErrorKind::File(path.to_owned()))?;
 
             // Write the new generated content to the file
             let mut file = OpenOptions::new().write(true).open(path)?;
@@ -317,13 +1083,20 @@ impl GitJournal {
                 old_msg_vec
                     .insert(0, "# The provided commit message:".to_owned());
             }
-            let prefix = if self.config.template_prefix.is_empty() {
+            let prefix = self.expand_template_prefix()?;
+            let prefix = if prefix.is_empty() {
                 "".to_owned()
             } else {
-                self.config.template_prefix.clone() + " "
+                prefix + " "
             };
+            let category = self
+                .get_current_branch()
+                .and_then(|branch| {
+                    self.get_branch_category(&branch).map(str::to_owned)
+                })
+                .unwrap_or_else(|| self.config.categories[0].clone());
             let new_content = prefix
-                + &self.config.categories[0]
+                + &category
                 + " ...\n\n# Add a more detailed description if needed\n\n# - "
                 + &self.config.categories.join("\n# - ")
                 + "\n\n"
@@ -333,6 +1106,56 @@ impl GitJournal {
         Ok(())
     }
 
+    /// Returns the name of the checked out branch, if any.
+    fn get_current_branch(&self) -> Option<String> {
+        let repo = Repository::open(&self.path).ok()?;
+        let head = repo.head().ok()?;
+        if head.is_branch() {
+            head.shorthand().map(str::to_owned)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the category of the first matching `branch_categories`
+    /// pattern for the branch.
+    fn get_branch_category(&self, branch: &str) -> Option<&str> {
+        self.config
+            .branch_categories
+            .iter()
+            .find(|(pattern, _)| branches::matches(pattern, branch))
+            .map(|(_, category)| category.as_str())
+    }
+
+    /// Expands the variables of the `template_prefix`, where the current
+    /// branch is used for `{branch}` and the ticket id within it for
+    /// `{ticket-from-branch}`. Variables without a value expand to nothing.
+    ///
+    /// # Errors
+    /// When the `ticket_pattern` is no valid regular expression.
+    fn expand_template_prefix(&self) -> Result<String, Error> {
+        let prefix = &self.config.template_prefix;
+        if !prefix.contains('{') {
+            return Ok(prefix.clone());
+        }
+        let branch = self.get_current_branch().unwrap_or_default();
+        let ticket_pattern = Regex::new(&self.config.ticket_pattern)
+            .context(ErrorKind::Config("ticket_pattern".to_owned()))?;
+        let ticket = ticket_pattern
+            .captures(&branch)
+            .and_then(|cap| cap.get(1).or_else(|| cap.get(0)))
+            .map_or("", |ticket| ticket.as_str());
+        let author = self
+            .get_author_identity()
+            .map(|(name, _)| name)
+            .unwrap_or_default();
+        let expanded = prefix
+            .replace("{branch}", &branch)
+            .replace("{ticket-from-branch}", ticket)
+            .replace("{author}", &author);
+        Ok(expanded.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+
     /// Verify a given commit message against the parsing rules of
     /// [RFC0001](https://github.com/saschagrunert/git-journal/blob/master/rfc/0001-commit-msg.md)
     ///
@@ -352,27 +1175,95 @@ impl GitJournal {
     /// given file failed.
     pub fn verify(&self, path: &str) -> Result<(), Error> {
         // Open the file and read to string
-        let mut file = File::open(path)?;
+        let mut file =
+            File::open(path).context(ErrorKind::File(path.to_owned()))?;
         let mut commit_message = String::new();
-        file.read_to_string(&mut commit_message)?;
+        file.read_to_string(&mut commit_message)
+            .context(ErrorKind::File(path.to_owned()))?;
+
+        // Relax the verification on branches like personal ones
+        let branch = match self.get_relaxed_branch() {
+            Some(branch) => branch,
+            None => return self.verify_message(&commit_message),
+        };
+        match self.config.relaxed_verification.as_str() {
+            "off" => {
+                info!("Skipping the verification on branch '{}'.", branch)
+            }
+            "warn" => {
+                if let Err(e) = self.verify_message(&commit_message) {
+                    warn!(
+                        "Invalid commit message on branch '{}': {:#}",
+                        branch,
+                        Report::from(e)
+                    );
+                }
+            }
+            mode => {
+                return Err(ErrorKind::Config(
+                    "relaxed_verification".to_owned(),
+                )
+                .wrap(format_err!("Unknown relaxed verification '{}'", mode)))
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the current branch if it matches one of the
+    /// `relaxed_branches`, where a detached `HEAD` like during a rebase is
+    /// never relaxed.
+    fn get_relaxed_branch(&self) -> Option<String> {
+        if self.config.relaxed_branches.is_empty() {
+            return None;
+        }
+        let branch = self.get_current_branch()?;
+        self.config
+            .relaxed_branches
+            .iter()
+            .any(|pattern| branches::matches(pattern, &branch))
+            .then_some(branch)
+    }
+
+    /// Verifies a commit message like `verify`, but without reading it from
+    /// a file.
+    ///
+    /// # Errors
+    /// When the commit message is not valid due to RFC0001, the tags of the
+    /// default template or one of the registered rules.
+    pub fn verify_message(&self, commit_message: &str) -> Result<(), Error> {
+        let author = match self.config.signoff.as_str() {
+            "author" => self.get_author(),
+            _ => None,
+        };
+        self.verify_authored_message(commit_message, author)
+    }
+
+    /// Verifies a commit message like `verify_message`, where the sign-off is
+    /// checked against the given author like "Name <email>" of the commit.
+    fn verify_authored_message(
+        &self,
+        commit_message: &str,
+        author: Option<String>,
+    ) -> Result<(), Error> {
+        if rules::skip_fixup(commit_message, &self.config)? {
+            return Ok(());
+        }
+
+        let replacements = Self::compile_replacements(
+            &self.config.message_replacements,
+            "message_replacements",
+        )?;
+        let commit_message =
+            &self.transform_message(&replacements, commit_message);
 
         // Parse the commit and extract the tags
         let parsed_commit =
-            self.parser.parse_commit_message(&commit_message, None)?;
+            self.parser.parse_commit_message(commit_message, None)?;
         let tags = parsed_commit.get_tags_unique(vec![]);
 
         // Check if the tags within the commit also occur in the default
         // template and error if not.
-        if let Some(ref template) = self.config.default_template {
-            let mut path_buf = PathBuf::from(&self.path);
-            path_buf.push(template);
-            let mut file = File::open(path_buf)?;
-            let mut toml_string = String::new();
-            file.read_to_string(&mut toml_string)?;
-
-            // Deserialize the toml
-            let toml = toml::from_str(&toml_string)?;
-            let toml_tags = self.parser.get_tags_from_toml(&toml, vec![]);
+        if let Some(toml_tags) = self.get_default_template_tags()? {
             let invalid_tags = tags
                 .into_iter()
                 .filter(|tag| !toml_tags.contains(tag))
@@ -385,18 +1276,514 @@ impl GitJournal {
                 bail!("Not all tags exists in the default template.");
             }
         }
-        Ok(())
+
+        // Check the built-in and registered rules and report all violations
+        rules::check(
+            &parsed_commit,
+            commit_message,
+            &self.config,
+            author,
+            &self.rules,
+        )
     }
 
-    /// Parses a revision range for a `GitJournal`.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use gitjournal::GitJournal;
-    ///
+    /// Returns the author of a new commit like "Name <email>", which is
+    /// taken from the `GIT_AUTHOR_NAME` and `GIT_AUTHOR_EMAIL` environment
+    /// variables or the `user.name` and `user.email` of the git config.
+    fn get_author(&self) -> Option<String> {
+        self.get_author_identity()
+            .map(|(name, email)| format!("{} <{}>", name, email))
+    }
+
+    /// Returns the name and the email of the author of a new commit.
+    fn get_author_identity(&self) -> Option<(String, String)> {
+        if let (Ok(name), Ok(email)) =
+            (env::var("GIT_AUTHOR_NAME"), env::var("GIT_AUTHOR_EMAIL"))
+        {
+            return Some((name, email));
+        }
+        let repo = Repository::open(&self.path).ok()?;
+        let signature = repo.signature().ok()?;
+        Some((signature.name()?.to_owned(), signature.email()?.to_owned()))
+    }
+
+    /// Registers a custom rule, which will be checked by `verify` for every
+    /// valid commit message.
+    pub fn register_rule(&mut self, rule: Box<dyn Rule>) {
+        self.rules.push(rule);
+        self.generation += 1;
+    }
+
+    /// Adds a transformation of the raw commit messages, which runs after
+    /// the `message_replacements` before parsing and verifying them, e.g. to
+    /// strip boilerplate footers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.add_message_hook(|message| message.replace("feat:", "Added"));
+    /// assert!(journal.verify_message("feat: some feature").is_ok());
+    /// ```
+    pub fn add_message_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.message_hooks.push(Box::new(hook));
+        self.generation += 1;
+    }
+
+    /// Adds a transformation of the parsed commits, which runs after the
+    /// `entry_replacements`, e.g. to redact internal host names.
+    pub fn add_entry_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&mut ParsedCommit) + Send + Sync + 'static,
+    {
+        self.entry_hooks.push(Box::new(hook));
+        self.generation += 1;
+    }
+
+    /// Compiles the regular expressions of the replacements configured by
+    /// the key.
+    fn compile_replacements(
+        replacements: &BTreeMap<String, String>,
+        key: &str,
+    ) -> Result<Vec<(Regex, String)>, Error> {
+        let mut compiled = vec![];
+        for (pattern, replacement) in replacements {
+            let regex = Regex::new(pattern)
+                .context(ErrorKind::Config(key.to_owned()))?;
+            compiled.push((regex, replacement.clone()));
+        }
+        Ok(compiled)
+    }
+
+    /// Returns the `entry_replacements` followed by the `redact` patterns,
+    /// where the latter are applied after the entry hooks.
+    fn get_entry_replacements(&self) -> Result<Replacements, Error> {
+        let replacements = Self::compile_replacements(
+            &self.config.entry_replacements,
+            "entry_replacements",
+        )?;
+        let mut redactions = vec![];
+        for pattern in &self.config.redact {
+            let regex = Regex::new(pattern)
+                .context(ErrorKind::Config("redact".to_owned()))?;
+            redactions.push(regex);
+        }
+        Ok((replacements, redactions))
+    }
+
+    /// Replaces the sensitive content of the text by "[redacted]".
+    fn redact(redactions: &[Regex], text: &str) -> String {
+        redactions.iter().fold(text.to_owned(), |text, regex| {
+            regex.replace_all(&text, "[redacted]").into_owned()
+        })
+    }
+
+    /// Applies the message replacements and hooks to a raw commit message.
+    fn transform_message(
+        &self,
+        replacements: &[(Regex, String)],
+        message: &str,
+    ) -> String {
+        let mut message = message.to_owned();
+        for (regex, replacement) in replacements {
+            message = regex
+                .replace_all(&message, replacement.as_str())
+                .into_owned();
+        }
+        for hook in &self.message_hooks {
+            message = hook(&message);
+        }
+        message
+    }
+
+    /// Applies the entry replacements and hooks to a parsed commit and
+    /// redacts its texts and footers afterwards.
+    fn transform_entry(
+        &self,
+        (replacements, redactions): &Replacements,
+        commit: &mut ParsedCommit,
+    ) {
+        if !replacements.is_empty() {
+            commit.map_texts(|text| {
+                replacements.iter().fold(
+                    text.to_owned(),
+                    |text, (regex, replacement)| {
+                        regex
+                            .replace_all(&text, replacement.as_str())
+                            .into_owned()
+                    },
+                )
+            });
+        }
+        for hook in &self.entry_hooks {
+            hook(commit);
+        }
+        if !redactions.is_empty() {
+            commit.map_texts(|text| Self::redact(redactions, text));
+            for footer in &mut commit.footer {
+                footer.value = Self::redact(redactions, &footer.value);
+            }
+        }
+    }
+
+    /// Formats a commit message into its canonical form, where the categories
+    /// are wrapped in their delimiters, the whitespace is normalized and the
+    /// paragraphs are wrapped at the configured `message_width`. Messages
+    /// which are not valid are returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// let message = "Added   Some feature :ui:\n\n\n- Fixed a bug";
+    /// assert_eq!(
+    ///     journal.format_commit_message(message),
+    ///     "[Added] some feature :ui:\n\n- [Fixed] a bug"
+    /// );
+    /// ```
+    pub fn format_commit_message(&self, message: &str) -> String {
+        self.parser.format_commit_message(message)
+    }
+
+    /// Formats the commit message of the given file in place, e.g. within
+    /// the `commit-msg` hook.
+    ///
+    /// # Errors
+    /// When the file could not be read or written.
+    pub fn format(&self, path: &str) -> Result<(), Error> {
+        let file = || ErrorKind::File(path.to_owned());
+        let commit_message = fs::read_to_string(path).context(file())?;
+        let formatted = self.format_commit_message(&commit_message);
+        if formatted != commit_message.trim_end() {
+            fs::write(path, formatted + "\n").context(file())?;
+        }
+        Ok(())
+    }
+
+    /// Amends the message of the last commit with the category, summary and
+    /// optional body, e.g. to fix a just made commit from an editor plugin.
+    /// The prefix like "JIRA-1234" and the trailers like `Signed-off-by` of
+    /// the last commit are kept. The new message is verified like within the
+    /// `commit-msg` hook before and the id of the amended commit is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// journal
+    ///     .amend_last("Fixed", "the login", Some("- [Fixed] the logout"))
+    ///     .expect("Could not amend the last commit.");
+    /// ```
+    ///
+    /// # Errors
+    /// When the new commit message is not valid or the last commit could not
+    /// be amended, e.g. within an empty repository.
+    pub fn amend_last(
+        &self,
+        category: &str,
+        summary: &str,
+        body: Option<&str>,
+    ) -> Result<Oid, Error> {
+        let repo = Repository::open(&self.path)?;
+        let head = repo.head()?.peel_to_commit()?;
+        let (prefix, footer) =
+            parser::prefix_and_footer(head.message().unwrap_or_default());
+
+        let mut message = format!("{} {}\n", category, summary.trim());
+        if let Some(prefix) = prefix {
+            message = format!("{} {}", prefix, message);
+        }
+        let body = body.map(str::trim).filter(|b| !b.is_empty());
+        if let Some(body) = body {
+            message += &format!("\n{}\n", body);
+        }
+        if let Some(footer) =
+            footer.filter(|f| !body.unwrap_or_default().contains(f))
+        {
+            message += &format!("\n{}\n", footer);
+        }
+        self.verify_message(&message)?;
+
+        let oid =
+            head.amend(Some("HEAD"), None, None, None, Some(&message), None)?;
+        info!("Amended the last commit {:.7} to {:.7}.", head.id(), oid);
+        Ok(oid)
+    }
+
+    /// Returns the tags of the default template, if configured.
+    fn get_default_template_tags(&self) -> Result<Option<Vec<String>>, Error> {
+        let template = match self.config.default_template {
+            Some(ref template) => template,
+            None => return Ok(None),
+        };
+        let mut path_buf = PathBuf::from(&self.path);
+        path_buf.push(template);
+        let file = || ErrorKind::File(path_buf.display().to_string());
+        let mut toml_string = String::new();
+        File::open(&path_buf)
+            .context(file())?
+            .read_to_string(&mut toml_string)
+            .context(file())?;
+
+        // Deserialize the toml
+        let toml = toml::from_str(&toml_string).context(file())?;
+        Ok(Some(self.parser.get_tags_from_toml(&toml, vec![])))
+    }
+
+    /// Returns a reference of the commit message syntax (RFC0001), where the
+    /// configured categories, delimiters, prefix and the tags of the default
+    /// template are used. This can be printed, for example, if a commit
+    /// message verification failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// println!("{}", journal.grammar_help());
+    /// ```
+    pub fn grammar_help(&self) -> String {
+        let delimiters = &self.config.category_delimiters;
+        let categories = self
+            .config
+            .categories
+            .iter()
+            .map(|c| format!("{}{}{}", delimiters[0], c, delimiters[1]))
+            .collect::<Vec<_>>();
+        let category = categories.first().cloned().unwrap_or_default();
+        let prefix = match self.expand_template_prefix() {
+            Ok(ref prefix) if !prefix.is_empty() => prefix.clone(),
+            _ => "JIRA-1234".to_owned(),
+        };
+
+        let mut help = "Commit message syntax (RFC0001):\n\n".to_owned();
+        help += "  <prefix> <category> <summary> <:tags:>  | Summary line\n";
+        help += "                                          |\n";
+        help += "  <paragraph>                             | Body\n";
+        help += "  - <category> <list item> <:tags:>       |\n";
+        help += "                                          |\n";
+        help += "  <Key>: <Value>                          | Footer\n\n";
+        help += &format!(
+            "Prefix (optional): An issue reference like '{}'.\n",
+            prefix
+        );
+        help += &format!(
+            "Categories: {} (mandatory for the summary line).\n",
+            categories.join(", ")
+        );
+        match self.get_default_template_tags() {
+            Ok(Some(ref tags)) if tags.len() > 1 => {
+                let tags = tags
+                    .iter()
+                    .filter(|tag| *tag != parser::TOML_DEFAULT_KEY)
+                    .map(|tag| format!(":{}:", tag))
+                    .collect::<Vec<_>>();
+                help += &format!("Tags (optional): {}.\n", tags.join(", "));
+            }
+            _ => {
+                help += "Tags (optional): Wrapped in colons and separated by \
+                         comma, like ':internal:' or ':A, B:'.\n"
+            }
+        }
+        help +=
+            "Footer (optional): Key value pairs like 'Reviewed-by: Me'.\n\n";
+        help += "Example:\n\n";
+        help += &format!("  {} {} the fancy thing :doc:\n\n", prefix, category);
+        help += "  A more detailed description of the change.\n\n";
+        help += &format!("  - {} some details\n\n", category);
+        help += "  Reviewed-by: John Doe\n";
+        help
+    }
+
+    /// Generates a todo list for `git rebase -i` from a commit range like
+    /// `origin/master..HEAD`. All commits with an invalid commit message will
+    /// be reworded, where a suggested summary is added as comment below the
+    /// action. Merge commits will be skipped like within a usual rebase.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// let todo = journal.generate_rebase_todo("HEAD~2..HEAD");
+    /// ```
+    ///
+    /// # Errors
+    /// When the commit range is invalid or the commits could not be read.
+    pub fn generate_rebase_todo(
+        &self,
+        revision_range: &str,
+    ) -> Result<String, Error> {
+        let repo = Repository::open(&self.path)?;
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE);
+        revwalk.push_range(revision_range)?;
+
+        let mut todo = String::new();
+        for id in revwalk {
+            let commit = repo.find_commit(id?)?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            let message = commit.message().ok_or_else(|| {
+                git2::Error::from_str("Commit message error.")
+            })?;
+            let summary = commit.summary().unwrap_or("");
+            let short_id = commit.as_object().short_id()?;
+            let short_id = short_id.as_str().unwrap_or("");
+            if self.parser.parse_commit_message(message, None).is_ok() {
+                todo += &format!("pick {} {}\n", short_id, summary);
+            } else {
+                todo += &format!("reword {} {}\n", short_id, summary);
+                todo += &format!(
+                    "# Suggested: {}\n",
+                    self.suggest_summary(summary)
+                );
+            }
+        }
+        Ok(todo)
+    }
+
+    /// Verifies the commit messages of a commit range like
+    /// `origin/master..HEAD` like `verify_message`, e.g. within a CI
+    /// pipeline. Returns the invalid commits together with their first changed
+    /// file, where merge commits are skipped. The sign-off of every commit is
+    /// checked against its own author.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// for warning in journal.verify_range("HEAD~2..HEAD").unwrap() {
+    ///     println!("{}", warning);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// When the commit range is invalid or the commits could not be read.
+    pub fn verify_range(
+        &self,
+        revision_range: &str,
+    ) -> Result<Warnings, Error> {
+        let repo = Repository::open(&self.path)?;
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE);
+        revwalk.push_range(revision_range)?;
+
+        let mut warnings = Warnings::default();
+        for id in revwalk {
+            let oid = id?;
+            let commit = repo.find_commit(oid)?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            let message = commit.message().ok_or_else(|| {
+                ErrorKind::Commit(oid.to_string())
+                    .wrap(format_err!("Commit message error."))
+            })?;
+            let author = commit.author();
+            let author = match (author.name(), author.email()) {
+                (Some(name), Some(email))
+                    if self.config.signoff == "author" =>
+                {
+                    Some(format!("{} <{}>", name, email))
+                }
+                _ => None,
+            };
+            if let Err(e) = self.verify_authored_message(message, author) {
+                warnings.push(Warning::InvalidCommit {
+                    oid,
+                    reason: format!("{:#}", Report::from(e)),
+                    path: changes::first_changed_path(&repo, &commit)?,
+                });
+            }
+        }
+        Ok(warnings)
+    }
+
+    /// Suggests a valid commit message summary for an invalid one, where
+    /// known keywords and conventional commit types like `fix(scope):` are
+    /// mapped to the configured categories.
+    fn suggest_summary(&self, summary: &str) -> String {
+        let summary = summary.trim();
+        let (keyword, text) = match summary.find(' ') {
+            Some(index) => (&summary[..index], summary[index..].trim_start()),
+            None => (summary, ""),
+        };
+
+        // Remove the scope and the colon of conventional commits
+        let keyword = keyword.trim_end_matches(':').trim_end_matches('!');
+        let keyword = match keyword.find('(') {
+            Some(index) => &keyword[..index],
+            None => keyword,
+        };
+
+        // Search for a matching category
+        let categories = &self.config.categories;
+        let lowercase = keyword.to_lowercase();
+        let category = match categories
+            .iter()
+            .find(|category| category.to_lowercase() == lowercase)
+        {
+            Some(category) => Some(category.as_str()),
+            None => match lowercase.as_str() {
+                "add" | "adds" | "feat" | "feature" | "new" => Some("Added"),
+                "change" | "changes" | "update" | "updated" | "chore" => {
+                    Some("Changed")
+                }
+                "fix" | "fixes" | "bugfix" | "hotfix" => Some("Fixed"),
+                "improve" | "improves" | "perf" | "refactor" => {
+                    Some("Improved")
+                }
+                "remove" | "removes" | "delete" | "drop" => Some("Removed"),
+                _ => None,
+            }
+            .filter(|category| categories.iter().any(|c| c == category)),
+        };
+
+        match category {
+            Some(category) if !text.is_empty() => {
+                format!("{} {}", category, text)
+            }
+            _ => {
+                let category = if categories.iter().any(|c| c == "Changed") {
+                    "Changed"
+                } else {
+                    categories.first().map_or("Changed", |c| c.as_str())
+                };
+                format!("{} {}", category, summary)
+            }
+        }
+    }
+
+    /// Parses a revision range for a `GitJournal`. Returns the recoverable
+    /// issues like skipped commits, which did not abort the parsing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
     /// let mut journal = GitJournal::new(".").unwrap();
-    /// journal.parse_log("HEAD", "rc", &1, &false, &false, None);
+    /// let options = ParseOptions::new("HEAD").max_tags_count(2).all(false);
+    /// if let Ok(warnings) = journal.parse_log(&options) {
+    ///     for warning in &warnings {
+    ///         println!("{}", warning);
+    ///     }
+    /// }
     /// ```
     ///
     /// # Errors
@@ -404,19 +1791,36 @@ impl GitJournal {
     /// range is invalid.
     pub fn parse_log(
         &mut self,
-        revision_range: &str,
-        tag_skip_pattern: &str,
-        max_tags_count: &u32,
-        all: &bool,
-        skip_unreleased: &bool,
-        ignore_tags: Option<Vec<&str>>,
-    ) -> Result<(), Error> {
+        options: &ParseOptions,
+    ) -> Result<Warnings, Error> {
+        // Use the current configuration for parsing
+        self.parser.config = self.config.clone();
+
+        let exclude_patterns = self
+            .config
+            .exclude_patterns
+            .iter()
+            .map(|pattern| Regex::new(pattern))
+            .collect::<Result<Vec<_>, _>>()
+            .context(ErrorKind::Config("exclude_patterns".to_owned()))?;
+        let message_replacements = Self::compile_replacements(
+            &self.config.message_replacements,
+            "message_replacements",
+        )?;
+        let entry_replacements = self.get_entry_replacements()?;
+
         let repo = Repository::open(&self.path)?;
         let mut revwalk = repo.revwalk()?;
-        revwalk.set_sorting(git2::Sort::TIME);
+        let (sorting, reverse) = self.get_revwalk_sorting()?;
+        revwalk.set_sorting(sorting);
+        if self.config.first_parent {
+            revwalk.simplify_first_parent();
+        }
 
-        // Fill the revwalk with the selected revisions.
-        let revspec = repo.revparse(revision_range)?;
+        // Fill the revwalk with the selected revisions. Commits hidden from the
+        // walk do not need to be assigned to any release.
+        let mut hidden = vec![];
+        let revspec = repo.revparse(&options.revision_range)?;
         if revspec.mode().contains(git2::RevparseMode::SINGLE) {
             // A single commit was given
             let from = revspec.from().ok_or_else(|| {
@@ -438,77 +1842,226 @@ impl GitJournal {
                 revwalk.push(o.id())?;
             }
             revwalk.hide(from.id())?;
+            hidden.push(from.id());
         }
 
-        // Iterate over the git objects and collect them in a vector of tuples
-        let mut num_parsed_tags: u32 = 1;
-        let unreleased_str = "Unreleased";
-        let mut current_tag = ParsedTag {
-            name: unreleased_str.to_owned(),
-            date: Utc::today(),
-            commits: vec![],
-            message_ids: vec![],
-        };
-        let mut worker_vec = vec![];
-        'revloop: for (index, id) in revwalk.enumerate() {
+        // Assign every commit to the oldest release containing it
+        let releases = self.get_releases(&repo, &options.tag_skip_pattern)?;
+        let release_map =
+            Self::get_release_map(&repo, &releases, &hidden, options)?;
+
+        // Collect the walked commits together with their release index, where
+        // `None` represents the unreleased commits
+        let mut commits = vec![];
+        let mut start_is_tagged = false;
+        for (index, id) in revwalk.enumerate() {
+            options.check_cancelled()?;
             let oid = id?;
-            let commit = repo.find_commit(oid)?;
-            for tag in self.tags.iter().filter(|tag| {
-                tag.0.as_bytes() == oid.as_bytes()
-                    && !tag.1.contains(tag_skip_pattern)
-            }) {
-                // Parsing entries of the last tag done
-                if !current_tag.message_ids.is_empty() {
-                    self.parser.result.push(current_tag.clone());
+            if index == 0 {
+                start_is_tagged = releases.iter().any(|r| r.0 == oid);
+            }
+            let release = release_map.get(&oid).cloned();
+            if options.skip_unreleased && release.is_none() {
+                continue;
+            }
+            commits.push((oid, release));
+        }
+
+        // Report cherry-picked changes only once by their oldest commit
+        let mut backported = HashSet::new();
+        if self.config.deduplicate_cherry_picks {
+            let oids = commits.iter().map(|c| c.0).collect::<Vec<_>>();
+            let cherry_picks = branches::find_cherry_picks(&repo, &oids)?;
+            backported.extend(cherry_picks.values().cloned());
+            commits.retain(|c| !cherry_picks.contains_key(&c.0));
+        }
+
+        // Infer the categories of the commits of merged branches, which are
+        // used if the commit messages are not valid
+        let mut branch_categories = HashMap::new();
+        if !self.config.branch_categories.is_empty() {
+            let oids = commits.iter().map(|c| c.0).collect::<Vec<_>>();
+            for (oid, branch) in branches::find_merged_branches(&repo, &oids)? {
+                if let Some(category) = self.get_branch_category(&branch) {
+                    branch_categories.insert(oid, category.to_owned());
                 }
+            }
+        }
+
+        // Assign the GitLab merge requests to the commits of merged branches
+        let merge_requests = match self.config.merge_requests.as_str() {
+            "off" => HashMap::new(),
+            "link" | "fetch" => {
+                let oids = commits.iter().map(|c| c.0).collect::<Vec<_>>();
+                branches::find_merge_requests(&repo, &oids)?
+            }
+            mode => {
+                return Err(ErrorKind::Config("merge_requests".to_owned())
+                    .wrap(format_err!(
+                        "Unknown merge requests mode '{}'",
+                        mode
+                    )))
+            }
+        };
+
+        // Keep only the newest releases if not all are wanted, where a tag at
+        // the start of the walk is always part of the result
+        let mut found_releases =
+            commits.iter().filter_map(|c| c.1).collect::<Vec<usize>>();
+        found_releases.sort();
+        found_releases.dedup();
+        let min_version = match self.config.min_version {
+            Some(ref version) => {
+                Some(Self::get_version(version).ok_or_else(|| {
+                    ErrorKind::Config("min_version".to_owned()).wrap(
+                        format_err!("Invalid minimum version '{}'", version),
+                    )
+                })?)
+            }
+            None => None,
+        };
+        found_releases.retain(|index| {
+            self.is_release_selected(&releases[*index], &min_version)
+        });
+        if !options.all {
+            let mut max = options.max_tags_count as usize;
+            if start_is_tagged && max == 0 {
+                max = 1;
+            }
+            found_releases.truncate(max);
+        }
 
-                // If a single revision is given stop at the first seen tag
-                if !all && index > 0 && num_parsed_tags > *max_tags_count {
-                    break 'revloop;
+        // Create the releases, starting with the unreleased commits
+        let mut sections = HashMap::new();
+        let mut parsed_tags = vec![];
+        if commits.iter().any(|c| c.1.is_none()) {
+            sections.insert(None, parsed_tags.len());
+            parsed_tags.push(ParsedTag {
+                name: self.get_unreleased_name(),
+                title: None,
+                date: self.get_current_date(),
+                oid: None,
+                commit_ids: vec![],
+                commits: vec![],
+                message_ids: vec![],
+            });
+        }
+        for index in found_releases {
+            let (oid, ref names, date) = releases[index];
+            let title = |names: &[String]| {
+                names
+                    .iter()
+                    .find_map(|name| self.tag_titles.get(name).cloned())
+            };
+            let names = match self.config.multiple_tags.as_str() {
+                "merged" => {
+                    vec![(names.join(MERGED_TAGS_SEPARATOR), title(names))]
                 }
+                "separate" => names
+                    .iter()
+                    .map(|name| {
+                        (name.clone(), self.tag_titles.get(name).cloned())
+                    })
+                    .collect(),
+                _ => {
+                    return Err(ErrorKind::Config("multiple_tags".to_owned())
+                        .wrap(format_err!(
+                            "Unknown multiple tags mode '{}'",
+                            self.config.multiple_tags
+                        )))
+                }
+            };
 
-                // Format the tag and set as current
-                num_parsed_tags += 1;
-                let date = Utc.timestamp(commit.time().seconds(), 0).date();
-                current_tag = ParsedTag {
-                    name: tag.1.clone(),
+            // The oldest tag contains the commits, whereas the newer ones will
+            // be empty sections above
+            for (name, title) in names.into_iter().rev() {
+                parsed_tags.push(ParsedTag {
+                    name,
+                    title,
                     date,
+                    oid: Some(oid),
+                    commit_ids: vec![],
                     commits: vec![],
                     message_ids: vec![],
-                };
+                });
             }
+            sections.insert(Some(index), parsed_tags.len() - 1);
+        }
 
-            // Do not parse if we want to skip commits which do not belong to
-            // any release
-            if *skip_unreleased && current_tag.name == unreleased_str {
-                continue;
-            }
+        let mut warnings = Warnings::default();
+        for warning in &self.repo_warnings {
+            warnings.push(warning.clone());
+        }
+        let mut worker_vec = vec![];
+        let mut authors = vec![];
+        let mut walked_sections = HashSet::new();
+        let offset = self.parser.result.len();
+        for (oid, release) in commits {
+            options.check_cancelled()?;
+            let parsed_tag = match sections.get(&release) {
+                Some(section) => {
+                    walked_sections.insert(offset + *section);
+                    &mut parsed_tags[*section]
+                }
+                None => continue,
+            };
 
             // Add the commit message to the parser work to be done, the `id`
             // represents the index within the worker vector
+            let commit = repo
+                .find_commit(oid)
+                .context(ErrorKind::Commit(oid.to_string()))?;
             let message = commit.message().ok_or_else(|| {
-                git2::Error::from_str("Commit message error.")
+                ErrorKind::Commit(oid.to_string())
+                    .wrap(format_err!("Commit message error."))
             })?;
+            for parent in commit.parents() {
+                if parent.time().seconds() > commit.time().seconds() {
+                    warnings.push(Warning::ClockSkew {
+                        oid,
+                        parent: parent.id(),
+                    });
+                }
+            }
+            let message =
+                self.transform_message(&message_replacements, message);
+            let summary = message.lines().next().unwrap_or_default();
+            if exclude_patterns.iter().any(|re| re.is_match(summary)) {
+                continue;
+            }
             let id = worker_vec.len();
 
-            // The worker_vec contains the commit message and the parsed commit
-            // (currently none)
-            worker_vec.push((message.to_owned(), oid, None));
-            current_tag.message_ids.push(id);
-        }
-
-        // Add the last element as well if needed
-        if !current_tag.message_ids.is_empty()
-            && !self.parser.result.contains(&current_tag)
-        {
-            self.parser.result.push(current_tag);
+            // The worker_vec contains the commit message, the parsed commit
+            // (currently none) and the reason if it could not be parsed
+            worker_vec.push((message, oid, None, None));
+            authors.push(commit.author().name().map(|name| name.to_owned()));
+            parsed_tag.commit_ids.push(oid);
+            parsed_tag.message_ids.push(id);
         }
+        self.parser.result.extend(parsed_tags);
 
         // Process with the full CPU power
         worker_vec.par_iter_mut().for_each(
-            |&mut (ref message, ref oid, ref mut result)| {
-                match self.parser.parse_commit_message(message, Some(*oid)) {
-                    Ok(parsed_message) => match ignore_tags {
+            |&mut (ref message, ref oid, ref mut result, ref mut reason)| {
+                if options.check_cancelled().is_err() {
+                    return;
+                }
+                let parsed = self
+                    .parser
+                    .parse_commit_message(message, Some(*oid))
+                    .or_else(|e| match branch_categories.get(oid) {
+                        Some(category) => self
+                            .parser
+                            .parse_commit_message(
+                                &format!("{} {}", category, message),
+                                Some(*oid),
+                            )
+                            .map_err(|_| e),
+                        None => Err(e),
+                    });
+                match parsed {
+                    Ok(parsed_message) if parsed_message.is_excluded() => {}
+                    Ok(parsed_message) => match options.ignore_tags {
                         Some(ref tags) => {
                             for tag in tags {
                                 // Filter out ignored tags
@@ -519,513 +2072,5135 @@ impl GitJournal {
                         }
                         _ => *result = Some(parsed_message),
                     },
-                    Err(e) => warn!("Skipping commit: {}", e),
+                    Err(e) => *reason = Some(e.to_string()),
                 }
             },
         );
 
+        options.check_cancelled()?;
+        for (_, oid, _, reason) in &worker_vec {
+            if let Some(reason) = reason {
+                warnings.push(Warning::InvalidCommit {
+                    oid: *oid,
+                    reason: reason.clone(),
+                    path: None,
+                });
+            }
+        }
+
         // Assemble results together via the message_id
+        let mut empty_releases = vec![];
         self.parser.result = self
             .parser
             .result
             .clone()
             .into_iter()
-            .filter_map(|mut parsed_tag| {
+            .enumerate()
+            .filter_map(|(index, mut parsed_tag)| {
                 for id in &parsed_tag.message_ids {
-                    if let Some(parsed_commit) = worker_vec[*id].2.clone() {
+                    if let Some(mut parsed_commit) = worker_vec[*id].2.clone() {
+                        parsed_commit.author = authors[*id].clone();
+                        parsed_commit.summary.backported =
+                            backported.contains(&worker_vec[*id].1);
+                        if parsed_commit.summary.merge_request.is_none() {
+                            parsed_commit.summary.merge_request =
+                                merge_requests.get(&worker_vec[*id].1).cloned();
+                        }
+                        self.transform_entry(
+                            &entry_replacements,
+                            &mut parsed_commit,
+                        );
                         parsed_tag.commits.push(parsed_commit);
                     }
                 }
-                if parsed_tag.commits.is_empty() {
+                parsed_tag.title = parsed_tag
+                    .title
+                    .map(|title| Self::redact(&entry_replacements.1, &title));
+                // Report the releases whose commits are all invalid or
+                // skipped, and keep the intentionally empty sections of
+                // multiple tags
+                if parsed_tag.commits.is_empty()
+                    && parsed_tag.oid.is_some()
+                    && walked_sections.contains(&index)
+                {
+                    empty_releases.push(parsed_tag.name.clone());
+                }
+                if parsed_tag.commits.is_empty()
+                    && !(parsed_tag.message_ids.is_empty()
+                        && parsed_tag.oid.is_some())
+                {
                     None
                 } else {
-                    if self.config.sort_by == "name" {
-                        parsed_tag.commits.sort_by(|l, r| {
-                            l.summary.category.cmp(&r.summary.category)
-                        });
+                    if reverse {
+                        parsed_tag.commits.reverse();
+                        parsed_tag.commit_ids.reverse();
                     }
+                    Self::sort_commits(&self.config, &mut parsed_tag.commits);
                     Some(parsed_tag)
                 }
             })
             .collect::<Vec<ParsedTag>>();
+        if reverse {
+            self.parser.result.reverse();
+        }
+        match self.config.empty_releases.as_str() {
+            "off" => {}
+            "warn" => {
+                for name in empty_releases {
+                    warnings.push(Warning::EmptyRelease(name));
+                }
+            }
+            "error" => {
+                if !empty_releases.is_empty() {
+                    bail!(
+                        "Releases without any valid commit: {}",
+                        empty_releases.join(", ")
+                    );
+                }
+            }
+            mode => {
+                return Err(ErrorKind::Config("empty_releases".to_owned())
+                    .wrap(format_err!(
+                        "Unknown empty releases mode '{}'",
+                        mode
+                    )))
+            }
+        }
 
-        info!(
-            "Parsing done. Processed {} commit messages.",
-            worker_vec.len()
-        );
-        Ok(())
-    }
+        // Add the manual entries to the unreleased commits
+        if !options.skip_unreleased {
+            let mut entries = self.get_manual_entries_from_file()?;
+            entries.extend(self.get_fragments()?.into_iter().map(|f| f.1));
+            entries.extend(self.manual_entries.clone());
+            self.merge_manual_entries(entries)?;
+        }
 
-    /// Generates an output template from the current parsing results.
-    ///
-    /// # Examples
-    ///
+        // Add the issues referenced by the commit prefixes and the merge
+        // requests
+        #[cfg(feature = "network")]
+        let client = http::Client::new(&self.config, &self.path);
+        #[cfg(feature = "network")]
+        jira::enrich(&mut self.parser.result, &self.config, &client);
+        #[cfg(feature = "network")]
+        gitlab::enrich(&mut self.parser.result, &self.config, &client);
+        #[cfg(not(feature = "network"))]
+        {
+            if self.config.jira_url.is_some() {
+                warn!(
+                    "Ignoring 'jira_url', the 'network' feature is disabled."
+                );
+            }
+            if self.config.merge_requests == "fetch" {
+                warn!(
+                    "Not fetching the merge requests, the 'network' feature \
+                     is disabled."
+                );
+            }
+        }
+
+        // Validate the referenced work items of Azure Boards
+        #[cfg(feature = "network")]
+        {
+            if let Some(ref url) = self.config.azure_boards_url {
+                for reference in
+                    azure::validate(&self.parser.result, url, &client)
+                {
+                    warnings.push(Warning::UnknownWorkItem(reference));
+                }
+            }
+        }
+
+        info!(
+            "Parsing done. Processed {} commit messages.",
+            worker_vec.len()
+        );
+        Ok(warnings)
+    }
+
+    /// Returns the names of the local and remote branches matching the
+    /// pattern, where `*` matches any characters, e.g. "release/*" or
+    /// "origin/release/*".
+    ///
+    /// # Examples
+    ///
     /// ```
     /// use gitjournal::GitJournal;
     ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// let branches = journal.branch_names("release/*").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// When the branches could not be read from the repository.
+    pub fn branch_names(&self, pattern: &str) -> Result<Vec<String>, Error> {
+        let repo = Repository::open(&self.path)?;
+        let mut names = vec![];
+        for branch in repo.branches(None)? {
+            if let Some(name) = branch?.0.name()? {
+                if branches::matches(pattern, name) {
+                    names.push(name.to_owned());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// Parses the log of every branch matching the pattern separately, for
+    /// example to generate a changelog per maintenance branch like
+    /// `release/1.x` and `release/2.x`. The revision range of the options is
+    /// replaced by the branch name. Commits which are cherry-picked between
+    /// the branches are detected via their patch id and contain the other
+    /// branches within `cherry_picks`. The current parsing results will be
+    /// replaced by the ones of the last branch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// let options = ParseOptions::default().all(true);
+    /// for (branch, parsed) in journal.parse_branches("release/*", &options).unwrap() {
+    ///     println!("{}:\n{}", branch, parsed.render(false, None).unwrap());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// When the branches could not be read or parsing one of them failed.
+    pub fn parse_branches(
+        &mut self,
+        pattern: &str,
+        options: &ParseOptions,
+    ) -> Result<Vec<(String, ParsedJournal)>, Error> {
+        let mut results = vec![];
+        for branch in self.branch_names(pattern)? {
+            self.parser.result.clear();
+            for warning in
+                self.parse_log(&options.clone().revision_range(&branch))?
+            {
+                warn!("{}: {}", branch, warning);
+            }
+            results.push((branch, self.parser.result.clone()));
+        }
+        let repo = Repository::open(&self.path)?;
+        branches::mark_cherry_picks(&repo, &mut results)?;
+
+        let template = self.get_template(None);
+        Ok(results
+            .into_iter()
+            .map(|(branch, result)| {
+                let parser = Parser {
+                    config: self.config.clone(),
+                    result,
+                };
+                (branch, ParsedJournal::new(parser, template.clone()))
+            })
+            .collect())
+    }
+
+    /// Parses the log between two tags, which will be resolved by their
+    /// names. Annotated as well as lightweight tags are supported and a
+    /// trailing `^{}` will be peeled as well. Parsing goes up to `HEAD` if no
+    /// `to_tag` is given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
     /// let mut journal = GitJournal::new(".").unwrap();
-    /// journal.parse_log("HEAD", "rc", &1, &false, &false, None);
+    /// let options = ParseOptions::default();
     /// journal
-    ///     .generate_template()
-    ///     .expect("Template generation failed.");
+    ///     .parse_log_between_tags("v1.0.0", Some("v2.0.0"), &options)
+    ///     .ok();
     /// ```
     ///
     /// # Errors
-    /// If the generation of the template was impossible.
-    pub fn generate_template(&self) -> Result<(), Error> {
-        let mut tags = vec![parser::TOML_DEFAULT_KEY.to_owned()];
+    /// When one of the tags could not be found or the parsing failed.
+    pub fn parse_log_between_tags(
+        &mut self,
+        from_tag: &str,
+        to_tag: Option<&str>,
+        options: &ParseOptions,
+    ) -> Result<Warnings, Error> {
+        let from = self.resolve_tag(from_tag)?;
+        let to = match to_tag {
+            Some(to_tag) => {
+                let to = self.resolve_tag(to_tag)?;
 
-        // Get all the tags
-        for parsed_tag in &self.parser.result {
-            tags = parsed_tag.get_tags_unique(tags);
+                // Lightweight tags are not part of the known tags, but the
+                // end of the range should be named after the given tag
+                let name = to_tag.trim_end_matches("^{}");
+                if !self.tags.iter().any(|tag| tag.0 == to && tag.1 == name) {
+                    self.tags.push((to, name.to_owned()));
+                }
+                to.to_string()
+            }
+            None => "HEAD".to_owned(),
+        };
+        self.parse_log(
+            &options
+                .clone()
+                .revision_range(&format!("{}..{}", from, to))
+                .all(true),
+        )
+    }
+
+    /// Returns the names of all annotated and lightweight tags of the
+    /// repository.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// let tags = journal.tag_names().expect("Could not read tags.");
+    /// ```
+    ///
+    /// # Errors
+    /// When the tags of the repository could not be read.
+    pub fn tag_names(&self) -> Result<Vec<String>, Error> {
+        let repo = Repository::open(&self.path)?;
+        let tag_names = repo.tag_names(None)?;
+        Ok(tag_names.iter().flatten().map(|n| n.to_owned()).collect())
+    }
+
+    /// Resolves a tag by its name to the commit the tag points to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// journal.resolve_tag("v1.0.0^{}").ok();
+    /// ```
+    ///
+    /// # Errors
+    /// When the tag does not exist or does not point to a commit.
+    pub fn resolve_tag(&self, name: &str) -> Result<Oid, Error> {
+        let repo = Repository::open(&self.path)?;
+        let name = name.trim_end_matches("^{}");
+        let reference =
+            match repo.find_reference(&format!("refs/tags/{}", name)) {
+                Ok(reference) => reference,
+                Err(_) => bail!("Tag '{}' not found.", name),
+            };
+        let commit = reference.peel_to_commit()?;
+        Ok(commit.id())
+    }
+
+    /// Adds a manual entry, which does not correspond to any commit, to the
+    /// unreleased section. The entry has to be a valid commit message and
+    /// will be part of the current and all following parsing results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal
+    ///     .add_entry("Added the documentation site")
+    ///     .expect("Could not add entry.");
+    /// ```
+    ///
+    /// # Errors
+    /// When the entry is not a valid commit message.
+    pub fn add_entry(&mut self, message: &str) -> Result<(), Error> {
+        let entry = self.parser.parse_commit_message(message, None)?;
+        self.manual_entries.push(entry.clone());
+        if !self.parser.result.is_empty() {
+            self.merge_manual_entries(vec![entry])?;
+        }
+        Ok(())
+    }
+
+    /// Reads the manual entries from the configured file, which contains an
+    /// `entries` array of commit messages.
+    fn get_manual_entries_from_file(&self) -> Result<Vec<ParsedCommit>, Error> {
+        let file_name = match self.config.manual_entries_file {
+            Some(ref file_name) => file_name,
+            None => return Ok(vec![]),
+        };
+        let mut path_buf = PathBuf::from(&self.path);
+        path_buf.push(file_name);
+        if !path_buf.exists() {
+            return Ok(vec![]);
         }
+        let file = || ErrorKind::File(path_buf.display().to_string());
+        let mut toml_string = String::new();
+        File::open(&path_buf)
+            .context(file())?
+            .read_to_string(&mut toml_string)
+            .context(file())?;
+        let toml: Value = toml::from_str(&toml_string).context(file())?;
 
-        if tags.len() > 1 {
-            info!("Found tags: '{}'.", tags[1..].join(", "));
-        } else {
-            warn!("No tags found.");
+        let mut entries = vec![];
+        if let Some(Value::Array(array)) = toml.get("entries") {
+            for message in array.iter().filter_map(|value| value.as_str()) {
+                entries.push(self.parser.parse_commit_message(message, None)?);
+            }
         }
+        Ok(entries)
+    }
 
-        // Create the toml representation
-        let mut toml_map = Map::new();
-        let toml_tags = tags
+    /// Reads the changelog fragments of the `fragments_dir` in the order of
+    /// their file names. A file like `123.fixed.md` contains the text of an
+    /// entry of the category within its name, otherwise it has to contain a
+    /// valid commit message.
+    fn get_fragments(&self) -> Result<Vec<(PathBuf, ParsedCommit)>, Error> {
+        let dir = match self.config.fragments_dir {
+            Some(ref dir) => Path::new(&self.path).join(dir),
+            None => return Ok(vec![]),
+        };
+        if !dir.is_dir() {
+            return Ok(vec![]);
+        }
+        let mut paths = fs::read_dir(&dir)
+            .context(ErrorKind::File(dir.display().to_string()))?
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| {
+                path.is_file()
+                    && !path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .is_none_or(|name| name.starts_with('.'))
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        let mut fragments = vec![];
+        for path in paths {
+            let file = || ErrorKind::File(path.display().to_string());
+            let content = fs::read_to_string(&path).context(file())?;
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            let category = name.split('.').skip(1).find_map(|part| {
+                self.config
+                    .categories
+                    .iter()
+                    .find(|category| category.eq_ignore_ascii_case(part))
+            });
+            let message = match category {
+                Some(category) => format!("{} {}", category, content.trim()),
+                None => content,
+            };
+            let entry = self
+                .parser
+                .parse_commit_message(&message, None)
+                .context(file())?;
+            fragments.push((path, entry));
+        }
+        Ok(fragments)
+    }
+
+    /// Adds the given entries in front of the unreleased commits, where the
+    /// unreleased section will be created if necessary.
+    fn merge_manual_entries(
+        &mut self,
+        mut entries: Vec<ParsedCommit>,
+    ) -> Result<(), Error> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let replacements = self.get_entry_replacements()?;
+        for entry in &mut entries {
+            self.transform_entry(&replacements, entry);
+        }
+        let name = self.get_unreleased_name();
+        let date = self.get_current_date();
+        let result = &mut self.parser.result;
+        let index = match result.iter().position(|tag| tag.oid.is_none()) {
+            Some(index) => index,
+            None => {
+                let unreleased = ParsedTag {
+                    name,
+                    title: None,
+                    date,
+                    oid: None,
+                    commit_ids: vec![],
+                    commits: vec![],
+                    message_ids: vec![],
+                };
+                if self.config.revwalk_sorting.iter().any(|s| s == "reverse") {
+                    result.push(unreleased);
+                    result.len() - 1
+                } else {
+                    result.insert(0, unreleased);
+                    0
+                }
+            }
+        };
+        let commits = &mut result[index].commits;
+        commits.splice(0..0, entries);
+        Self::sort_commits(&self.config, commits);
+        Ok(())
+    }
+
+    /// Sorts the commits by the position of their category within the
+    /// `category_order`, where unlisted categories come last. Commits of the
+    /// same priority are sorted by their category name if `sort_by` is
+    /// "name", otherwise they keep their order.
+    fn sort_commits(config: &Config, commits: &mut [ParsedCommit]) {
+        let by_name = config.sort_by == "name";
+        if config.category_order.is_empty() && !by_name {
+            return;
+        }
+        let rank = |commit: &ParsedCommit| {
+            config
+                .category_order
+                .iter()
+                .position(|c| c == &commit.summary.category)
+                .unwrap_or(config.category_order.len())
+        };
+        commits.sort_by(|l, r| {
+            let ordering = rank(l).cmp(&rank(r));
+            if by_name {
+                ordering
+                    .then_with(|| l.summary.category.cmp(&r.summary.category))
+            } else {
+                ordering
+            }
+        });
+    }
+
+    /// Returns the releases of the last parsing, containing the commits which
+    /// belong to them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// for release in journal.releases() {
+    ///     println!("{}: {} commits", release.name, release.commit_ids.len());
+    /// }
+    /// ```
+    pub fn releases(&self) -> &[ParsedTag] {
+        &self.parser.result
+    }
+
+    /// Returns the release of the last parsing which contains the given
+    /// commit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// let head = journal.resolve_tag("v1.0.0").ok();
+    /// if let Some(oid) = head {
+    ///     journal.release_of(oid);
+    /// }
+    /// ```
+    pub fn release_of(&self, oid: Oid) -> Option<&ParsedTag> {
+        self.parser
+            .result
             .iter()
-            .map(|tag| {
-                let mut map = Map::new();
-                map.insert(
-                    parser::TOML_TAG.to_owned(),
-                    Value::String(tag.to_owned()),
-                );
-                map.insert(
-                    parser::TOML_NAME_KEY.to_owned(),
-                    Value::String(tag.to_owned()),
-                );
-                map.insert(
-                    parser::TOML_FOOTERS_KEY.to_owned(),
-                    Value::Array(vec![]),
-                );
-                Value::Table(map)
+            .find(|parsed_tag| parsed_tag.commit_ids.contains(&oid))
+    }
+
+    /// Keeps only the entries of the given categories within the results of
+    /// the last parsing, e.g. `["Fixed", "Security"]` for a hotfix notice.
+    /// Releases without any remaining entries are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// journal.retain_categories(&["Fixed"]);
+    /// journal.print_log(false, None, None).expect("Could not print log.");
+    /// ```
+    pub fn retain_categories<T: AsRef<str>>(&mut self, categories: &[T]) {
+        for parsed_tag in &mut self.parser.result {
+            parsed_tag.commits.retain(|commit| {
+                categories
+                    .iter()
+                    .any(|c| c.as_ref() == commit.summary.category)
+            });
+        }
+        self.parser
+            .result
+            .retain(|parsed_tag| !parsed_tag.commits.is_empty());
+    }
+
+    /// Searches the results of the last parsing for commits containing the
+    /// keyword in their summary, body, footers or tags, ignoring the case.
+    /// The results are ordered like the releases and their commits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD").all(true));
+    /// for result in journal.search("parser") {
+    ///     println!("{} [{}] {}", result.release, result.category,
+    ///              result.commit.summary.text);
+    /// }
+    /// ```
+    pub fn search(&self, keyword: &str) -> Vec<SearchResult> {
+        self.parser
+            .result
+            .iter()
+            .flat_map(|parsed_tag| {
+                parsed_tag
+                    .commits
+                    .iter()
+                    .filter(|commit| commit.matches(keyword))
+                    .map(move |commit| SearchResult {
+                        release: parsed_tag.name.clone(),
+                        category: commit.summary.category.clone(),
+                        commit: commit.clone(),
+                    })
             })
-            .collect::<Vec<Value>>();
-        toml_map.insert("tags".to_owned(), Value::Array(toml_tags));
+            .collect()
+    }
+
+    /// Renders the commits matching the keyword like `GitJournal::search`
+    /// below a "Search results" heading, structured by their releases.
+    ///
+    /// # Errors
+    /// If some commit message could not be rendered.
+    pub fn render_search(
+        &self,
+        keyword: &str,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<String, Error> {
+        let mut config = self.config.clone();
+        config.changelog_header = Some(format!(
+            "# {} '{}'",
+            config.translate("Search results for"),
+            keyword
+        ));
+        config.changelog_footer = None;
+        let mut parser = Parser {
+            config,
+            result: self.parser.result.clone(),
+        };
+        for parsed_tag in &mut parser.result {
+            parsed_tag.commits.retain(|commit| commit.matches(keyword));
+        }
+        parser
+            .result
+            .retain(|parsed_tag| !parsed_tag.commits.is_empty());
+        let output_vec = parser.render(&compact, template, &parser.config)?;
+        Ok(String::from_utf8(output_vec)?)
+    }
+
+    /// Finds the commits of the last parsing which touched the given file or
+    /// directory path, or added or removed lines containing the query, e.g.
+    /// a function name. This answers which releases changed a part of the
+    /// project, where the results are ordered like the releases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD").all(true));
+    /// for result in journal.releases_touching("src/parser.rs").unwrap() {
+    ///     println!("{}: {}", result.release, result.commit.summary.text);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// When the repository or the diff of a commit could not be read.
+    pub fn releases_touching(
+        &self,
+        query: &str,
+    ) -> Result<Vec<SearchResult>, Error> {
+        let repo = Repository::open(&self.path)?;
+        let mut results = vec![];
+        for parsed_tag in &self.parser.result {
+            for parsed_commit in &parsed_tag.commits {
+                let oid = match parsed_commit.oid {
+                    Some(oid) => oid,
+                    None => continue,
+                };
+                if changes::touches(&repo, &repo.find_commit(oid)?, query)? {
+                    results.push(SearchResult {
+                        release: parsed_tag.name.clone(),
+                        category: parsed_commit.summary.category.clone(),
+                        commit: parsed_commit.clone(),
+                    });
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    /// Returns all releases sorted from the newest to the oldest one. Tags
+    /// matching a non empty skip pattern are not considered as releases, as well as
+    /// pre-release tags which should be folded into their final release.
+    fn get_releases(
+        &self,
+        repo: &Repository,
+        tag_skip_pattern: &str,
+    ) -> Result<Vec<Release>, Error> {
+        let tags = self
+            .tags
+            .iter()
+            .filter(|tag| {
+                tag_skip_pattern.is_empty() || !tag.1.contains(tag_skip_pattern)
+            })
+            .collect::<Vec<_>>();
+        let mut releases: Vec<Release> = vec![];
+        for tag in tags.iter().filter(|tag| {
+            let final_name = Self::get_final_release_name(&tag.1);
+            !(self.config.fold_prereleases
+                && tags.iter().any(|t| Some(&t.1) == final_name.as_ref()))
+        }) {
+            // Multiple tags on the same commit belong to the same release
+            if let Some(release) = releases.iter_mut().find(|r| r.0 == tag.0) {
+                if !release.1.contains(&tag.1) {
+                    release.1.push(tag.1.clone());
+                }
+                continue;
+            }
+            let commit = repo.find_commit(tag.0)?;
+            let date = Utc.timestamp(commit.time().seconds(), 0);
+            releases.push((tag.0, vec![tag.1.clone()], date));
+        }
+        match self.config.tag_sorting.as_str() {
+            "time" => releases.sort_by_key(|r| Reverse(r.2)),
+            "semver" => releases
+                .sort_by_key(|r| Reverse((Self::get_release_version(r), r.2))),
+            _ => {
+                return Err(ErrorKind::Config("tag_sorting".to_owned()).wrap(
+                    format_err!(
+                        "Unknown tag sorting '{}'",
+                        self.config.tag_sorting
+                    ),
+                ))
+            }
+        }
+        Ok(releases)
+    }
+
+    /// Returns the version of the crate from the `Cargo.toml` of the
+    /// repository, which can be inherited from the workspace as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// assert!(journal.cargo_version().is_ok());
+    /// ```
+    ///
+    /// # Errors
+    /// If the `Cargo.toml` could not be read or contains no version.
+    pub fn cargo_version(&self) -> Result<String, Error> {
+        let path = Path::new(&self.path).join("Cargo.toml");
+        let file = || ErrorKind::File(path.display().to_string());
+        let manifest: Value =
+            toml::from_str(&fs::read_to_string(&path).context(file())?)
+                .context(file())?;
+        match cargo::version(&manifest) {
+            Some(version) => Ok(version.to_owned()),
+            None => bail!("No version found in '{}'", path.display()),
+        }
+    }
+
+    /// Prepares a release for `cargo release`, which runs this as
+    /// `pre-release-hook` after bumping the version. The unreleased section
+    /// will be named after the given version or the one of the `Cargo.toml`
+    /// and the whole log is written to the output file, which will be
+    /// verified to contain the new release afterwards. The release name uses
+    /// the format of `cargo_version`, which defaults to "v{version}". The
+    /// changelog fragments of the `fragments_dir` are removed after writing.
+    /// Returns the written changelog.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gitjournal::GitJournal;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal
+    ///     .pre_release(Some("2.0.0"), "CHANGELOG.md", false)
+    ///     .expect("Could not prepare the release.");
+    /// ```
+    ///
+    /// # Errors
+    /// When the version is invalid or already released, there are no
+    /// unreleased changes or writing and verifying the output failed.
+    pub fn pre_release(
+        &mut self,
+        version: Option<&str>,
+        output: &str,
+        dry_run: bool,
+    ) -> Result<String, Error> {
+        let version = match version {
+            Some(version) => version.to_owned(),
+            None => self.cargo_version()?,
+        };
+        Version::parse(&version)?;
+        let name = self
+            .config
+            .cargo_version
+            .as_deref()
+            .unwrap_or("v{version}")
+            .replace("{version}", &version);
+        if self.tags.iter().any(|(_, tag)| *tag == name) {
+            bail!("The release '{}' already exists.", name);
+        }
+
+        // Parse the whole log and name the unreleased section
+        let fragments = self.get_fragments()?;
+        self.parser.result.clear();
+        self.parse_log(&ParseOptions::new("HEAD").all(true))?;
+        match self.parser.result.iter_mut().find(|tag| tag.oid.is_none()) {
+            Some(unreleased) => unreleased.name = name.clone(),
+            None => bail!("No unreleased changes found for '{}'.", name),
+        }
+        let used_template = self.get_template(None);
+        let changelog = String::from_utf8(self.parser.render(
+            &false,
+            used_template.as_deref(),
+            &self.config,
+        )?)?;
+        if dry_run {
+            return Ok(changelog);
+        }
+
+        self.write_output(output, changelog.clone().into_bytes(), false)?;
+        let written = fs::read_to_string(output)
+            .context(ErrorKind::File(output.to_owned()))?;
+        if !written
+            .lines()
+            .any(|line| line.contains(&format!("# {}", name)))
+        {
+            bail!("The release '{}' is missing in '{}'.", name, output);
+        }
+        info!("Release '{}' written to '{}'.", name, output);
+
+        // Remove the fragments which are part of the release now
+        for (path, _) in &fragments {
+            fs::remove_file(path)
+                .context(ErrorKind::File(path.display().to_string()))?;
+        }
+        if !fragments.is_empty() {
+            info!("Removed {} changelog fragments.", fragments.len());
+        }
+        Ok(changelog)
+    }
+
+    /// Scores the commit hygiene of every parsed release with commits, based
+    /// on the share of valid commit messages, commits with a body and with
+    /// a reference to an issue or pull request as well as heuristics of the
+    /// summaries. The trend of every release is the difference of its score
+    /// to the previous release.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD").all(true));
+    /// for quality in journal.quality() {
+    ///     println!("{}: {:.0}", quality.release, quality.score);
+    /// }
+    /// ```
+    pub fn quality(&self) -> Vec<ReleaseQuality> {
+        quality::score(&self.parser.result, &self.config)
+    }
+
+    /// Compares two parsed releases by the entry counts of their categories
+    /// and collects the breaking changes introduced after the older release
+    /// up to the newer one, e.g. as input for an upgrade guide.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD").all(true));
+    /// if let Ok(diff) = journal.diff_releases("v1.0.0", "v2.0.0") {
+    ///     println!("{} breaking changes", diff.breaking_changes.len());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// If one of the releases is not part of the parsing results or `from` is
+    /// newer than `to`.
+    pub fn diff_releases(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<ReleaseDiff, Error> {
+        diff::diff(&self.parser.result, from, to, &self.config)
+    }
+
+    /// Generates the skeleton of an "Upgrading from X to Y" document, which
+    /// lists every breaking change introduced after the older release up to
+    /// the newer one together with the link to its commit and its body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD").all(true));
+    /// if let Ok(guide) = journal.upgrade_guide("v1.0.0", "v2.0.0") {
+    ///     println!("{}", guide);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// If one of the releases is not part of the parsing results, `from` is
+    /// newer than `to` or the configured `provider` is unknown.
+    pub fn upgrade_guide(&self, from: &str, to: &str) -> Result<String, Error> {
+        let diff = self.diff_releases(from, to)?;
+        let links = Provider::from_config(&self.config)?;
+        Ok(diff::upgrade_guide(&diff, &self.config, |oid| {
+            links.map(|(provider, url)| provider.commit_url(url, oid))
+        }))
+    }
+
+    /// Returns the compact highlights of a release for posts on social media
+    /// or preview texts, which defaults to the first parsed release. The
+    /// entries are selected by the priority of the `highlight_categories`
+    /// within the `highlight_count` and the `highlight_width`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD").all(true));
+    /// if let Ok(highlights) = journal.highlights(None) {
+    ///     println!("{}", highlights);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// If the release is not part of the parsing results.
+    pub fn highlights(&self, release: Option<&str>) -> Result<String, Error> {
+        let parsed_tag = match release {
+            Some(release) => self.parser.result.iter().find(|tag| {
+                tag.name
+                    .split(MERGED_TAGS_SEPARATOR)
+                    .any(|name| name == release)
+            }),
+            None => self.parser.result.first(),
+        };
+        match parsed_tag {
+            Some(parsed_tag) => {
+                Ok(highlights::highlights(parsed_tag, &self.config))
+            }
+            None => bail!(
+                "Release '{}' not found in the parsed log.",
+                release.unwrap_or_default()
+            ),
+        }
+    }
+
+    /// Suggests the next semantic version based on the parsed unreleased
+    /// commits. Breaking changes within the `major_categories` or tagged as
+    /// `:breaking:` lead to a major bump, new features within the
+    /// `minor_categories` to a minor bump and everything else to a patch
+    /// bump. Versions below 1.0.0 bump the minor version for breaking changes
+    /// and the patch version otherwise. The current version is returned if
+    /// there are no unreleased commits at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    /// use semver::Version;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// let next = journal.suggest_version(&Version::new(1, 2, 3));
+    /// assert!(next >= Version::new(1, 2, 3));
+    /// ```
+    pub fn suggest_version(&self, current: &Version) -> Version {
+        let commits = match self.parser.result.iter().find(|t| t.oid.is_none())
+        {
+            Some(unreleased) if !unreleased.commits.is_empty() => {
+                &unreleased.commits
+            }
+            _ => return current.clone(),
+        };
+        let is_in = |categories: &[String]| {
+            commits
+                .iter()
+                .any(|c| categories.contains(&c.summary.category))
+        };
+        let breaking =
+            commits.iter().any(|c| diff::is_breaking(c, &self.config));
+        let feature = is_in(&self.config.minor_categories);
+
+        let mut next = current.clone();
+        match (current.major, breaking, feature) {
+            (0, true, _) => next.increment_minor(),
+            (0, false, _) => next.increment_patch(),
+            (_, true, _) => next.increment_major(),
+            (_, false, true) => next.increment_minor(),
+            (_, false, false) => next.increment_patch(),
+        }
+        next
+    }
+
+    /// Sets the version of the `Cargo.toml` and all workspace members to the
+    /// suggested next version of `suggest_version`, where every manifest is
+    /// bumped from its own version and inherited versions stay untouched. The
+    /// edit is returned as diff for review and the files will only be written
+    /// if `write` is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// if let Ok(diff) = journal.bump_cargo_version(false) {
+    ///     print!("{}", diff);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// When the manifests could not be read or written, the current version
+    /// is invalid or there are no unreleased changes.
+    pub fn bump_cargo_version(&self, write: bool) -> Result<String, Error> {
+        let current = Version::parse(&self.cargo_version()?)?;
+        let next = self.suggest_version(&current);
+        if next == current {
+            bail!("No unreleased changes found for a new version.");
+        }
+
+        // Collect the edits of the root and the member manifests
+        let root = Path::new(&self.path);
+        let manifest = fs::read_to_string(root.join("Cargo.toml"))?;
+        let mut paths = vec![root.to_path_buf()];
+        paths.extend(cargo::members(root, &manifest)?);
+        let mut edits = vec![];
+        for path in paths {
+            let path = path.join("Cargo.toml");
+            let file = || ErrorKind::File(path.display().to_string());
+            let old = fs::read_to_string(&path).context(file())?;
+            let manifest: Value = toml::from_str(&old).context(file())?;
+            let version = match cargo::version(&manifest) {
+                Some(version) => Version::parse(version).context(file())?,
+                None => continue,
+            };
+            let version = self.suggest_version(&version).to_string();
+            if let Some(new) = cargo::set_version(&old, &version) {
+                let name = path.strip_prefix(root).unwrap_or(&path);
+                edits.push(cargo::Edit {
+                    name: name.display().to_string(),
+                    path,
+                    old,
+                    new,
+                });
+            }
+        }
+
+        let diff = edits.iter().map(cargo::diff).collect::<String>();
+        if write {
+            for edit in &edits {
+                fs::write(&edit.path, &edit.new).context(ErrorKind::File(
+                    edit.path.display().to_string(),
+                ))?;
+            }
+            info!("Version bumped from {} to {}.", current, next);
+        }
+        Ok(diff)
+    }
+
+    /// Returns the current date for the unreleased section. The output is
+    /// kept byte-stable in `reproducible` mode, where the date is taken from
+    /// the `SOURCE_DATE_EPOCH` environment variable or the commit time of
+    /// `HEAD` instead.
+    fn get_current_date(&self) -> DateTime<Utc> {
+        if !self.config.reproducible {
+            return Utc::now();
+        }
+        self.get_source_date(env::var("SOURCE_DATE_EPOCH").ok())
+    }
+
+    /// Returns the date of the given `SOURCE_DATE_EPOCH`, or the commit time
+    /// of `HEAD` if it is not set or invalid.
+    fn get_source_date(&self, epoch: Option<String>) -> DateTime<Utc> {
+        let seconds = epoch
+            .and_then(|epoch| epoch.trim().parse().ok())
+            .or_else(|| {
+                let repo = Repository::open(&self.path).ok()?;
+                let head = repo.head().ok()?.peel_to_commit().ok()?;
+                Some(head.time().seconds())
+            })
+            .unwrap_or(0);
+        Utc.timestamp(seconds, 0)
+    }
+
+    /// Returns the name of the unreleased section, which is the version of
+    /// the crate if `cargo_version` is set.
+    fn get_unreleased_name(&self) -> String {
+        if let Some(ref format) = self.config.cargo_version {
+            match self.cargo_version() {
+                Ok(version) => return format.replace("{version}", &version),
+                Err(e) => warn!("Could not get the crate version: {}", e),
+            }
+        }
+        "Unreleased".to_owned()
+    }
+
+    /// Returns the semantic version of a tag name, where a leading "v" will
+    /// be ignored.
+    fn get_version(name: &str) -> Option<Version> {
+        Version::parse(name.trim_start_matches(&['v', 'V'][..])).ok()
+    }
+
+    /// Returns the highest semantic version of all tags of a release.
+    fn get_release_version(release: &Release) -> Option<Version> {
+        release
+            .1
+            .iter()
+            .filter_map(|name| Self::get_version(name))
+            .max()
+    }
+
+    /// Checks if a release should be part of the output regarding the
+    /// configured version filters. Releases without a semantic version are
+    /// skipped as soon as any filter is set.
+    fn is_release_selected(
+        &self,
+        release: &Release,
+        min_version: &Option<Version>,
+    ) -> bool {
+        if min_version.is_none() && self.config.major_version.is_none() {
+            return true;
+        }
+        let version = match Self::get_release_version(release) {
+            Some(version) => version,
+            None => return false,
+        };
+        if let Some(ref min_version) = *min_version {
+            if version < *min_version {
+                return false;
+            }
+        }
+        if let Some(major_version) = self.config.major_version {
+            if version.major != major_version {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Returns the name of the final release if the given tag name is a
+    /// semantic pre-release version after an optional prefix, e.g. "v1.2.0"
+    /// for "v1.2.0-rc1" or "release-1.2.0" for "release-1.2.0-beta.2".
+    fn get_final_release_name(name: &str) -> Option<String> {
+        let index = name.find(|c: char| c.is_ascii_digit())?;
+        let (prefix, version) = name.split_at(index);
+        match Version::parse(version) {
+            Ok(ref version) if !version.pre.is_empty() => Some(format!(
+                "{}{}.{}.{}",
+                prefix, version.major, version.minor, version.patch
+            )),
+            _ => None,
+        }
+    }
+
+    /// Maps every commit reachable from the given releases to the index of
+    /// the oldest release containing it. Commits reachable from one of the
+    /// hidden commits are skipped.
+    fn get_release_map(
+        repo: &Repository,
+        releases: &[Release],
+        hidden: &[Oid],
+        options: &ParseOptions,
+    ) -> Result<HashMap<Oid, usize>, Error> {
+        let mut release_map = HashMap::new();
+        for (index, release) in releases.iter().enumerate().rev() {
+            let mut revwalk = repo.revwalk()?;
+            revwalk.push(release.0)?;
+            for oid in hidden
+                .iter()
+                .chain(releases[index + 1..].iter().map(|r| &r.0))
+            {
+                revwalk.hide(*oid)?;
+            }
+            for id in revwalk {
+                options.check_cancelled()?;
+                release_map.entry(id?).or_insert(index);
+            }
+        }
+        Ok(release_map)
+    }
+
+    /// Returns the sorting for the revwalk and if the results should be
+    /// reversed. The revwalk itself always starts with the newest commits,
+    /// since this is needed to assign the commits to their tags.
+    fn get_revwalk_sorting(&self) -> Result<(git2::Sort, bool), Error> {
+        let mut sorting = git2::Sort::NONE;
+        let mut reverse = false;
+        for item in &self.config.revwalk_sorting {
+            match item.as_str() {
+                "time" => sorting |= git2::Sort::TIME,
+                "topological" => sorting |= git2::Sort::TOPOLOGICAL,
+                "reverse" => reverse = true,
+                _ => {
+                    return Err(ErrorKind::Config("revwalk_sorting".to_owned())
+                        .wrap(format_err!(
+                            "Unknown revwalk sorting '{}'",
+                            item
+                        )))
+                }
+            }
+        }
+        Ok((sorting, reverse))
+    }
+
+    /// Generates an output template from the current parsing results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// journal
+    ///     .generate_template()
+    ///     .expect("Template generation failed.");
+    /// ```
+    ///
+    /// # Errors
+    /// If the generation of the template was impossible.
+    pub fn generate_template(&self) -> Result<(), Error> {
+        let mut tags = vec![parser::TOML_DEFAULT_KEY.to_owned()];
+
+        // Get all the tags
+        for parsed_tag in &self.parser.result {
+            tags = parsed_tag.get_tags_unique(tags);
+        }
+
+        if tags.len() > 1 {
+            info!("Found tags: '{}'.", tags[1..].join(", "));
+        } else {
+            warn!("No tags found.");
+        }
+
+        // Create the toml representation
+        let mut toml_map = Map::new();
+        let toml_tags = tags
+            .iter()
+            .map(|tag| {
+                let mut map = Map::new();
+                map.insert(
+                    parser::TOML_TAG.to_owned(),
+                    Value::String(tag.to_owned()),
+                );
+                map.insert(
+                    parser::TOML_NAME_KEY.to_owned(),
+                    Value::String(tag.to_owned()),
+                );
+                map.insert(
+                    parser::TOML_FOOTERS_KEY.to_owned(),
+                    Value::Array(vec![]),
+                );
+                Value::Table(map)
+            })
+            .collect::<Vec<Value>>();
+        toml_map.insert("tags".to_owned(), Value::Array(toml_tags));
+
+        let mut header_footer_map = Map::new();
+        header_footer_map
+            .insert(parser::TOML_ONCE_KEY.to_owned(), Value::Boolean(false));
+        header_footer_map.insert(
+            parser::TOML_TEXT_KEY.to_owned(),
+            Value::String(String::new()),
+        );
+        toml_map.insert(
+            parser::TOML_HEADER_KEY.to_owned(),
+            Value::Table(header_footer_map.clone()),
+        );
+        toml_map.insert(
+            parser::TOML_FOOTER_KEY.to_owned(),
+            Value::Table(header_footer_map),
+        );
+
+        let toml = Value::Table(toml_map);
+
+        // Write toml to file
+        let mut path_buf = PathBuf::from(&self.path);
+        path_buf.push("template.toml");
+        let toml_string = toml::to_string(&toml)?;
+        let mut toml_file = File::create(&path_buf)?;
+        toml_file.write_all(toml_string.as_bytes())?;
+
+        info!("Template written to '{}'", path_buf.display());
+        Ok(())
+    }
+
+    /// Returns the GPG program and the signing key from the git
+    /// configuration, where the `signing_key` takes precedence.
+    fn get_signing_config(&self) -> (String, Option<String>) {
+        let config = Repository::open(&self.path).and_then(|r| r.config());
+        let get = |name: &str| {
+            config.as_ref().ok().and_then(|c| c.get_string(name).ok())
+        };
+        let program = get("gpg.program").unwrap_or_else(|| "gpg".to_owned());
+        let key = self
+            .config
+            .signing_key
+            .clone()
+            .or_else(|| get("user.signingkey"));
+        (program, key)
+    }
+
+    /// Appends the signature block to the output if the signing `mode` is
+    /// "trailer". The signature covers the content already `written` to the
+    /// file as well, which keeps appended outputs verifiable as a whole.
+    fn sign_trailer(
+        &self,
+        mode: &str,
+        written: &[u8],
+        mut output: Vec<u8>,
+    ) -> Result<Vec<u8>, Error> {
+        match mode {
+            "off" | "detached" => {}
+            "trailer" => {
+                let (program, key) = self.get_signing_config();
+                let content = [written, &output].concat();
+                let signature =
+                    signing::sign(&program, key.as_deref(), &content)?;
+                output.extend(signing::trailer(&signature).into_bytes());
+            }
+            mode => {
+                return Err(ErrorKind::Config("sign_output".to_owned())
+                    .wrap(format_err!("Unknown signing '{}'", mode)))
+            }
+        }
+        Ok(output)
+    }
+
+    /// Writes the detached signature of the file next to it with the
+    /// extension ".asc" if the signing `mode` is "detached".
+    fn sign_detached(&self, mode: &str, path: &str) -> Result<(), Error> {
+        if mode != "detached" {
+            return Ok(());
+        }
+        let file = || ErrorKind::File(path.to_owned());
+        let content = fs::read(path).context(file())?;
+        let (program, key) = self.get_signing_config();
+        let signature = signing::sign(&program, key.as_deref(), &content)?;
+        let signature_path = format!("{}.asc", path);
+        fs::write(&signature_path, signature)
+            .context(ErrorKind::File(signature_path.clone()))?;
+        info!("Signature written to '{}'.", signature_path);
+        Ok(())
+    }
+
+    /// Writes the Markdown or plain text output to the file, which is either
+    /// appended to or replaced, and signs the resulting file according to
+    /// `sign_output`. Structured exports are written via `write_export`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let journal = GitJournal::new(".").unwrap();
+    /// journal
+    ///     .write_output("target/output.md", b"# Changelog\n".to_vec(), false)
+    ///     .expect("Could not write the output.");
+    /// ```
+    ///
+    /// # Errors
+    /// If the file could not be written or signed.
+    pub fn write_output(
+        &self,
+        output: &str,
+        content: Vec<u8>,
+        append: bool,
+    ) -> Result<(), Error> {
+        self.write_signed(output, content, append, &self.config.sign_output)
+    }
+
+    /// Writes the export of the format like `export` to the file, which is
+    /// replaced. A "trailer" signature would make the structured formats
+    /// invalid, which are therefore signed detached instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// journal
+    ///     .write_export("json", "target/output.json")
+    ///     .expect("Could not write the export.");
+    /// ```
+    ///
+    /// # Errors
+    /// If the export failed or the file could not be written or signed.
+    pub fn write_export(
+        &self,
+        format: &str,
+        output: &str,
+    ) -> Result<(), Error> {
+        let mode = match self.config.sign_output.as_str() {
+            "trailer" => "detached",
+            mode => mode,
+        };
+        let content = self.export(format)?.into_bytes();
+        self.write_signed(output, content, false, mode)
+    }
+
+    /// Writes the output to the file and signs it with the signing `mode`.
+    fn write_signed(
+        &self,
+        output: &str,
+        content: Vec<u8>,
+        append: bool,
+        mode: &str,
+    ) -> Result<(), Error> {
+        let file = || ErrorKind::File(output.to_owned());
+        let written = if append && Path::new(output).exists() {
+            fs::read(output).context(file())?
+        } else {
+            vec![]
+        };
+        let content = self.sign_trailer(mode, &written, content)?;
+        let mut output_file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(output)
+            .context(file())?;
+        output_file.write_all(&content).context(file())?;
+        info!("Output written to '{}'.", output);
+        self.sign_detached(mode, output)
+    }
+
+    /// Prints the resulting log in a short or detailed variant. Will use the
+    /// template as an output formatter if provided.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// journal
+    ///     .print_log(true, None, None)
+    ///     .expect("Could not print short log.");
+    /// journal
+    ///     .print_log(false, None, None)
+    ///     .expect("Could not print detailed log.");
+    /// ```
+    ///
+    /// # Errors
+    /// If some commit message could not be print.
+    pub fn print_log(
+        &self,
+        compact: bool,
+        template: Option<&str>,
+        output: Option<&str>,
+    ) -> Result<(), Error> {
+        // Print the log
+        let used_template = self.get_template(template);
+        let output_vec = self.parser.print(
+            &compact,
+            used_template.as_deref(),
+            &self.config,
+        )?;
+
+        // Print the log to the file if necessary
+        if let Some(output) = output {
+            self.write_output(output, output_vec, true)?;
+        }
+
+        Ok(())
+    }
+
+    /// Parses and renders the log like `print_log` without printing it, where
+    /// the rendered log is cached within the `cache_dir`. The cache is keyed
+    /// by the parse options, the references of the repository, the effective
+    /// configuration, the template, the manual entries, the formatted date of
+    /// the unreleased section and the number of registered rules, hooks and
+    /// backends, which invalidates outdated entries automatically. Only the
+    /// newest 16 logs are kept. Issues fetched during the enrichment are
+    /// cached as well and will only be updated if any of these inputs change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.config.cache_dir = Some("target/gitjournal".to_owned());
+    /// let log = journal
+    ///     .render_cached(&ParseOptions::new("HEAD"), true, None)
+    ///     .expect("Could not render the log.");
+    /// ```
+    ///
+    /// # Errors
+    /// If the parsing or rendering failed or the cache could not be written.
+    pub fn render_cached(
+        &mut self,
+        options: &ParseOptions,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<String, Error> {
+        Ok(self.get_cached_log(options, compact, template)?.log)
+    }
+
+    /// Prints the log rendered by `render_cached` like `print_log`, where the
+    /// colors of the terminal output and the warnings of the parsing are
+    /// cached as well. The output file is appended to and signed the same
+    /// way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.config.cache_dir = Some("target/gitjournal".to_owned());
+    /// journal
+    ///     .print_cached(&ParseOptions::new("HEAD"), true, None, None)
+    ///     .expect("Could not print the log.");
+    /// ```
+    ///
+    /// # Errors
+    /// If the log could not be rendered, printed or written to the output.
+    pub fn print_cached(
+        &mut self,
+        options: &ParseOptions,
+        compact: bool,
+        template: Option<&str>,
+        output: Option<&str>,
+    ) -> Result<(), Error> {
+        let cached = self.get_cached_log(options, compact, template)?;
+        for warning in &cached.warnings {
+            warn!("{}", warning);
+        }
+        let mut term = output::terminal(
+            self.config.use_pager,
+            self.config.pager.as_deref(),
+        );
+        output::replay(&cached.output, &mut *term)?;
+        writeln!(term)?;
+        term.finish()?;
+
+        if let Some(output) = output {
+            self.write_output(output, cached.log.into_bytes(), true)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the cached log of `render_cached`, or parses, renders and
+    /// caches it if the inputs changed.
+    fn get_cached_log(
+        &mut self,
+        options: &ParseOptions,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<CachedLog, Error> {
+        let used_template = self.get_template(template);
+        let cache_file = match self.config.cache_dir {
+            Some(ref dir) => {
+                let key = self.get_cache_key(
+                    options,
+                    compact,
+                    used_template.as_deref(),
+                )?;
+                let name = format!("log-{}.json", key);
+                Some(Path::new(&self.path).join(dir).join(name))
+            }
+            None => None,
+        };
+        if let Some(ref path) = cache_file {
+            let cached = fs::read_to_string(path)
+                .ok()
+                .and_then(|json| serde_json::from_str(&json).ok());
+            if let Some(cached) = cached {
+                info!("Using the cached log '{}'.", path.display());
+                return Ok(cached);
+            }
+        }
+
+        self.parser.result.clear();
+        let warnings = self.parse_log(options)?;
+        let (log, output) = self.parser.record(
+            &compact,
+            used_template.as_deref(),
+            &self.config,
+        )?;
+        let cached = CachedLog {
+            log: String::from_utf8(log)?,
+            output,
+            warnings: warnings.iter().map(ToString::to_string).collect(),
+        };
+        if let Some(ref path) = cache_file {
+            let file = || ErrorKind::File(path.display().to_string());
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).context(file())?;
+                fs::write(path, serde_json::to_string(&cached)?)
+                    .context(file())?;
+                Self::prune_cached_logs(dir)?;
+            }
+        }
+        Ok(cached)
+    }
+
+    /// Removes all but the newest `CACHED_LOGS` logs from the cache
+    /// directory.
+    fn prune_cached_logs(dir: &Path) -> Result<(), Error> {
+        let file = |path: &Path| ErrorKind::File(path.display().to_string());
+        let mut logs = vec![];
+        for entry in fs::read_dir(dir).context(file(dir))? {
+            let path = entry.context(file(dir))?.path();
+            let name = path.file_name().and_then(|name| name.to_str());
+            if name
+                .is_some_and(|n| n.starts_with("log-") && n.ends_with(".json"))
+            {
+                let modified = fs::metadata(&path)
+                    .and_then(|metadata| metadata.modified())
+                    .context(file(&path))?;
+                logs.push((Reverse(modified), path));
+            }
+        }
+        logs.sort();
+        for (_, path) in logs.into_iter().skip(CACHED_LOGS) {
+            fs::remove_file(&path).context(file(&path))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the hash of all inputs of the rendered log as cache key.
+    fn get_cache_key(
+        &self,
+        options: &ParseOptions,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<String, Error> {
+        let mut hasher = DefaultHasher::new();
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        options.cache_key().hash(&mut hasher);
+        compact.hash(&mut hasher);
+        self.generation.hash(&mut hasher);
+        self.get_refs_state()?.hash(&mut hasher);
+        toml::to_string(&self.config)?.hash(&mut hasher);
+        if let Some(template) = template {
+            fs::read(template)
+                .context(ErrorKind::File(template.to_owned()))?
+                .hash(&mut hasher);
+        }
+        let mut entries = self.get_manual_entries_from_file()?;
+        entries.extend(self.get_fragments()?.into_iter().map(|f| f.1));
+        entries.extend(self.manual_entries.clone());
+        format!("{:?}", entries).hash(&mut hasher);
+        self.get_unreleased_name().hash(&mut hasher);
+        ParsedTag {
+            name: String::new(),
+            title: None,
+            date: self.get_current_date(),
+            oid: None,
+            commit_ids: vec![],
+            commits: vec![],
+            message_ids: vec![],
+        }
+        .format_date(&self.config)?
+        .hash(&mut hasher);
+        Ok(format!("{:016x}", hasher.finish()))
+    }
+
+    /// Watches the repository and parses the log again whenever `HEAD` or one
+    /// of the references changes. New commits on top of the last seen `HEAD`
+    /// are merged into the `Unreleased` section, where only they are parsed,
+    /// and every other change parses the whole log again. The callback
+    /// receives the `Unreleased` section, if there is one, once initially and
+    /// then after every change. Watching stops as soon as the callback
+    /// returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    /// use std::time::Duration;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// let options = ParseOptions::new("HEAD").max_tags_count(0);
+    /// journal
+    ///     .watch(&options, Duration::from_secs(1), |unreleased| {
+    ///         if let Some(unreleased) = unreleased {
+    ///             println!("{} unreleased commits", unreleased.commits.len());
+    ///         }
+    ///         false
+    ///     })
+    ///     .expect("Could not watch the repository.");
+    /// ```
+    ///
+    /// # Errors
+    /// When the references could not be read or the parsing failed.
+    pub fn watch<F>(
+        &mut self,
+        options: &ParseOptions,
+        interval: Duration,
+        mut callback: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(Option<&ParsedTag>) -> bool,
+    {
+        let mut state = None;
+        loop {
+            let new_state = self.get_refs_state()?;
+            if state.as_ref() != Some(&new_state) {
+                let appended = match state {
+                    Some(ref state) => {
+                        self.parse_appended(state, &new_state, options)?
+                    }
+                    None => false,
+                };
+                if !appended {
+                    self.load_tags(&Repository::open(&self.path)?)?;
+                    self.parser.result.clear();
+                    self.parse_log(options)?;
+                }
+                state = Some(new_state);
+                let unreleased =
+                    self.parser.result.iter().find(|t| t.oid.is_none());
+                if !callback(unreleased) {
+                    return Ok(());
+                }
+            }
+            thread::sleep(interval);
+        }
+    }
+
+    /// Parses only the commits added on top of the `HEAD` of the old state
+    /// and merges them into the `Unreleased` section. Returns `false` if the
+    /// log has to be parsed completely, because another reference than `HEAD`
+    /// and its branch changed, the history was rewritten or one of the new
+    /// commits is tagged.
+    fn parse_appended(
+        &mut self,
+        old_state: &[(String, Option<Oid>)],
+        new_state: &[(String, Option<Oid>)],
+        options: &ParseOptions,
+    ) -> Result<bool, Error> {
+        let repo = Repository::open(&self.path)?;
+        let head = repo.head()?;
+        if old_state.len() != new_state.len()
+            || old_state.iter().zip(new_state).any(|(old, new)| {
+                old.0 != new.0
+                    || (old.1 != new.1
+                        && old.0 != "HEAD"
+                        && Some(old.0.as_str()) != head.name())
+            })
+        {
+            return Ok(false);
+        }
+        let head_of = |state: &[(String, Option<Oid>)]| {
+            state
+                .iter()
+                .find(|(name, _)| name == "HEAD")
+                .and_then(|(_, oid)| *oid)
+        };
+        let (old, new) = match (head_of(old_state), head_of(new_state)) {
+            (Some(old), Some(new)) if repo.graph_descendant_of(new, old)? => {
+                (old, new)
+            }
+            _ => return Ok(false),
+        };
+
+        // Parse the new commits on their own and keep the former results
+        let result = mem::take(&mut self.parser.result);
+        let range = format!("{}..{}", old, new);
+        let parsed = self.parse_log(&options.clone().revision_range(&range));
+        let mut appended = mem::replace(&mut self.parser.result, result);
+        parsed?;
+        match appended.pop() {
+            None => Ok(true),
+            Some(_) if !appended.is_empty() => Ok(false),
+            Some(tag) if tag.oid.is_some() => Ok(false),
+            Some(mut tag) => {
+                match self.parser.result.first_mut() {
+                    Some(unreleased) if unreleased.oid.is_none() => {
+                        tag.commit_ids.append(&mut unreleased.commit_ids);
+                        tag.commits.append(&mut unreleased.commits);
+                        *unreleased = tag;
+                    }
+                    _ => self.parser.result.insert(0, tag),
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    fn get_refs_state(&self) -> Result<Vec<(String, Option<Oid>)>, Error> {
+        let repo = Repository::open(&self.path)?;
+        let mut state = vec![("HEAD".to_owned(), repo.head()?.target())];
+        for reference in repo.references()? {
+            let reference = reference?;
+            if let Some(name) = reference.name() {
+                state.push((name.to_owned(), reference.target()));
+            }
+        }
+        state.sort();
+        Ok(state)
+    }
+
+    /// Returns a read-only snapshot of the current parsing results, which can
+    /// be rendered from multiple threads while this `GitJournal` continues to
+    /// parse. The default template will be resolved once at this point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// let parsed = journal.parsed();
+    /// println!("{}", parsed.render(true, None).unwrap());
+    /// ```
+    pub fn parsed(&self) -> ParsedJournal {
+        let mut parser = self.parser.clone();
+        parser.config = self.config.clone();
+        ParsedJournal::new(parser, self.get_template(None))
+    }
+
+    /// Registers a custom output format, which will be used by `export` for
+    /// the name of the backend instead of a built-in format.
+    pub fn register_backend(&mut self, backend: Box<dyn OutputBackend>) {
+        self.backends.retain(|b| b.name() != backend.name());
+        self.backends.push(backend);
+        self.generation += 1;
+    }
+
+    /// Exports the current parsing results in a structured format, which can
+    /// be "json", "yaml", "toml", or one commit per line as "csv" or "tsv".
+    /// The structured formats contain the `format_version` and follow the
+    /// `JSON_SCHEMA`, where all dates are in UTC regardless of the configured
+    /// `timezone`. The dates of the releases within the CSV are formatted like
+    /// the rendered ones. Formats of registered backends are rendered by them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// journal.export("toml").expect("Could not export the log.");
+    /// ```
+    ///
+    /// # Errors
+    /// If the format is unknown or the serialization failed.
+    pub fn export(&self, format: &str) -> Result<String, Error> {
+        if let Some(backend) = self.backends.iter().find(|b| b.name() == format)
+        {
+            return backend.render(&self.parsed());
+        }
+        match format {
+            "csv" => self.to_csv(','),
+            "json" => self.to_json(),
+            "toml" => self.to_toml(),
+            "tsv" => self.to_csv('\t'),
+            #[cfg(feature = "yaml")]
+            "yaml" => self.to_yaml(),
+            #[cfg(not(feature = "yaml"))]
+            "yaml" => bail!("The yaml export requires the 'yaml' feature"),
+            _ => bail!("Unknown export format '{}'", format),
+        }
+    }
+
+    /// Exports the commits of the current parsing results as comma or tab
+    /// separated values including a header line. The columns are the tag,
+    /// date, category, scope (the summary tags), summary, sha and author.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// println!("{}", journal.to_csv(',').unwrap());
+    /// ```
+    ///
+    /// # Errors
+    /// If the date of a release could not be formatted.
+    pub fn to_csv(&self, delimiter: char) -> Result<String, Error> {
+        let escape = |field: &str| {
+            if delimiter == '\t' {
+                field.replace(&['\t', '\n', '\r'][..], " ")
+            } else if field.contains(|c| {
+                c == delimiter || c == '"' || c == '\n' || c == '\r'
+            }) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_owned()
+            }
+        };
+
+        let header = ["tag", "date", "category", "scope", "summary", "sha"];
+        let mut csv = header.join(&delimiter.to_string());
+        csv += &format!("{}author\n", delimiter);
+        for parsed_tag in &self.parser.result {
+            let date = parsed_tag.format_date(&self.config)?;
+            for commit in &parsed_tag.commits {
+                let fields = [
+                    escape(&parsed_tag.name),
+                    escape(&date),
+                    escape(&commit.summary.category),
+                    escape(&commit.summary.tags.join(", ")),
+                    escape(&commit.summary.text),
+                    commit.oid.map(|oid| oid.to_string()).unwrap_or_default(),
+                    escape(commit.author.as_ref().map_or("", |a| a.as_str())),
+                ];
+                csv += &fields.join(&delimiter.to_string());
+                csv.push('\n');
+            }
+        }
+        Ok(csv)
+    }
+
+    /// Serializes the current parsing results to TOML, where the releases
+    /// are stored within the `releases` array of tables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// println!("{}", journal.to_toml().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    /// If the serialization failed.
+    pub fn to_toml(&self) -> Result<String, Error> {
+        // Converting into a value first ensures that the tables are emitted
+        // after the plain values
+        Ok(toml::to_string(&Value::try_from(self.document())?)?)
+    }
+
+    /// Serializes the current parsing results to pretty printed JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// println!("{}", journal.to_json().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    /// If the serialization failed.
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(&self.document())?)
+    }
+
+    /// Serializes the current parsing results to YAML.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// println!("{}", journal.to_yaml().unwrap());
+    /// ```
+    ///
+    /// # Errors
+    /// If the serialization failed.
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml(&self) -> Result<String, Error> {
+        Ok(serde_yaml::to_string(&self.document())?)
+    }
+
+    /// Returns the versioned document of the structured exports.
+    fn document(&self) -> Document<'_> {
+        Document {
+            format_version: FORMAT_VERSION,
+            releases: &self.parser.result,
+        }
+    }
+
+    /// Prints only the section of a single release, for example to be used as
+    /// the body of a release announcement. The configured changelog header
+    /// and footer will be omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// journal.print_release("Unreleased", false, None, None).ok();
+    /// ```
+    ///
+    /// # Errors
+    /// If the release is not part of the parsing results or printing failed.
+    pub fn print_release(
+        &self,
+        release: &str,
+        compact: bool,
+        template: Option<&str>,
+        output: Option<&str>,
+    ) -> Result<(), Error> {
+        let parser = self.parser.select_release(release, &self.config)?;
+        let used_template = self.get_template(template);
+        let output_vec =
+            parser.print(&compact, used_template.as_deref(), &parser.config)?;
+
+        if let Some(output) = output {
+            self.write_output(output, output_vec, true)?;
+        }
+        Ok(())
+    }
+
+    /// Renders only the section of a single release like `print_release`,
+    /// but returns it instead of printing it to the terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// if let Ok(notes) = journal.render_release("Unreleased", true, None) {
+    ///     println!("{}", notes);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// If the release is not part of the parsing results or rendering failed.
+    pub fn render_release(
+        &self,
+        release: &str,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<String, Error> {
+        let parser = self.parser.select_release(release, &self.config)?;
+        let used_template = self.get_template(template);
+        let output_vec = parser.render(
+            &compact,
+            used_template.as_deref(),
+            &parser.config,
+        )?;
+        Ok(String::from_utf8(output_vec)?)
+    }
+
+    /// Writes the rendered log or the section of a single release as `notes`
+    /// step output of GitHub Actions together with the name of the first
+    /// release as `release`, and appends the notes to the step summary.
+    /// Nothing is written if the workflow variables `GITHUB_OUTPUT` and
+    /// `GITHUB_STEP_SUMMARY` are not set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// journal
+    ///     .write_github_outputs(None, false, None)
+    ///     .expect("Could not write the outputs.");
+    /// ```
+    ///
+    /// # Errors
+    /// If rendering failed or the files could not be written.
+    pub fn write_github_outputs(
+        &self,
+        release: Option<&str>,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<(), Error> {
+        self.write_github_outputs_with(release, compact, template, |key| {
+            env::var(key).ok()
+        })
+    }
+
+    /// Writes the outputs like `write_github_outputs`, where the workflow
+    /// variables are looked up via `var`.
+    fn write_github_outputs_with<F>(
+        &self,
+        release: Option<&str>,
+        compact: bool,
+        template: Option<&str>,
+        var: F,
+    ) -> Result<(), Error>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let output = var("GITHUB_OUTPUT");
+        let summary = var("GITHUB_STEP_SUMMARY");
+        if output.is_none() && summary.is_none() {
+            warn!("Not running within GitHub Actions, no outputs written.");
+            return Ok(());
+        }
+        let (name, notes) = match release {
+            Some(release) => (
+                release.to_owned(),
+                self.render_release(release, compact, template)?,
+            ),
+            None => {
+                let used_template = self.get_template(template);
+                let notes = self.parser.render(
+                    &compact,
+                    used_template.as_deref(),
+                    &self.config,
+                )?;
+                let name = self
+                    .parser
+                    .result
+                    .first()
+                    .map(|tag| tag.name.clone())
+                    .unwrap_or_default();
+                (name, String::from_utf8(notes)?)
+            }
+        };
+        let notes = notes.trim();
+        if let Some(output) = output {
+            actions::append(
+                &output,
+                &(actions::output("release", &name)
+                    + &actions::output("notes", notes)),
+            )?;
+        }
+        if let Some(summary) = summary {
+            actions::append(&summary, &format!("{}\n", notes))?;
+        }
+        Ok(())
+    }
+
+    /// Injects the rendered section of a single release or the first parsed
+    /// one between the `<!-- journal:start -->` and `<!-- journal:end -->`
+    /// markers of the file, e.g. for a "What's new" section of the README.
+    /// The rest of the file is kept and the file is only written if the
+    /// notes changed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD").skip_unreleased(true));
+    /// journal
+    ///     .inject_release_notes("README.md", None, true, None)
+    ///     .expect("Could not inject the release notes.");
+    /// ```
+    ///
+    /// # Errors
+    /// If there is no release, rendering failed, one of the markers is
+    /// missing or the file could not be read or written.
+    pub fn inject_release_notes(
+        &self,
+        path: &str,
+        release: Option<&str>,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<(), Error> {
+        let release = match release
+            .or_else(|| self.parser.result.first().map(|tag| tag.name.as_str()))
+        {
+            Some(release) => release,
+            None => bail!("No release found for the release notes."),
+        };
+        let notes = self.render_release(release, compact, template)?;
+        let file = || ErrorKind::File(path.to_owned());
+        let content = fs::read_to_string(path).context(file())?;
+        let injected = markers::replace(&content, &notes).context(file())?;
+        if injected != content {
+            fs::write(path, injected).context(file())?;
+            info!("Release '{}' injected into '{}'.", release, path);
+        }
+        Ok(())
+    }
+
+    /// Writes a page for every parsed release to the directory together with
+    /// a `SUMMARY.md` fragment linking the pages, which can be added as
+    /// chapter of an mdBook documentation site. Existing pages are replaced
+    /// and the paths of all written files are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD").all(true));
+    /// journal
+    ///     .write_mdbook("docs/src/changelog", false, None)
+    ///     .expect("Could not write the pages.");
+    /// ```
+    ///
+    /// # Errors
+    /// If rendering failed or the files could not be written.
+    pub fn write_mdbook(
+        &self,
+        directory: &str,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<Vec<String>, Error> {
+        let file = |path: &Path| ErrorKind::File(path.display().to_string());
+        let directory = Path::new(directory);
+        fs::create_dir_all(directory).context(file(directory))?;
+        let mut paths = vec![];
+        let mut releases = vec![];
+        for parsed_tag in &self.parser.result {
+            let release = parsed_tag
+                .name
+                .split(MERGED_TAGS_SEPARATOR)
+                .next()
+                .unwrap_or_default();
+            let notes = self.render_release(release, compact, template)?;
+            let file_name = pages::file_name(&parsed_tag.name);
+            let path = directory.join(&file_name);
+            fs::write(&path, notes.trim_start()).context(file(&path))?;
+            paths.push(path.display().to_string());
+            releases.push((parsed_tag.name.clone(), file_name));
+        }
+        let name = directory.file_name().unwrap_or_default().to_string_lossy();
+        let summary = pages::mdbook_summary(&name, &releases);
+        let path = directory.join("SUMMARY.md");
+        fs::write(&path, summary).context(file(&path))?;
+        paths.push(path.display().to_string());
+        info!(
+            "{} release pages written to '{}'.",
+            releases.len(),
+            directory.display()
+        );
+        Ok(paths)
+    }
+
+    /// Writes the page of every parsed release into the content directory of
+    /// a static site generator, which is either "zola" or "hugo". The pages
+    /// contain a front matter with the date, the version and the categories as
+    /// taxonomy terms, see `pages::site_page`. An index of the section is
+    /// created as `_index.md` if it does not exist yet. Returns the paths of
+    /// all written files.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD").all(true));
+    /// journal
+    ///     .write_site("site/content/releases", "zola", false, None)
+    ///     .expect("Could not write the pages.");
+    /// ```
+    ///
+    /// # Errors
+    /// If the generator is unknown, rendering failed or the files could not be
+    /// written.
+    pub fn write_site(
+        &self,
+        directory: &str,
+        generator: &str,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<Vec<String>, Error> {
+        let file = |path: &Path| ErrorKind::File(path.display().to_string());
+        let directory = Path::new(directory);
+        let mut pages = vec![];
+        for parsed_tag in &self.parser.result {
+            let release = parsed_tag
+                .name
+                .split(MERGED_TAGS_SEPARATOR)
+                .next()
+                .unwrap_or_default();
+            let notes = self.render_release(release, compact, template)?;
+            let page =
+                pages::site_page(generator, parsed_tag, &notes, &self.config)?;
+            pages.push((
+                directory.join(pages::file_name(&parsed_tag.name)),
+                page,
+            ));
+        }
+        fs::create_dir_all(directory).context(file(directory))?;
+        let mut paths = vec![];
+        let index = directory.join("_index.md");
+        if !index.exists() {
+            fs::write(&index, pages::site_index(generator))
+                .context(file(&index))?;
+            paths.push(index.display().to_string());
+        }
+        for (path, page) in &pages {
+            fs::write(path, page).context(file(path))?;
+            paths.push(path.display().to_string());
+        }
+        info!(
+            "{} release pages written to '{}'.",
+            pages.len(),
+            directory.display()
+        );
+        Ok(paths)
+    }
+
+    /// Returns the announcement of a single release as RFC 5322 message,
+    /// which can be piped into `sendmail -t`. The subject is taken from the
+    /// release header and the plain text body is wrapped, an HTML alternative
+    /// is added if `email_html` is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// journal.config.email_to = Some("team@example.com".to_owned());
+    /// if let Ok(message) = journal.announce("Unreleased", false, None) {
+    ///     println!("{}", message);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// If the release is not part of the parsing results or rendering failed.
+    pub fn announce(
+        &self,
+        release: &str,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<String, Error> {
+        let section = self.render_release(release, compact, template)?;
+        Ok(email::message(
+            &self.config,
+            release,
+            &section,
+            self.get_current_date(),
+        ))
+    }
+
+    /// Announces a single release by posting its rendered section to the
+    /// configured `webhook_url` in the `webhook_format`. Requires the
+    /// `network` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD").all(true));
+    /// journal.config.webhook_url =
+    ///     Some("https://hooks.slack.com/services/T00/B00/XXX".to_owned());
+    /// journal.notify("v1.0.0", true, None).ok();
+    /// ```
+    ///
+    /// # Errors
+    /// When no webhook is configured, the release is not part of the parsing
+    /// results or posting to the webhook failed.
+    #[cfg(feature = "network")]
+    pub fn notify(
+        &self,
+        release: &str,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = match self.config.webhook_url {
+            Some(ref url) => url,
+            None => bail!("No 'webhook_url' configured."),
+        };
+        let text = self.render_release(release, compact, template)?;
+        notify::post(url, &self.config.webhook_format, release, &text)?;
+        info!("Release '{}' announced via webhook.", release);
+        Ok(())
+    }
+
+    /// Returns the rendered notes of the latest release. This will be the
+    /// `Unreleased` section if `HEAD` is not tagged, otherwise the section of
+    /// the most recent tag which does not match the `tag_skip_pattern`. The
+    /// current parsing results will be replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::GitJournal;
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// if let Ok(notes) = journal.latest_release_notes("rc", true, None) {
+    ///     println!("{}", notes);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// If the parsing failed or no release could be found at all.
+    pub fn latest_release_notes(
+        &mut self,
+        tag_skip_pattern: &str,
+        compact: bool,
+        template: Option<&str>,
+    ) -> Result<String, Error> {
+        self.parser.result.clear();
+        self.parse_log(
+            &ParseOptions::new("HEAD").tag_skip_pattern(tag_skip_pattern),
+        )?;
+        let release = match self.parser.result.first() {
+            Some(parsed_tag) => parsed_tag.name.clone(),
+            None => bail!("No release found for the release notes."),
+        };
+        self.render_release(&release, compact, template)
+    }
+
+    /// Writes the resulting log once for every configured translation profile
+    /// (`translation_profiles`) without printing it to the terminal. The
+    /// language is inserted before the extension of the output file, e.g.
+    /// `CHANGELOG.de.md`. Returns the paths of the written files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{GitJournal, ParseOptions};
+    ///
+    /// let mut journal = GitJournal::new(".").unwrap();
+    /// journal.parse_log(&ParseOptions::new("HEAD"));
+    /// journal
+    ///     .print_translated_logs(false, None, "CHANGELOG.md")
+    ///     .expect("Could not write translated logs.");
+    /// ```
+    ///
+    /// # Errors
+    /// If some commit message could not be rendered or writing to one of the
+    /// output files failed.
+    pub fn print_translated_logs(
+        &self,
+        compact: bool,
+        template: Option<&str>,
+        output: &str,
+    ) -> Result<Vec<String>, Error> {
+        let used_template = self.get_template(template);
+        let mut written_files = vec![];
+        for (language, translations) in &self.config.translation_profiles {
+            let mut config = self.config.clone();
+            config.translations = translations.clone();
+            config.translate_cmd = config
+                .translate_cmd
+                .map(|command| command.replace("{language}", language));
+            let output_vec = self.parser.render(
+                &compact,
+                used_template.as_deref(),
+                &config,
+            )?;
+
+            let path = Self::get_translated_path(output, language);
+            self.write_output(&path, output_vec, true)?;
+            written_files.push(path);
+        }
+        Ok(written_files)
+    }
+
+    fn get_translated_path(output: &str, language: &str) -> String {
+        let path = Path::new(output);
+        let file_name = match (path.file_stem(), path.extension()) {
+            (Some(stem), Some(extension)) => format!(
+                "{}.{}.{}",
+                stem.to_string_lossy(),
+                language,
+                extension.to_string_lossy()
+            ),
+            _ => format!(
+                "{}.{}",
+                path.file_name().unwrap_or_default().to_string_lossy(),
+                language
+            ),
+        };
+        path.with_file_name(file_name)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn get_template(&self, template: Option<&str>) -> Option<String> {
+        match self.config.default_template {
+            Some(ref default_template_file) => match template {
+                None => {
+                    let mut default_template = PathBuf::from(&self.path);
+                    default_template.push(default_template_file);
+                    if default_template.exists() {
+                        info!(
+                            "Using default template '{}'.",
+                            default_template.display()
+                        );
+                        default_template.to_str().map(|t| t.to_owned())
+                    } else {
+                        warn!(
+                            "The default template '{}' does not exist.",
+                            default_template.display()
+                        );
+                        None
+                    }
+                }
+                Some(t) => Some(t.to_owned()),
+            },
+            None => template.map(|t| t.to_owned()),
+        }
+    }
+}
+
+/// Parses a single commit message with the given configuration, without the
+/// need for a repository. The parser never panics, invalid messages result in
+/// an error instead.
+///
+/// # Examples
+///
+/// ```
+/// use gitjournal::{parse_commit_message, Config};
+///
+/// let commit = parse_commit_message("Added some feature :ui:", &Config::new())
+///     .unwrap();
+/// assert_eq!(commit.summary.category, "Added");
+/// assert_eq!(commit.summary.tags, vec!["ui"]);
+/// ```
+///
+/// # Errors
+/// When the message does not follow the syntax of RFC0001.
+pub fn parse_commit_message(
+    message: &str,
+    config: &Config,
+) -> Result<ParsedCommit, Error> {
+    let parser = Parser {
+        config: config.clone(),
+        result: vec![],
+    };
+    parser.parse_commit_message(message, None)
+}
+
+/// Verifies a single commit message with the given configuration like
+/// `GitJournal::verify_message`, without the need for a repository. The
+/// `message_replacements` and the built-in rules are applied, where a
+/// `signoff` of "author" is always violated since there is no author.
+///
+/// # Examples
+///
+/// ```
+/// use gitjournal::{verify_commit_message, Config};
+///
+/// let config = Config::new();
+/// assert!(verify_commit_message("Added some feature", &config).is_ok());
+/// assert!(verify_commit_message("fixup! Added some feature", &config).is_ok());
+/// assert!(verify_commit_message("Some invalid message", &config).is_err());
+/// ```
+///
+/// # Errors
+/// When the commit message is not valid due to RFC0001 or one of the
+/// built-in rules.
+pub fn verify_commit_message(
+    message: &str,
+    config: &Config,
+) -> Result<(), Error> {
+    if rules::skip_fixup(message, config)? {
+        return Ok(());
+    }
+    let mut message = message.to_owned();
+    for (pattern, replacement) in &config.message_replacements {
+        let regex = regex::Regex::new(pattern)
+            .context(ErrorKind::Config("message_replacements".to_owned()))?;
+        message = regex
+            .replace_all(&message, replacement.as_str())
+            .into_owned();
+    }
+    let commit = parse_commit_message(&message, config)?;
+    rules::check(&commit, &message, config, None, &[])
+}
+
+/// Formats a commit message into its canonical form like
+/// `GitJournal::format_commit_message`, without the need for a repository.
+///
+/// # Examples
+///
+/// ```
+/// use gitjournal::{format_commit_message, Config};
+///
+/// assert_eq!(
+///     format_commit_message("Added   Some feature :ui:", &Config::new()),
+///     "[Added] some feature :ui:"
+/// );
+/// ```
+pub fn format_commit_message(message: &str, config: &Config) -> String {
+    let parser = Parser {
+        config: config.clone(),
+        result: vec![],
+    };
+    parser.format_commit_message(message)
+}
+
+#[cfg(all(test, feature = "git"))]
+mod tests {
+    use super::*;
+
+    use git2::{Signature, Time};
+    use std::{sync::Arc, thread};
+
+    fn create_test_repo(name: &str) -> (PathBuf, Repository) {
+        let mut path = env::temp_dir();
+        path.push(format!("git-journal-{}-{}", name, std::process::id()));
+        if path.exists() {
+            fs::remove_dir_all(&path).unwrap();
+        }
+        let repo = Repository::init(&path).unwrap();
+        (path, repo)
+    }
+
+    fn commit(repo: &Repository, message: &str, time: i64) -> Oid {
+        let signature =
+            Signature::new("Test", "test@example.com", &Time::new(time, 0))
+                .unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let parents = match repo.head() {
+            Ok(head) => vec![head.peel_to_commit().unwrap()],
+            Err(_) => vec![],
+        };
+        let parents = parents.iter().collect::<Vec<_>>();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+
+    fn tag(repo: &Repository, name: &str, oid: Oid, message: &str) {
+        let signature =
+            Signature::new("Test", "test@example.com", &Time::new(0, 0))
+                .unwrap();
+        let object = repo.find_object(oid, None).unwrap();
+        repo.tag(name, &object, &signature, message, false).unwrap();
+    }
+
+    fn create_tagged_test_repo(name: &str) -> PathBuf {
+        let (path, repo) = create_test_repo(name);
+        let c1 = commit(&repo, "Added file1", 1_000);
+        tag(&repo, "v1", c1, "Version 1");
+        commit(&repo, "Fixed file1", 2_000);
+        let c3 = commit(&repo, "Added file2", 3_000);
+        tag(&repo, "v2", c3, "Version 2");
+        commit(&repo, "Changed file2", 4_000);
+        path
+    }
+
+    #[test]
+    fn new() {
+        assert!(GitJournal::new(".").is_ok());
+        let res = GitJournal::new("/dev/null");
+        assert!(res.is_err());
+        if let Err(e) = res {
+            println!("{}", e);
+        }
+    }
+
+    #[test]
+    fn setup_succeed() {
+        let path = ".";
+        let journal = GitJournal::new(path);
+        assert!(journal.is_ok());
+        assert!(journal.unwrap().setup().is_ok());
+        assert!(GitJournal::new(path).is_ok());
+    }
+
+    #[test]
+    fn setup_failed() {
+        let journal = GitJournal::new("./tests/test_repo");
+        assert!(journal.is_ok());
+        let res = journal.unwrap().setup();
+        assert!(res.is_err());
+        if let Err(e) = res {
+            println!("{}", e);
+        }
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_success_1() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.verify("./tests/commit_messages/success_1").is_ok());
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_success_2() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.verify("./tests/commit_messages/success_2").is_ok());
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_success_3() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.verify("./tests/commit_messages/success_3").is_ok());
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_success_4() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.verify("./tests/commit_messages/success_4").is_ok());
+    }
+
+    fn verify_failure(path: &str) {
+        let journal = GitJournal::new(".").unwrap();
+        let res = journal.verify(path);
+        assert!(res.is_err());
+        if let Err(e) = res {
+            println!("{}", e);
+        }
+    }
+
+    #[test]
+    fn verify_custom_rules() {
+        struct MaxTags(usize);
+
+        impl Rule for MaxTags {
+            fn name(&self) -> &str {
+                "max-tags"
+            }
+
+            fn check(
+                &self,
+                commit: &ParsedCommit,
+                _: &str,
+                _: &Config,
+            ) -> Result<(), Error> {
+                if commit.summary.tags.len() > self.0 {
+                    bail!("More than {} tags", self.0);
+                }
+                Ok(())
+            }
+        }
+
+        let mut journal = GitJournal::new(".").unwrap();
+        journal.register_rule(Box::new(MaxTags(1)));
+        assert!(journal.verify_message("Added feature :a:").is_ok());
+        assert!(journal.verify_message("Invalid message").is_err());
+        let error =
+            journal.verify_message("Added feature :a: :b:").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The commit message violates the rules: max-tags"
+        );
+    }
+
+    #[test]
+    fn verify_signoff() {
+        let (path, repo) = create_test_repo("verify-signoff");
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Jane Doe").unwrap();
+        config.set_str("user.email", "jane@example.com").unwrap();
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.signoff = "author".to_owned();
+        if env::var("GIT_AUTHOR_NAME").is_err() {
+            assert_eq!(
+                journal.get_author().unwrap(),
+                "Jane Doe <jane@example.com>"
+            );
+        }
+        let author = journal.get_author().unwrap();
+        assert!(journal
+            .verify_message(&format!("Added a\n\nSigned-off-by: {}", author))
+            .is_ok());
+        assert!(journal
+            .verify_message("Added a\n\nSigned-off-by: Jo <jo@example.com>")
+            .is_err());
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn verify_range_signoff() {
+        let (path, repo) = create_test_repo("verify_range_signoff");
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Jane Doe").unwrap();
+        config.set_str("user.email", "jane@example.com").unwrap();
+        let base = commit(&repo, "Added file1", 1_000);
+        commit(
+            &repo,
+            "Added a\n\nSigned-off-by: Test <test@example.com>",
+            2_000,
+        );
+        let invalid = commit(
+            &repo,
+            "Added b\n\nSigned-off-by: Jane Doe <jane@example.com>",
+            3_000,
+        );
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.signoff = "author".to_owned();
+        let warnings =
+            journal.verify_range(&format!("{}..HEAD", base)).unwrap();
+        assert_eq!(warnings.len(), 1);
+        match warnings.iter().next() {
+            Some(Warning::InvalidCommit { oid, .. }) => {
+                assert_eq!(*oid, invalid)
+            }
+            _ => panic!("Expected an invalid commit."),
+        }
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn verify_commit_range() {
+        let (path, repo) = create_test_repo("verify_range");
+        let base = commit(&repo, "Added file1", 1_000);
+        fs::create_dir(path.join("src")).unwrap();
+        fs::write(path.join("src/main.rs"), "fn main() {}").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("src/main.rs")).unwrap();
+        index.write().unwrap();
+        let invalid = commit(&repo, "wip", 2_000);
+        commit(&repo, "Fixed file1", 3_000);
+        let journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let warnings =
+            journal.verify_range(&format!("{}..HEAD", base)).unwrap();
+        assert_eq!(warnings.len(), 1);
+        match warnings.iter().next() {
+            Some(Warning::InvalidCommit { oid, path, .. }) => {
+                assert_eq!(*oid, invalid);
+                assert_eq!(path.as_deref(), Some("src/main.rs"));
+            }
+            _ => panic!("Expected an invalid commit"),
+        }
+        assert!(annotations::github(&warnings).starts_with(&format!(
+            "::error file=src/main.rs,line=1,title=Invalid commit message \
+             {:.7}::",
+            invalid
+        )));
+        assert!(journal.verify_range("missing..HEAD").is_err());
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn verify_relaxed_branches() {
+        let (path, repo) = create_test_repo("relaxed_branches");
+        let oid = commit(&repo, "Added file1", 1_000);
+        let head = repo.find_commit(oid).unwrap();
+        repo.branch("wip/feature", &head, false).unwrap();
+        let message = path.join("COMMIT_EDITMSG");
+        fs::write(&message, "wip").unwrap();
+        let message = message.to_str().unwrap();
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.relaxed_branches = vec!["wip/*".to_owned()];
+        assert!(journal.verify(message).is_err());
+
+        repo.set_head("refs/heads/wip/feature").unwrap();
+        assert!(journal.verify(message).is_ok());
+        journal.config.relaxed_verification = "off".to_owned();
+        assert!(journal.verify(message).is_ok());
+        journal.config.relaxed_verification = "wrong".to_owned();
+        assert!(journal.verify(message).is_err());
+
+        repo.set_head_detached(oid).unwrap();
+        journal.config.relaxed_verification = "off".to_owned();
+        assert!(journal.verify(message).is_err());
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn verify_fixup_commits() {
+        let mut journal = GitJournal::new(".").unwrap();
+        let fixup = "fixup! Added the file\n";
+        assert!(journal.verify_message(fixup).is_ok());
+        assert!(journal.verify_message("squash! wip\n\nSome text").is_ok());
+        assert!(journal.verify_message("fixups are wrong").is_err());
+
+        journal.config.fixup_commits = "reject".to_owned();
+        assert!(journal.verify_message(fixup).is_err());
+        assert!(journal.verify_message("Added the file").is_ok());
+
+        journal.config.fixup_commits = "wrong".to_owned();
+        assert!(journal.verify_message(fixup).is_err());
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_failure_1() {
+        verify_failure("./tests/commit_messages/failure_1");
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_failure_2() {
+        verify_failure("./tests/commit_messages/failure_2");
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_failure_3() {
+        verify_failure("./tests/commit_messages/failure_3");
+    }
+
+    #[test]
+    fn verify_commit_msg_paragraph_failure_1() {
+        verify_failure("./tests/commit_messages/failure_4");
+    }
+
+    #[test]
+    fn verify_commit_msg_paragraph_failure_2() {
+        verify_failure("./tests/commit_messages/failure_5");
+    }
+
+    #[test]
+    fn verify_commit_msg_paragraph_failure_3() {
+        verify_failure("./tests/commit_messages/failure_6");
+    }
+
+    #[test]
+    fn verify_commit_msg_summary_failure_tag() {
+        let journal = GitJournal::new("./tests/test_repo2").unwrap();
+        assert!(journal.verify("./tests/commit_messages/success_1").is_err());
+        assert!(journal.verify("./tests/commit_messages/success_3").is_err());
+    }
+
+    #[test]
+    fn parse_and_print_log_1() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert_eq!(journal.tags.len(), 2);
+        assert_eq!(journal.parser.result.len(), 0);
+        assert_eq!(journal.config.show_prefix, false);
+        assert_eq!(journal.config.colored_output, true);
+        assert_eq!(journal.config.show_commit_hash, false);
+        assert_eq!(journal.config.excluded_commit_tags.len(), 0);
+        assert!(journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .is_ok());
+        assert_eq!(journal.parser.result.len(), journal.tags.len() + 1);
+        assert_eq!(journal.parser.result[0].commits.len(), 15);
+        assert_eq!(journal.parser.result[1].commits.len(), 1);
+        assert_eq!(journal.parser.result[2].commits.len(), 2);
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal
+            .print_log(
+                false,
+                Some("./tests/template.toml"),
+                Some("CHANGELOG.md")
+            )
+            .is_ok());
+        assert!(journal
+            .print_log(
+                true,
+                Some("./tests/template.toml"),
+                Some("CHANGELOG.md")
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn parse_and_print_log_2() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert!(journal.parse_log(&ParseOptions::new("HEAD")).is_ok());
+        assert_eq!(journal.parser.result.len(), 2);
+        assert_eq!(journal.parser.result[0].name, "Unreleased");
+        assert_eq!(journal.parser.result[1].name, "v2");
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal
+            .print_log(
+                false,
+                Some("./tests/template.toml"),
+                Some("CHANGELOG.md")
+            )
+            .is_ok());
+        assert!(journal
+            .print_log(
+                true,
+                Some("./tests/template.toml"),
+                Some("CHANGELOG.md")
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn parse_and_print_log_3() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert!(journal
+            .parse_log(&ParseOptions::new("HEAD").skip_unreleased(true))
+            .is_ok());
+        assert_eq!(journal.parser.result.len(), 1);
+        assert_eq!(journal.parser.result[0].name, "v2");
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal
+            .print_log(
+                false,
+                Some("./tests/template.toml"),
+                Some("CHANGELOG.md")
+            )
+            .is_ok());
+        assert!(journal
+            .print_log(
+                true,
+                Some("./tests/template.toml"),
+                Some("CHANGELOG.md")
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn parse_and_print_log_4() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert!(journal
+            .parse_log(
+                &ParseOptions::new("HEAD")
+                    .max_tags_count(2)
+                    .skip_unreleased(true)
+            )
+            .is_ok());
+        assert_eq!(journal.parser.result.len(), 2);
+        assert_eq!(journal.parser.result[0].name, "v2");
+        assert_eq!(journal.parser.result[1].name, "v1");
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal
+            .print_log(
+                false,
+                Some("./tests/template.toml"),
+                Some("CHANGELOG.md")
+            )
+            .is_ok());
+        assert!(journal
+            .print_log(
+                true,
+                Some("./tests/template.toml"),
+                Some("CHANGELOG.md")
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn parse_and_print_log_5() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert!(journal
+            .parse_log(&ParseOptions::new("v1..v2").all(true))
+            .is_ok());
+        assert_eq!(journal.parser.result.len(), 1);
+        assert_eq!(journal.parser.result[0].name, "v2");
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        assert!(journal
+            .print_log(
+                false,
+                Some("./tests/template.toml"),
+                Some("CHANGELOG.md")
+            )
+            .is_ok());
+        assert!(journal
+            .print_log(
+                true,
+                Some("./tests/template.toml"),
+                Some("CHANGELOG.md")
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn parse_and_print_log_6() {
+        let mut journal = GitJournal::new("./tests/test_repo2").unwrap();
+        assert!(journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .is_ok());
+        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+    }
+
+    #[test]
+    fn prepare_message_success_1() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.prepare("./tests/COMMIT_EDITMSG", None).is_ok());
+    }
+
+    #[test]
+    fn prepare_message_success_2() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal
+            .prepare("./tests/commit_messages/prepare_1", None)
+            .is_ok());
+    }
+
+    #[test]
+    fn prepare_message_success_3() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal
+            .prepare("./tests/commit_messages/prepare_2", None)
+            .is_ok());
+    }
+
+    #[test]
+    fn prepare_message_success_4() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal
+            .prepare("./tests/commit_messages/prepare_4", None)
+            .is_ok());
+    }
+
+    #[test]
+    fn prepare_template_variables() {
+        let (path, repo) = create_test_repo("prepare-template-variables");
+        let oid = commit(&repo, "Added initial commit", 0);
+        let head = repo.find_commit(oid).unwrap();
+        repo.branch("feature/ABC-42-login", &head, false).unwrap();
+        repo.set_head("refs/heads/feature/ABC-42-login").unwrap();
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.template_prefix =
+            "{ticket-from-branch} {unknown} ({branch})".to_owned();
+        assert_eq!(
+            journal.expand_template_prefix().unwrap(),
+            "ABC-42 {unknown} (feature/ABC-42-login)"
+        );
+
+        let message = path.join("COMMIT_EDITMSG");
+        fs::write(&message, "").unwrap();
+        journal.config.template_prefix = "{ticket-from-branch}".to_owned();
+        journal.prepare(message.to_str().unwrap(), None).unwrap();
+        let prepared = fs::read_to_string(&message).unwrap();
+        assert!(prepared.starts_with("ABC-42 Added ...\n"));
+
+        journal.config.ticket_pattern = "^feature/([a-z]+)".to_owned();
+        assert_eq!(journal.expand_template_prefix().unwrap(), "");
+        journal.config.ticket_pattern = "(".to_owned();
+        assert!(journal.expand_template_prefix().is_err());
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn prepare_message_failure_1() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.prepare("TEST", None).is_err());
+        assert!(journal.prepare("TEST", Some("message")).is_err());
+    }
+
+    #[test]
+    fn prepare_message_failure_2() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal
+            .prepare("./tests/commit_messages/prepare_3", Some("message"))
+            .is_err());
+    }
+
+    #[test]
+    fn install_git_hook() {
+        let journal = GitJournal::new(".").unwrap();
+        assert!(journal.install_git_hook("test", "echo 1\n").is_ok());
+        assert!(journal.install_git_hook("test", "echo 1\n").is_ok());
+        assert!(journal.install_git_hook("test", "echo 2\n").is_ok());
+    }
+
+    #[test]
+    fn generate_template_1() {
+        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+        assert!(journal.generate_template().is_ok());
+        assert!(journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .is_ok());
+        assert!(journal.generate_template().is_ok());
+    }
+
+    #[test]
+    fn print_and_render_release() {
+        let path = create_tagged_test_repo("release");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.changelog_header = Some("Header".to_owned());
+        assert!(journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .is_ok());
+        assert_eq!(journal.parser.result.len(), 3);
+        let notes = journal.render_release("v2", true, None).unwrap();
+        assert!(notes.starts_with("\n# v2 ("));
+        assert!(notes.contains("- [Added] file2\n- [Fixed] file1"));
+        assert!(!notes.contains("Header"));
+        assert!(!notes.contains("Unreleased"));
+        assert!(journal.print_release("v1", false, None, None).is_ok());
+        assert!(journal.render_release("v3", true, None).is_err());
+    }
+
+    #[test]
+    fn render_tag_titles() {
+        let path = create_tagged_test_repo("tag_titles");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert!(journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .is_ok());
+        let titles = journal
+            .releases()
+            .iter()
+            .map(|r| r.title.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, vec![None, Some("Version 2"), Some("Version 1")]);
+        let notes = journal.render_release("v2", true, None).unwrap();
+        assert!(notes.starts_with("\n# v2 (1970-01-01):"));
+
+        journal.config.show_tag_titles = true;
+        let notes = journal.render_release("v2", true, None).unwrap();
+        assert!(notes.starts_with("\n# v2 — Version 2 (1970-01-01):"));
+    }
+
+    #[test]
+    fn cargo_version_and_pre_release() {
+        let path = create_tagged_test_repo("cargo_version");
+        let manifest = path.join("Cargo.toml");
+        fs::write(&manifest, "[package]\nname = \"a\"\nversion = \"3.0.0\"\n")
+            .unwrap();
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(journal.cargo_version().unwrap(), "3.0.0");
+        journal.config.cargo_version = Some("v{version}".to_owned());
+        journal.parse_log(&ParseOptions::new("HEAD")).unwrap();
+        assert_eq!(journal.releases()[0].name, "v3.0.0");
+
+        let output = path.join("CHANGELOG.md");
+        let output = output.to_str().unwrap();
+        let changelog = journal.pre_release(None, output, true).unwrap();
+        assert!(changelog.starts_with("\n# v3.0.0 ("));
+        assert!(!Path::new(output).exists());
+        journal.pre_release(Some("3.1.0"), output, false).unwrap();
+        let written = fs::read_to_string(output).unwrap();
+        assert!(written.starts_with("\n# v3.1.0 ("));
+        assert!(written.contains("\n# v2 ("));
+        assert!(journal.pre_release(Some("2"), output, false).is_err());
+
+        fs::write(
+            &manifest,
+            "[package]\nversion.workspace = true\n\
+             [workspace.package]\nversion = \"2.0.0\"\n",
+        )
+        .unwrap();
+        assert_eq!(journal.cargo_version().unwrap(), "2.0.0");
+        journal.config.cargo_version = Some("{version}".to_owned());
+        assert!(journal.pre_release(None, output, false).is_ok());
 
-        let mut header_footer_map = Map::new();
-        header_footer_map
-            .insert(parser::TOML_ONCE_KEY.to_owned(), Value::Boolean(false));
-        header_footer_map.insert(
-            parser::TOML_TEXT_KEY.to_owned(),
-            Value::String(String::new()),
+        let repo = Repository::open(&path).unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+        tag(&repo, "2.0.0", head, "Version 2.0.0");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.cargo_version = Some("{version}".to_owned());
+        assert!(journal.pre_release(None, output, false).is_err());
+    }
+
+    #[test]
+    fn pre_release_fragments() {
+        let path = create_tagged_test_repo("fragments");
+        let fragments = path.join("changelog.d");
+        fs::create_dir_all(&fragments).unwrap();
+        fs::write(fragments.join("12.fixed.md"), "the login :ui:\n").unwrap();
+        fs::write(fragments.join("13.md"), "Added the docs\n").unwrap();
+        fs::write(fragments.join(".gitkeep"), "").unwrap();
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.fragments_dir = Some("changelog.d".to_owned());
+        journal.parse_log(&ParseOptions::new("HEAD")).unwrap();
+        let unreleased = &journal.releases()[0];
+        assert!(unreleased.oid.is_none());
+        assert_eq!(unreleased.commits[0].summary.category, "Fixed");
+        assert_eq!(unreleased.commits[0].summary.tags, vec!["ui"]);
+        assert_eq!(unreleased.commits[1].summary.text, "the docs");
+
+        let output = path.join("CHANGELOG.md");
+        let output = output.to_str().unwrap();
+        journal.pre_release(Some("3.0.0"), output, true).unwrap();
+        assert!(fragments.join("12.fixed.md").exists());
+        let changelog =
+            journal.pre_release(Some("3.0.0"), output, false).unwrap();
+        assert!(changelog.contains("the login"));
+        assert!(!fragments.join("12.fixed.md").exists());
+        assert!(!fragments.join("13.md").exists());
+        assert!(fragments.join(".gitkeep").exists());
+
+        fs::write(fragments.join("14.md"), "Invalid fragment").unwrap();
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.fragments_dir = Some("changelog.d".to_owned());
+        assert!(journal.parse_log(&ParseOptions::new("HEAD")).is_err());
+    }
+
+    #[test]
+    fn cached_log() {
+        let (path, repo) = create_test_repo("cached_log");
+        commit(&repo, "Added file1", 1_000);
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.cache_dir = Some("cache".to_owned());
+        let options = ParseOptions::new("HEAD");
+        let log = journal.render_cached(&options, true, None).unwrap();
+        assert!(log.contains("[Added] file1"));
+
+        // The cached log is reused for unchanged inputs
+        let cached = fs::read_dir(path.join("cache"))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        fs::write(&cached, r#"{"log":"cached","output":[],"warnings":[]}"#)
+            .unwrap();
+        assert_eq!(
+            journal.render_cached(&options, true, None).unwrap(),
+            "cached"
         );
-        toml_map.insert(
-            parser::TOML_HEADER_KEY.to_owned(),
-            Value::Table(header_footer_map.clone()),
+        let log = journal.render_cached(&options, false, None).unwrap();
+        assert!(log.contains("[Added] file1"));
+
+        // New commits and configuration changes invalidate the cache
+        commit(&repo, "Fixed file1", 2_000);
+        let log = journal.render_cached(&options, true, None).unwrap();
+        assert!(log.contains("[Fixed] file1"));
+        journal.config.category_delimiters =
+            vec!["<".to_owned(), ">".to_owned()];
+        let log = journal.render_cached(&options, true, None).unwrap();
+        assert!(log.contains("<Fixed> file1"));
+
+        // As well as registering hooks
+        journal.add_entry_hook(|commit| {
+            commit.summary.text = commit.summary.text.to_uppercase()
+        });
+        let log = journal.render_cached(&options, true, None).unwrap();
+        assert!(log.contains("<Fixed> FILE1"));
+
+        // The warnings and colors of the output are cached as well
+        commit(&repo, "Invalid message", 3_000);
+        journal.config.colored_output = true;
+        journal.render_cached(&options, true, None).unwrap();
+        let newest = fs::read_dir(path.join("cache"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .max_by_key(|path| fs::metadata(path).unwrap().modified().unwrap())
+            .unwrap();
+        let cached: CachedLog =
+            serde_json::from_str(&fs::read_to_string(newest).unwrap()).unwrap();
+        assert_eq!(cached.warnings.len(), 1);
+        assert!(cached.warnings[0].contains("Skipping commit"));
+        assert!(cached.output.contains(&output::Segment::Reset));
+
+        // Only the newest logs are kept, where other files are ignored
+        fs::write(path.join("cache").join("other.json"), "").unwrap();
+        for compact in &[true, false] {
+            for index in 0..CACHED_LOGS {
+                journal.config.date_format = format!("%Y {}", index);
+                journal.render_cached(&options, *compact, None).unwrap();
+            }
+        }
+        assert_eq!(
+            fs::read_dir(path.join("cache")).unwrap().count(),
+            CACHED_LOGS + 1
         );
-        toml_map.insert(
-            parser::TOML_FOOTER_KEY.to_owned(),
-            Value::Table(header_footer_map),
+    }
+
+    #[test]
+    fn reproducible_output() {
+        let path = create_tagged_test_repo("reproducible");
+        let render = || {
+            let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+            journal.config.reproducible = true;
+            journal.config.timezone = "local".to_owned();
+            journal.config.date_format = "%Y-%m-%d %H:%M:%S".to_owned();
+            journal.parse_log(&ParseOptions::new("HEAD")).unwrap();
+            String::from_utf8(
+                journal
+                    .parser
+                    .render(&false, None, &journal.config)
+                    .unwrap(),
+            )
+            .unwrap()
+        };
+        let output = render();
+        if env::var("SOURCE_DATE_EPOCH").is_err() {
+            assert!(output.contains("# Unreleased (1970-01-01 01:06:40):"));
+        }
+        assert_eq!(output, render());
+
+        // The date is taken from `SOURCE_DATE_EPOCH` if it is valid
+        let journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let date = |epoch: &str| journal.get_source_date(Some(epoch.into()));
+        assert_eq!(date(" 86400\n"), Utc.timestamp(86_400, 0));
+        assert_eq!(date("invalid"), Utc.timestamp(4_000, 0));
+        assert_eq!(journal.get_source_date(None), Utc.timestamp(4_000, 0));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signed_output() {
+        use std::os::unix::prelude::PermissionsExt;
+        let path = create_tagged_test_repo("signed_output");
+        let gpg = path.join("fake-gpg");
+        fs::write(
+            &gpg,
+            "#!/bin/sh\necho \"-----BEGIN PGP SIGNATURE-----\"\n\
+             echo \"$*\"\nwc -c | tr -d ' '\n\
+             echo \"-----END PGP SIGNATURE-----\"\n",
+        )
+        .unwrap();
+        fs::set_permissions(&gpg, fs::Permissions::from_mode(0o755)).unwrap();
+        Repository::open(&path)
+            .unwrap()
+            .config()
+            .unwrap()
+            .set_str("gpg.program", gpg.to_str().unwrap())
+            .unwrap();
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.colored_output = false;
+        journal.config.signing_key = Some("ABCD".to_owned());
+        journal.parse_log(&ParseOptions::new("HEAD")).unwrap();
+        let output = path.join("CHANGELOG.md");
+        let output = output.to_str().unwrap();
+
+        journal.config.sign_output = "detached".to_owned();
+        journal.print_log(true, None, Some(output)).unwrap();
+        let length = fs::read(output).unwrap().len();
+        assert_eq!(
+            fs::read_to_string(format!("{}.asc", output)).unwrap(),
+            format!(
+                "-----BEGIN PGP SIGNATURE-----\n--batch --armor --detach-sign \
+                 --local-user ABCD\n{}\n-----END PGP SIGNATURE-----\n",
+                length
+            )
         );
 
-        let toml = Value::Table(toml_map);
+        fs::remove_file(output).unwrap();
+        journal.config.sign_output = "trailer".to_owned();
+        journal.print_log(true, None, Some(output)).unwrap();
+        let written = fs::read_to_string(output).unwrap();
+        assert!(written.ends_with(&format!(
+            "\n{}\n-----END PGP SIGNATURE-----\n-->\n",
+            length
+        )));
+        assert_eq!(written.find("\n<!--\n").unwrap(), length);
 
-        // Write toml to file
-        let mut path_buf = PathBuf::from(&self.path);
-        path_buf.push("template.toml");
-        let toml_string = toml::to_string(&toml)?;
-        let mut toml_file = File::create(&path_buf)?;
-        toml_file.write_all(toml_string.as_bytes())?;
+        // Appended outputs are signed as a whole including the former trailer
+        journal.print_log(true, None, Some(output)).unwrap();
+        let appended = fs::read_to_string(output).unwrap();
+        let signed = appended.rfind("\n<!--\n").unwrap();
+        assert_eq!(signed, written.len() + length);
+        assert!(appended.ends_with(&format!(
+            "\n{}\n-----END PGP SIGNATURE-----\n-->\n",
+            signed
+        )));
 
-        info!("Template written to '{}'", path_buf.display());
-        Ok(())
+        // Releases and other outputs are signed like the log
+        fs::remove_file(output).unwrap();
+        journal.config.sign_output = "detached".to_owned();
+        journal
+            .print_release("Unreleased", true, None, Some(output))
+            .unwrap();
+        assert!(fs::read_to_string(format!("{}.asc", output))
+            .unwrap()
+            .contains(&format!("\n{}\n", fs::read(output).unwrap().len())));
+        journal.write_output(output, b"[]".to_vec(), false).unwrap();
+        assert_eq!(fs::read_to_string(output).unwrap(), "[]");
+        assert!(fs::read_to_string(format!("{}.asc", output))
+            .unwrap()
+            .contains("\n2\n"));
+
+        // Structured exports are signed detached instead of via a trailer
+        journal.config.sign_output = "trailer".to_owned();
+        let export = path.join("CHANGELOG.json");
+        let export = export.to_str().unwrap();
+        journal.write_export("json", export).unwrap();
+        let exported = fs::read_to_string(export).unwrap();
+        assert!(serde_json::from_str::<serde_json::Value>(&exported).is_ok());
+        assert!(fs::read_to_string(format!("{}.asc", export))
+            .unwrap()
+            .contains(&format!("\n{}\n", exported.len())));
+        journal.write_output(output, b"[]".to_vec(), false).unwrap();
+        assert!(fs::read_to_string(output)
+            .unwrap()
+            .starts_with("[]\n<!--\n-----BEGIN PGP SIGNATURE-----"));
+
+        // Cached logs are appended and signed like the log
+        journal.config.cache_dir = Some("cache".to_owned());
+        journal.config.sign_output = "trailer".to_owned();
+        fs::write(output, "existing\n").unwrap();
+        let options = ParseOptions::new("HEAD");
+        for _ in 0..2 {
+            journal
+                .print_cached(&options, true, None, Some(output))
+                .unwrap();
+        }
+        let written = fs::read_to_string(output).unwrap();
+        assert!(written.starts_with("existing\n"));
+        assert_eq!(written.matches("# v2").count(), 2);
+        let signed = written.rfind("\n<!--\n").unwrap();
+        assert!(written.ends_with(&format!(
+            "\n{}\n-----END PGP SIGNATURE-----\n-->\n",
+            signed
+        )));
+
+        journal.config.sign_output = "inline".to_owned();
+        assert!(journal.print_log(true, None, Some(output)).is_err());
     }
 
-    /// Prints the resulting log in a short or detailed variant. Will use the
-    /// template as an output formatter if provided.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use gitjournal::GitJournal;
-    ///
-    /// let mut journal = GitJournal::new(".").unwrap();
-    /// journal.parse_log("HEAD", "rc", &1, &false, &false, None);
-    /// journal
-    ///     .print_log(true, None, None)
-    ///     .expect("Could not print short log.");
-    /// journal
-    ///     .print_log(false, None, None)
-    ///     .expect("Could not print detailed log.");
-    /// ```
-    ///
-    /// # Errors
-    /// If some commit message could not be print.
-    pub fn print_log(
-        &self,
-        compact: bool,
-        template: Option<&str>,
-        output: Option<&str>,
-    ) -> Result<(), Error> {
-        // Choose the template
-        let mut default_template = PathBuf::from(&self.path);
-        let used_template = match self.config.default_template {
-            Some(ref default_template_file) => {
-                default_template.push(default_template_file);
-
-                match template {
-                    None => {
-                        if default_template.exists() {
-                            info!(
-                                "Using default template '{}'.",
-                                default_template.display()
-                            );
-                            default_template.to_str()
-                        } else {
-                            warn!(
-                                "The default template '{}' does not exist.",
-                                default_template.display()
-                            );
-                            None
-                        }
-                    }
-                    Some(t) => Some(t),
+    #[test]
+    fn github_outputs() {
+        let path = create_tagged_test_repo("github_outputs");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .unwrap();
+        let output = path.join("output");
+        let summary = path.join("summary");
+        journal
+            .write_github_outputs_with(Some("v2"), true, None, |key| {
+                match key {
+                    "GITHUB_OUTPUT" => Some(&output),
+                    "GITHUB_STEP_SUMMARY" => Some(&summary),
+                    _ => None,
                 }
-            }
-            None => template,
-        };
+                .map(|path| path.display().to_string())
+            })
+            .unwrap();
 
-        // Print the log
-        let output_vec = self.parser.print(&compact, used_template)?;
+        let notes = journal.render_release("v2", true, None).unwrap();
+        let output = fs::read_to_string(output).unwrap();
+        assert!(output.starts_with("release<<ghadelimiter\nv2\nghadelimiter\n"));
+        assert!(output.contains(&format!(
+            "notes<<ghadelimiter\n{}\nghadelimiter\n",
+            notes.trim()
+        )));
+        assert_eq!(
+            fs::read_to_string(summary).unwrap(),
+            format!("{}\n", notes.trim())
+        );
+    }
 
-        // Print the log to the file if necessary
-        if let Some(output) = output {
-            let mut output_file =
-                OpenOptions::new().create(true).append(true).open(output)?;
-            output_file.write_all(&output_vec)?;
-            info!("Output written to '{}'.", output);
-        }
+    #[test]
+    fn inject_release_notes() {
+        let path = create_tagged_test_repo("inject_notes");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal
+            .parse_log(&ParseOptions::new("HEAD").skip_unreleased(true))
+            .unwrap();
+        let readme = path.join("README.md");
+        fs::write(
+            &readme,
+            "# Project\n<!-- journal:start -->\n<!-- journal:end -->\nEnd\n",
+        )
+        .unwrap();
+        let readme = readme.to_str().unwrap();
+        journal
+            .inject_release_notes(readme, None, true, None)
+            .unwrap();
+        let notes = journal.render_release("v2", true, None).unwrap();
+        assert_eq!(
+            fs::read_to_string(readme).unwrap(),
+            format!(
+                "# Project\n<!-- journal:start -->\n{}\n\
+                 <!-- journal:end -->\nEnd\n",
+                notes.trim()
+            )
+        );
 
-        Ok(())
+        fs::write(readme, "# Project\n").unwrap();
+        assert!(journal
+            .inject_release_notes(readme, Some("v1"), true, None)
+            .is_err());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn mdbook_pages() {
+        let path = create_tagged_test_repo("mdbook_pages");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .unwrap();
+        let directory = path.join("src/changelog");
+        let paths = journal
+            .write_mdbook(directory.to_str().unwrap(), true, None)
+            .unwrap();
+        assert_eq!(paths.len(), 4);
+        assert_eq!(
+            fs::read_to_string(directory.join("SUMMARY.md")).unwrap(),
+            "- [Unreleased](changelog/Unreleased.md)\n\
+             - [v2](changelog/v2.md)\n\
+             - [v1](changelog/v1.md)\n"
+        );
+        let page = fs::read_to_string(directory.join("v1.md")).unwrap();
+        let notes = journal.render_release("v1", true, None).unwrap();
+        assert_eq!(page, notes.trim_start());
+    }
 
     #[test]
-    fn new() {
-        assert!(GitJournal::new(".").is_ok());
-        let res = GitJournal::new("/dev/null");
-        assert!(res.is_err());
-        if let Err(e) = res {
-            println!("{}", e);
-        }
+    fn site_pages() {
+        let path = create_tagged_test_repo("site_pages");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .unwrap();
+        let directory = path.join("content/releases");
+        let directory = directory.to_str().unwrap();
+        assert!(journal.write_site(directory, "jekyll", true, None).is_err());
+        let paths = journal.write_site(directory, "zola", true, None).unwrap();
+        assert_eq!(paths.len(), 4);
+        let index = path.join("content/releases/_index.md");
+        assert!(fs::read_to_string(&index).unwrap().contains("sort_by"));
+        let page = fs::read_to_string(path.join("content/releases/v1.md"));
+        assert!(page.unwrap().starts_with("+++\ndate = \"1970-01-01"));
+
+        fs::write(&index, "+++\ntitle = \"Changes\"\n+++\n").unwrap();
+        let paths = journal.write_site(directory, "hugo", true, None).unwrap();
+        assert_eq!(paths.len(), 3);
+        assert!(fs::read_to_string(&index).unwrap().contains("Changes"));
     }
 
     #[test]
-    fn setup_succeed() {
-        let path = ".";
-        let journal = GitJournal::new(path);
-        assert!(journal.is_ok());
-        assert!(journal.unwrap().setup().is_ok());
-        assert!(GitJournal::new(path).is_ok());
+    fn release_highlights() {
+        let path = create_tagged_test_repo("release_highlights");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .unwrap();
+        assert_eq!(
+            journal.highlights(Some("v2")).unwrap(),
+            "v2: Added file2, Fixed file1"
+        );
+        assert_eq!(
+            journal.highlights(None).unwrap(),
+            "Unreleased: Changed file2"
+        );
+        assert!(journal.highlights(Some("v3")).is_err());
     }
 
     #[test]
-    fn setup_failed() {
-        let journal = GitJournal::new("./tests/test_repo");
-        assert!(journal.is_ok());
-        let res = journal.unwrap().setup();
-        assert!(res.is_err());
-        if let Err(e) = res {
-            println!("{}", e);
+    fn suggest_and_bump_version() {
+        let path = create_tagged_test_repo("bump_version");
+        fs::create_dir_all(path.join("crates/b")).unwrap();
+        fs::write(
+            path.join("Cargo.toml"),
+            "[package]\nname = \"a\"\nversion = \"1.2.3\"\n\n\
+             [workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            path.join("crates/b/Cargo.toml"),
+            "[package]\nname = \"b\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let current = Version::new(1, 2, 3);
+        assert_eq!(journal.suggest_version(&current), current);
+        assert!(journal.bump_cargo_version(false).is_err());
+
+        // The unreleased commit "Changed file2" is a patch
+        journal.parse_log(&ParseOptions::new("HEAD")).unwrap();
+        assert_eq!(journal.suggest_version(&current), Version::new(1, 2, 4));
+        journal.config.minor_categories = vec!["Changed".to_owned()];
+        assert_eq!(journal.suggest_version(&current), Version::new(1, 3, 0));
+        journal.config.major_categories = vec!["Changed".to_owned()];
+        assert_eq!(journal.suggest_version(&current), Version::new(2, 0, 0));
+        assert_eq!(
+            journal.suggest_version(&Version::new(0, 2, 3)),
+            Version::new(0, 3, 0)
+        );
+
+        let diff = journal.bump_cargo_version(false).unwrap();
+        assert_eq!(
+            diff,
+            "--- a/Cargo.toml\n+++ b/Cargo.toml\n@@ -3 +3 @@\n\
+             -version = \"1.2.3\"\n+version = \"2.0.0\"\n\
+             --- a/crates/b/Cargo.toml\n+++ b/crates/b/Cargo.toml\n\
+             @@ -3 +3 @@\n-version = \"0.1.0\"\n+version = \"0.2.0\"\n"
+        );
+        assert_eq!(journal.cargo_version().unwrap(), "1.2.3");
+        assert_eq!(journal.bump_cargo_version(true).unwrap(), diff);
+        assert_eq!(journal.cargo_version().unwrap(), "2.0.0");
+    }
+
+    #[test]
+    fn bump_member_versions() {
+        let path = create_tagged_test_repo("bump_member_versions");
+        for member in &["b", "c", "d"] {
+            fs::create_dir_all(path.join(member)).unwrap();
         }
+        fs::write(
+            path.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"b\", \"c\", \"d\"]\n\n\
+             [workspace.package]\nversion = \"1.2.3\"\n",
+        )
+        .unwrap();
+        fs::write(
+            path.join("b/Cargo.toml"),
+            "[package]\nname = \"b\"\nversion = \"4.0.1\"\n",
+        )
+        .unwrap();
+        fs::write(
+            path.join("c/Cargo.toml"),
+            "[package]\nname = \"c\"\nversion.workspace = true\n",
+        )
+        .unwrap();
+        fs::write(
+            path.join("d/Cargo.toml"),
+            "[package]\nname = \"d\"\nversion = \"1.2.3\"\n",
+        )
+        .unwrap();
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.parse_log(&ParseOptions::new("HEAD")).unwrap();
+
+        // Every member is bumped from its own version
+        journal.bump_cargo_version(true).unwrap();
+        let read = |name: &str| fs::read_to_string(path.join(name)).unwrap();
+        assert!(read("Cargo.toml").ends_with("version = \"1.2.4\"\n"));
+        assert!(read("b/Cargo.toml").ends_with("version = \"4.0.2\"\n"));
+        assert!(read("c/Cargo.toml").ends_with("version.workspace = true\n"));
+        assert!(read("d/Cargo.toml").ends_with("version = \"1.2.4\"\n"));
     }
 
     #[test]
-    fn verify_commit_msg_summary_success_1() {
-        let journal = GitJournal::new(".").unwrap();
-        assert!(journal.verify("./tests/commit_messages/success_1").is_ok());
+    fn render_empty_section_placeholder() {
+        let path = create_tagged_test_repo("empty_section");
+        let template = path.join("template.toml");
+        fs::write(
+            &template,
+            "[[tag]]\ntag = \"default\"\nname = \"Default\"\n\n\
+             [[tag]]\ntag = \"feature\"\nname = \"Features\"\n",
+        )
+        .unwrap();
+        let template = template.to_str();
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert!(journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .is_ok());
+        let notes = journal.render_release("v2", true, template).unwrap();
+        assert!(notes.contains("# Default\n- [Added] file2"));
+        assert!(!notes.contains("Features"));
+
+        journal.config.empty_section_placeholder = Some("None.".to_owned());
+        let notes = journal.render_release("v2", true, template).unwrap();
+        assert!(notes.contains("# Default\n- [Added] file2"));
+        assert!(notes.contains("\n## Features\nNone.\n"));
     }
 
     #[test]
-    fn verify_commit_msg_summary_success_2() {
-        let journal = GitJournal::new(".").unwrap();
-        assert!(journal.verify("./tests/commit_messages/success_2").is_ok());
+    fn latest_release_notes() {
+        let path = create_tagged_test_repo("latest_release");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let notes = journal.latest_release_notes("rc", true, None).unwrap();
+        assert!(notes.starts_with("\n# Unreleased ("));
+        assert!(notes.contains("- [Changed] file2"));
+
+        // Tag HEAD, which makes the tag the latest release
+        let repo = Repository::open(&path).unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+        tag(&repo, "v3", head, "Version 3");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let notes = journal.latest_release_notes("rc", true, None).unwrap();
+        assert!(notes.starts_with("\n# v3 ("));
+        assert!(notes.contains("- [Changed] file2"));
+        assert!(!notes.contains("Added"));
     }
 
     #[test]
-    fn verify_commit_msg_summary_success_3() {
-        let journal = GitJournal::new(".").unwrap();
-        assert!(journal.verify("./tests/commit_messages/success_3").is_ok());
+    fn watch() {
+        let path = create_tagged_test_repo("watch");
+        let repo = Repository::open(&path).unwrap();
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let options = ParseOptions::new("HEAD").max_tags_count(0);
+        let mut counts = vec![];
+        assert!(journal
+            .watch(&options, Duration::from_millis(10), |unreleased| {
+                counts.push(unreleased.map(|u| u.commits.len()));
+                match counts.len() {
+                    1 => {
+                        commit(&repo, "Added file3", 5000);
+                        true
+                    }
+                    2 => {
+                        let head = repo.head().unwrap().target().unwrap();
+                        tag(&repo, "v3", head, "Version 3");
+                        true
+                    }
+                    _ => false,
+                }
+            })
+            .is_ok());
+        assert_eq!(counts, vec![Some(1), Some(2), None]);
+        assert_eq!(journal.parser.result[0].name, "v3");
+        assert_eq!(journal.parser.result[0].commits.len(), 2);
     }
 
     #[test]
-    fn verify_commit_msg_summary_success_4() {
-        let journal = GitJournal::new(".").unwrap();
-        assert!(journal.verify("./tests/commit_messages/success_4").is_ok());
+    fn watch_appended_commits() {
+        let path = create_tagged_test_repo("watch_appended");
+        let repo = Repository::open(&path).unwrap();
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let options = ParseOptions::new("HEAD");
+        journal.parse_log(&options).unwrap();
+        let texts = |journal: &GitJournal| {
+            journal
+                .parser
+                .result
+                .iter()
+                .map(|tag| tag.commits.iter().map(|c| c.summary.text.clone()))
+                .map(Iterator::collect::<Vec<_>>)
+                .collect::<Vec<_>>()
+        };
+
+        // Only the new commits are parsed and merged into the results
+        let state = journal.get_refs_state().unwrap();
+        commit(&repo, "Added file3", 5_000);
+        commit(&repo, "Fixed file3", 6_000);
+        let new_state = journal.get_refs_state().unwrap();
+        assert!(journal
+            .parse_appended(&state, &new_state, &options)
+            .unwrap());
+        let mut parsed = GitJournal::new(path.to_str().unwrap()).unwrap();
+        parsed.parse_log(&options).unwrap();
+        assert_eq!(texts(&journal), texts(&parsed));
+        assert_eq!(texts(&journal)[0], vec!["file3", "file3", "file2"]);
+
+        // Rewritten histories and new tags need a complete parsing
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        head.amend(Some("HEAD"), None, None, None, Some("Fixed it"), None)
+            .unwrap();
+        let amended = journal.get_refs_state().unwrap();
+        assert!(!journal
+            .parse_appended(&new_state, &amended, &options)
+            .unwrap());
+        let head = repo.head().unwrap().target().unwrap();
+        tag(&repo, "v3", head, "Version 3");
+        let tagged = journal.get_refs_state().unwrap();
+        assert!(!journal.parse_appended(&amended, &tagged, &options).unwrap());
+        fs::remove_dir_all(path).unwrap();
     }
 
-    fn verify_failure(path: &str) {
-        let journal = GitJournal::new(".").unwrap();
-        let res = journal.verify(path);
-        assert!(res.is_err());
-        if let Err(e) = res {
-            println!("{}", e);
+    #[test]
+    fn parsed_journal() {
+        let path = create_tagged_test_repo("parsed_journal");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert!(journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .is_ok());
+        let parsed = Arc::new(journal.parsed());
+        journal.parser.result.clear();
+
+        let handles = (0..4)
+            .map(|_| {
+                let parsed = parsed.clone();
+                thread::spawn(move || parsed.render_release("v2", true, None))
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            let notes = handle.join().unwrap().unwrap();
+            assert!(notes.contains("- [Added] file2\n- [Fixed] file1"));
         }
+        assert_eq!(parsed.releases().len(), 3);
+        assert!(parsed.render(false, None).unwrap().contains("# v1 ("));
+        assert!(parsed.render_release("v3", true, None).is_err());
     }
 
     #[test]
-    fn verify_commit_msg_summary_failure_1() {
-        verify_failure("./tests/commit_messages/failure_1");
+    fn parse_log_between_tags() {
+        let path = create_tagged_test_repo("between_tags");
+        let repo = Repository::open(&path).unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+        let object = repo.find_object(head, None).unwrap();
+        repo.tag_lightweight("v3", &object, false).unwrap();
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert!(journal.resolve_tag("v1").is_ok());
+        assert_eq!(journal.resolve_tag("v3^{}").unwrap(), head);
+        assert!(journal.resolve_tag("v4").is_err());
+        assert!(journal
+            .parse_log_between_tags("v1", Some("v3"), &ParseOptions::default())
+            .is_ok());
+        assert_eq!(journal.parser.result.len(), 2);
+        assert_eq!(journal.parser.result[0].name, "v3");
+        assert_eq!(journal.parser.result[0].commits.len(), 1);
+        assert_eq!(journal.parser.result[1].name, "v2");
+        assert_eq!(journal.parser.result[1].commits.len(), 2);
     }
 
     #[test]
-    fn verify_commit_msg_summary_failure_2() {
-        verify_failure("./tests/commit_messages/failure_2");
+    fn grammar_help() {
+        let mut journal = GitJournal::new(".").unwrap();
+        journal.config.categories = vec!["Feature".to_owned()];
+        journal.config.category_delimiters =
+            vec!["<".to_owned(), ">".to_owned()];
+        let help = journal.grammar_help();
+        assert!(help.contains("Categories: <Feature> "));
+        assert!(help.contains("  JIRA-1234 <Feature> the fancy thing :doc:"));
+
+        journal.config.default_template =
+            Some("tests/template.toml".to_owned());
+        assert!(journal.grammar_help().contains("Tags (optional): :tag1:"));
     }
 
     #[test]
-    fn verify_commit_msg_summary_failure_3() {
-        verify_failure("./tests/commit_messages/failure_3");
+    fn generate_rebase_todo() {
+        let (path, repo) = create_test_repo("rebase_todo");
+        commit(&repo, "Added file1", 1_000);
+        let c2 = commit(&repo, "fix(parser): file1 parsing", 2_000);
+        commit(&repo, "Improved file1", 3_000);
+        commit(&repo, "update the readme", 4_000);
+
+        let journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let todo = journal.generate_rebase_todo("HEAD~3..HEAD").unwrap();
+        let lines = todo.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[0],
+            format!("reword {:.7} fix(parser): file1 parsing", c2)
+        );
+        assert_eq!(lines[1], "# Suggested: Fixed file1 parsing");
+        assert!(lines[2].starts_with("pick "));
+        assert!(lines[3].starts_with("reword "));
+        assert_eq!(lines[4], "# Suggested: Changed the readme");
+        assert_eq!(journal.suggest_summary("wip"), "Changed wip");
+        assert!(journal.generate_rebase_todo("wrong..HEAD").is_err());
     }
 
     #[test]
-    fn verify_commit_msg_paragraph_failure_1() {
-        verify_failure("./tests/commit_messages/failure_4");
+    #[cfg(feature = "yaml")]
+    fn export_yaml() {
+        let path = create_tagged_test_repo("export_yaml");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert!(journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .is_ok());
+        let yaml = journal.export("yaml").unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(value["format_version"].as_u64(), Some(1));
+        let value = &value["releases"];
+        assert_eq!(value[0]["name"].as_str(), Some("Unreleased"));
+        assert_eq!(value[1]["name"].as_str(), Some("v2"));
+        assert_eq!(
+            value[1]["commits"][0]["summary"]["text"].as_str(),
+            Some("file2")
+        );
+        assert_eq!(value[2]["oid"].as_str().map(|o| o.len()), Some(40));
+        assert!(value[0].get("message_ids").is_none());
+        assert!(journal.export("wrong").is_err());
     }
 
     #[test]
-    fn verify_commit_msg_paragraph_failure_2() {
-        verify_failure("./tests/commit_messages/failure_5");
+    fn export_toml() {
+        let path = create_tagged_test_repo("export_toml");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert!(journal
+            .add_entry("Added a\n\n- Fixed b\n\nFixes: #1")
+            .is_ok());
+        assert!(journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .is_ok());
+        let toml = journal.export("toml").unwrap();
+        let value: Value = toml::from_str(&toml).unwrap();
+        assert_eq!(value["format_version"].as_integer(), Some(1));
+        let releases = value["releases"].as_array().unwrap();
+        assert_eq!(releases.len(), 3);
+        assert_eq!(releases[0]["name"].as_str(), Some("Unreleased"));
+        assert_eq!(
+            releases[0]["commits"][0]["footer"][0]["value"].as_str(),
+            Some("#1")
+        );
+        assert_eq!(
+            releases[1]["commits"][0]["summary"]["text"].as_str(),
+            Some("file2")
+        );
     }
 
     #[test]
-    fn verify_commit_msg_paragraph_failure_3() {
-        verify_failure("./tests/commit_messages/failure_6");
+    fn export_json() {
+        let path = create_tagged_test_repo("export_json");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.timezone = "local".to_owned();
+        assert!(journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .is_ok());
+        let json = journal.export("json").unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["format_version"], FORMAT_VERSION);
+        assert_eq!(value["releases"][1]["date"], "1970-01-01T00:50:00Z");
+        assert_eq!(value["releases"][0]["name"], "Unreleased");
+        assert!(value["releases"][0]["oid"].is_null());
+        assert_eq!(
+            value["releases"][1]["commits"][0]["summary"]["text"],
+            "file2"
+        );
+
+        let schema: serde_json::Value =
+            serde_json::from_str(JSON_SCHEMA).unwrap();
+        assert_eq!(
+            schema["properties"]["format_version"]["const"],
+            FORMAT_VERSION
+        );
+        let required = schema["definitions"]["release"]["required"]
+            .as_array()
+            .unwrap();
+        for field in required {
+            assert!(!value["releases"][1][field.as_str().unwrap()].is_null());
+        }
     }
 
     #[test]
-    fn verify_commit_msg_summary_failure_tag() {
-        let journal = GitJournal::new("./tests/test_repo2").unwrap();
-        assert!(journal.verify("./tests/commit_messages/success_1").is_err());
-        assert!(journal.verify("./tests/commit_messages/success_3").is_err());
+    fn export_csv() {
+        let path = create_tagged_test_repo("export_csv");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert!(journal.add_entry("Added a, \"quoted\" :tag:").is_ok());
+        assert!(journal
+            .parse_log(&ParseOptions::new("v1..HEAD").all(true))
+            .is_ok());
+        let csv = journal.export("csv").unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "tag,date,category,scope,summary,sha,author");
+        assert!(lines[1].starts_with("Unreleased,"));
+        assert!(lines[1].ends_with(",Added,tag,\"a, \"\"quoted\"\"\",,"));
+        assert!(lines[4].starts_with("v2,1970-01-01,Fixed,,file1,"));
+        assert!(lines[4].ends_with(",Test"));
+
+        let tsv = journal.export("tsv").unwrap();
+        assert!(tsv.starts_with("tag\tdate\tcategory\t"));
+        assert!(tsv.contains("\tAdded\ttag\ta, \"quoted\"\t\t\n"));
     }
 
     #[test]
-    fn parse_and_print_log_1() {
-        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
-        assert_eq!(journal.tags.len(), 2);
-        assert_eq!(journal.parser.result.len(), 0);
-        assert_eq!(journal.config.show_prefix, false);
-        assert_eq!(journal.config.colored_output, true);
-        assert_eq!(journal.config.show_commit_hash, false);
-        assert_eq!(journal.config.excluded_commit_tags.len(), 0);
+    fn export_custom_backend() {
+        struct Summaries;
+
+        impl OutputBackend for Summaries {
+            fn name(&self) -> &str {
+                "summaries"
+            }
+
+            fn render(&self, journal: &ParsedJournal) -> Result<String, Error> {
+                Ok(journal
+                    .releases()
+                    .iter()
+                    .flat_map(|release| &release.commits)
+                    .map(|commit| format!("{}\n", commit.summary.text))
+                    .collect())
+            }
+        }
+
+        let path = create_tagged_test_repo("export_custom_backend");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert!(journal.export("summaries").is_err());
+        journal.register_backend(Box::new(Summaries));
         assert!(journal
-            .parse_log("HEAD", "rc", &0, &true, &false, None)
+            .parse_log(&ParseOptions::new("v1..HEAD").all(true))
             .is_ok());
-        assert_eq!(journal.parser.result.len(), journal.tags.len() + 1);
-        assert_eq!(journal.parser.result[0].commits.len(), 15);
-        assert_eq!(journal.parser.result[1].commits.len(), 1);
-        assert_eq!(journal.parser.result[2].commits.len(), 2);
-        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
-        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        assert_eq!(
+            journal.export("summaries").unwrap(),
+            "file2\nfile2\nfile1\n"
+        );
+        assert!(journal.export("json").is_ok());
+    }
+
+    #[test]
+    fn add_manual_entries() {
+        let path = create_tagged_test_repo("manual_entries");
+        let mut file = File::create(path.join("journal.toml")).unwrap();
+        file.write_all(b"entries = [\"Improved the CI setup\"]\n")
+            .unwrap();
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.manual_entries_file = Some("journal.toml".to_owned());
+        assert!(journal.add_entry("Added the documentation site").is_ok());
+        assert!(journal.add_entry("No valid entry").is_err());
         assert!(journal
-            .print_log(
-                false,
-                Some("./tests/template.toml"),
-                Some("CHANGELOG.md")
-            )
+            .parse_log(&ParseOptions::new("v2..HEAD").all(true))
             .is_ok());
+        let summaries = |journal: &GitJournal| {
+            journal.releases()[0]
+                .commits
+                .iter()
+                .map(|c| c.summary.text.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(journal.releases().len(), 1);
+        assert_eq!(
+            summaries(&journal),
+            ["the CI setup", "the documentation site", "file2"]
+        );
+
+        // Entries are added to the current results as well
+        assert!(journal.add_entry("Fixed the docs").is_ok());
+        assert_eq!(summaries(&journal)[0], "the docs");
+
+        // An unreleased section will be created if necessary
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert!(journal.add_entry("Added the documentation site").is_ok());
         assert!(journal
-            .print_log(
-                true,
-                Some("./tests/template.toml"),
-                Some("CHANGELOG.md")
-            )
+            .parse_log(&ParseOptions::new("v1..v2").all(true))
             .is_ok());
+        assert_eq!(journal.releases()[0].name, "Unreleased");
+        assert_eq!(journal.releases()[1].name, "v2");
+    }
+
+    #[test]
+    fn tag_names() {
+        let path = create_tagged_test_repo("tag_names");
+        let repo = Repository::open(&path).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.tag_lightweight("v3", head.as_object(), false).unwrap();
+        let journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(journal.tag_names().unwrap(), ["v1", "v2", "v3"]);
     }
 
     #[test]
-    fn parse_and_print_log_2() {
-        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+    fn parse_log_multiple_tags() {
+        let path = create_tagged_test_repo("multiple_tags");
+        let repo = Repository::open(&path).unwrap();
+        let v2 = repo.revparse_single("v2^{}").unwrap().id();
+        tag(&repo, "v2.0.1", v2, "Version 2.0.1");
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
         assert!(journal
-            .parse_log("HEAD", "rc", &1, &false, &false, None)
+            .parse_log(&ParseOptions::new("HEAD").all(true))
             .is_ok());
-        assert_eq!(journal.parser.result.len(), 2);
-        assert_eq!(journal.parser.result[0].name, "Unreleased");
-        assert_eq!(journal.parser.result[1].name, "v2");
-        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
-        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        let names = journal
+            .releases()
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Unreleased", "v2 / v2.0.1", "v1"]);
+        assert!(journal.render_release("v2.0.1", true, None).is_ok());
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.multiple_tags = "separate".to_owned();
         assert!(journal
-            .print_log(
-                false,
-                Some("./tests/template.toml"),
-                Some("CHANGELOG.md")
-            )
+            .parse_log(&ParseOptions::new("HEAD").all(true))
             .is_ok());
+        let names = journal
+            .releases()
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Unreleased", "v2.0.1", "v2", "v1"]);
+        assert!(journal.releases()[1].commits.is_empty());
+        assert_eq!(journal.releases()[2].commits.len(), 2);
+
+        journal.config.multiple_tags = "wrong".to_owned();
         assert!(journal
-            .print_log(
-                true,
-                Some("./tests/template.toml"),
-                Some("CHANGELOG.md")
-            )
-            .is_ok());
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .is_err());
     }
 
     #[test]
-    fn parse_and_print_log_3() {
-        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
-        assert!(journal
-            .parse_log("HEAD", "rc", &1, &false, &true, None)
-            .is_ok());
-        assert_eq!(journal.parser.result.len(), 1);
-        assert_eq!(journal.parser.result[0].name, "v2");
-        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
-        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+    fn parse_log_fold_prereleases() {
+        let (path, repo) = create_test_repo("fold_prereleases");
+        let c1 = commit(&repo, "Added file1", 1_000);
+        tag(&repo, "v1.0.0-rc1", c1, "Version 1 RC 1");
+        let c2 = commit(&repo, "Fixed file1", 2_000);
+        tag(&repo, "v1.0.0", c2, "Version 1");
+        let c3 = commit(&repo, "Added file2", 3_000);
+        tag(&repo, "v2.0.0-rc1", c3, "Version 2 RC 1");
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.fold_prereleases = true;
         assert!(journal
-            .print_log(
-                false,
-                Some("./tests/template.toml"),
-                Some("CHANGELOG.md")
+            .parse_log(
+                &ParseOptions::new("HEAD").tag_skip_pattern("").all(true)
             )
             .is_ok());
+        let releases = journal.releases();
+        assert_eq!(releases.len(), 2);
+        assert_eq!(releases[0].name, "v2.0.0-rc1");
+        assert_eq!(releases[1].name, "v1.0.0");
+        assert_eq!(releases[1].commit_ids, vec![c2, c1]);
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
         assert!(journal
-            .print_log(
-                true,
-                Some("./tests/template.toml"),
-                Some("CHANGELOG.md")
+            .parse_log(
+                &ParseOptions::new("HEAD").tag_skip_pattern("").all(true)
             )
             .is_ok());
+        assert_eq!(journal.releases().len(), 3);
+        let final_name = GitJournal::get_final_release_name;
+        assert_eq!(final_name("v1.0.0-rc1").unwrap(), "v1.0.0");
+        assert_eq!(
+            final_name("release-1.2.0-beta.2").unwrap(),
+            "release-1.2.0"
+        );
+        assert_eq!(final_name("release-1.2.0"), None);
+        assert_eq!(final_name("v1-rc"), None);
+        assert_eq!(final_name("v1.0.0+build.1"), None);
     }
 
     #[test]
-    fn parse_and_print_log_4() {
-        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+    fn parse_log_semver() {
+        let (path, repo) = create_test_repo("semver");
+        let c1 = commit(&repo, "Added file1", 1_000);
+        tag(&repo, "v1.0.0", c1, "Version 1.0.0");
+        let c2 = commit(&repo, "Added file2", 2_000);
+        tag(&repo, "v2.0.0", c2, "Version 2.0.0");
+        let base_commit = repo.find_commit(c1).unwrap();
+        let signature =
+            Signature::new("Test", "test@example.com", &Time::new(3_000, 0))
+                .unwrap();
+        let tree = base_commit.tree().unwrap();
+        let c3 = repo
+            .commit(
+                None,
+                &signature,
+                &signature,
+                "Fixed file1",
+                &tree,
+                &[&base_commit],
+            )
+            .unwrap();
+        tag(&repo, "v1.0.1", c3, "Version 1.0.1");
+        let signature =
+            Signature::new("Test", "test@example.com", &Time::new(4_000, 0))
+                .unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Added the maintenance branch",
+            &tree,
+            &[
+                &repo.find_commit(c2).unwrap(),
+                &repo.find_commit(c3).unwrap(),
+            ],
+        )
+        .unwrap();
+
+        let names = |journal: &GitJournal| {
+            journal
+                .releases()
+                .iter()
+                .map(|r| r.name.clone())
+                .collect::<Vec<_>>()
+        };
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
         assert!(journal
-            .parse_log("HEAD", "rc", &2, &false, &true, None)
+            .parse_log(&ParseOptions::new("HEAD").all(true))
             .is_ok());
-        assert_eq!(journal.parser.result.len(), 2);
-        assert_eq!(journal.parser.result[0].name, "v2");
-        assert_eq!(journal.parser.result[1].name, "v1");
-        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
-        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        assert_eq!(
+            names(&journal),
+            ["Unreleased", "v1.0.1", "v2.0.0", "v1.0.0"]
+        );
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.tag_sorting = "semver".to_owned();
         assert!(journal
-            .print_log(
-                false,
-                Some("./tests/template.toml"),
-                Some("CHANGELOG.md")
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .is_ok());
+        assert_eq!(
+            names(&journal),
+            ["Unreleased", "v2.0.0", "v1.0.1", "v1.0.0"]
+        );
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.tag_sorting = "semver".to_owned();
+        journal.config.min_version = Some("1.0.1".to_owned());
+        assert!(journal
+            .parse_log(
+                &ParseOptions::new("HEAD").all(true).skip_unreleased(true)
             )
             .is_ok());
+        assert_eq!(names(&journal), ["v2.0.0", "v1.0.1"]);
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.tag_sorting = "semver".to_owned();
+        journal.config.major_version = Some(1);
         assert!(journal
-            .print_log(
-                true,
-                Some("./tests/template.toml"),
-                Some("CHANGELOG.md")
+            .parse_log(
+                &ParseOptions::new("HEAD").all(true).skip_unreleased(true)
             )
             .is_ok());
+        assert_eq!(names(&journal), ["v1.0.1", "v1.0.0"]);
+
+        journal.config.min_version = Some("wrong".to_owned());
+        assert!(journal
+            .parse_log(
+                &ParseOptions::new("HEAD").all(true).skip_unreleased(true)
+            )
+            .is_err());
     }
 
     #[test]
-    fn parse_and_print_log_5() {
-        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
+    fn get_releases_empty_skip_pattern() {
+        let (path, repo) = create_test_repo("empty_skip_pattern");
+        let c1 = commit(&repo, "Added file1", 1_000);
+        tag(&repo, "v1.0.0", c1, "Version 1");
+        let c2 = commit(&repo, "Added file2", 2_000);
+        tag(&repo, "v2.0.0-rc1", c2, "Version 2 RC 1");
+
+        let journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(journal.get_releases(&repo, "").unwrap().len(), 2);
+        let releases = journal.get_releases(&repo, "rc").unwrap();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].1, vec!["v1.0.0"]);
+    }
+
+    #[test]
+    fn parse_log_tags_outside_walk() {
+        let (path, repo) = create_test_repo("tags_outside_walk");
+        let base = commit(&repo, "Added file1", 1_000);
+        let base_commit = repo.find_commit(base).unwrap();
+        let signature =
+            Signature::new("Test", "test@example.com", &Time::new(2_000, 0))
+                .unwrap();
+        let tree = base_commit.tree().unwrap();
+        let feature = repo
+            .commit(
+                None,
+                &signature,
+                &signature,
+                "Added feature",
+                &tree,
+                &[&base_commit],
+            )
+            .unwrap();
+        tag(&repo, "v1", feature, "Version 1");
+        let merge = commit(&repo, "Fixed file1", 3_000);
+        let merge_commit = repo.find_commit(merge).unwrap();
+        let signature =
+            Signature::new("Test", "test@example.com", &Time::new(4_000, 0))
+                .unwrap();
+        let merge = repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                "Added the feature branch",
+                &tree,
+                &[&merge_commit, &repo.find_commit(feature).unwrap()],
+            )
+            .unwrap();
+        tag(&repo, "v2", merge, "Version 2");
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
         assert!(journal
-            .parse_log("v1..v2", "rc", &0, &true, &false, None)
+            .parse_log(&ParseOptions::new("HEAD").all(true))
             .is_ok());
-        assert_eq!(journal.parser.result.len(), 1);
-        assert_eq!(journal.parser.result[0].name, "v2");
-        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
-        assert!(journal.print_log(true, None, Some("CHANGELOG.md")).is_ok());
+        let releases = journal.releases();
+        assert_eq!(releases.len(), 2);
+        assert_eq!(releases[0].name, "v2");
+        assert_eq!(releases[0].oid, Some(merge));
+        assert_eq!(releases[0].commit_ids.len(), 2);
+        assert_eq!(releases[1].name, "v1");
+        assert_eq!(releases[1].commit_ids, vec![feature, base]);
+        assert_eq!(journal.release_of(base).unwrap().name, "v1");
+
+        // The tagged feature commit is not part of the walk
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.first_parent = true;
         assert!(journal
-            .print_log(
-                false,
-                Some("./tests/template.toml"),
-                Some("CHANGELOG.md")
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .is_ok());
+        let releases = journal.releases();
+        assert_eq!(releases.len(), 2);
+        assert_eq!(releases[1].name, "v1");
+        assert_eq!(releases[1].commit_ids, vec![base]);
+        assert!(journal.release_of(feature).is_none());
+    }
+
+    #[test]
+    fn parse_log_first_parent() {
+        let (path, repo) = create_test_repo("first_parent");
+        let base = commit(&repo, "Added file1", 1_000);
+        let base_commit = repo.find_commit(base).unwrap();
+        let signature =
+            Signature::new("Test", "test@example.com", &Time::new(2_000, 0))
+                .unwrap();
+        let tree = base_commit.tree().unwrap();
+        let feature = repo
+            .commit(
+                None,
+                &signature,
+                &signature,
+                "Added feature",
+                &tree,
+                &[&base_commit],
             )
+            .unwrap();
+        let feature_commit = repo.find_commit(feature).unwrap();
+        let signature =
+            Signature::new("Test", "test@example.com", &Time::new(3_000, 0))
+                .unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Added the feature branch",
+            &tree,
+            &[&base_commit, &feature_commit],
+        )
+        .unwrap();
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert!(journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
             .is_ok());
+        assert_eq!(journal.parser.result[0].commits.len(), 3);
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.first_parent = true;
         assert!(journal
-            .print_log(
-                true,
-                Some("./tests/template.toml"),
-                Some("CHANGELOG.md")
-            )
+            .parse_log(&ParseOptions::new("HEAD").all(true))
             .is_ok());
+        assert_eq!(journal.parser.result[0].commits.len(), 2);
     }
 
     #[test]
-    fn parse_and_print_log_6() {
-        let mut journal = GitJournal::new("./tests/test_repo2").unwrap();
-        assert!(journal
-            .parse_log("HEAD", "rc", &0, &true, &false, None)
-            .is_ok());
-        assert!(journal.print_log(false, None, Some("CHANGELOG.md")).is_ok());
+    fn parse_log_branch_categories() {
+        let (path, repo) = create_test_repo("branch_categories");
+        let base = commit(&repo, "Added file1", 1_000);
+        let base_commit = repo.find_commit(base).unwrap();
+        let tree = base_commit.tree().unwrap();
+        let signature =
+            Signature::new("Test", "test@example.com", &Time::new(2_000, 0))
+                .unwrap();
+        let fix = repo
+            .commit(
+                None,
+                &signature,
+                &signature,
+                "login works again",
+                &tree,
+                &[&base_commit],
+            )
+            .unwrap();
+        let fix_commit = repo.find_commit(fix).unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Merge branch 'fix/login'",
+            &tree,
+            &[&base_commit, &fix_commit],
+        )
+        .unwrap();
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal
+            .config
+            .branch_categories
+            .insert("fix/*".to_owned(), "Fixed".to_owned());
+        journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .unwrap();
+        let commits = &journal.parser.result[0].commits;
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].summary.category, "Fixed");
+        assert_eq!(commits[0].summary.text, "login works again");
+
+        repo.branch("fix/typo", &base_commit, false).unwrap();
+        repo.set_head("refs/heads/fix/typo").unwrap();
+        let message = path.join("COMMIT_EDITMSG");
+        fs::write(&message, "").unwrap();
+        journal.prepare(message.to_str().unwrap(), None).unwrap();
+        let prepared = fs::read_to_string(&message).unwrap();
+        assert!(prepared.starts_with("JIRA-1234 Fixed ...\n"));
+        fs::remove_dir_all(path).unwrap();
     }
 
     #[test]
-    fn prepare_message_success_1() {
-        let journal = GitJournal::new(".").unwrap();
-        assert!(journal.prepare("./tests/COMMIT_EDITMSG", None).is_ok());
+    fn parse_log_merge_requests() {
+        let (path, repo) = create_test_repo("merge_requests");
+        let base = commit(&repo, "Added file1", 1_000);
+        let base_commit = repo.find_commit(base).unwrap();
+        let tree = base_commit.tree().unwrap();
+        let signature =
+            Signature::new("Test", "test@example.com", &Time::new(2_000, 0))
+                .unwrap();
+        let feature = repo
+            .commit(
+                None,
+                &signature,
+                &signature,
+                "Added the login",
+                &tree,
+                &[&base_commit],
+            )
+            .unwrap();
+        let feature_commit = repo.find_commit(feature).unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Merge branch 'login' into 'master'\n\nAdded the login\n\n\
+             See merge request group/project!7",
+            &tree,
+            &[&base_commit, &feature_commit],
+        )
+        .unwrap();
+        let pull_requests = |journal: &GitJournal| {
+            journal.parser.result[0]
+                .commits
+                .iter()
+                .map(|commit| commit.summary.merge_request)
+                .collect::<Vec<_>>()
+        };
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let options = ParseOptions::new("HEAD").all(true);
+        journal.parse_log(&options).unwrap();
+        assert_eq!(pull_requests(&journal), vec![None, None]);
+
+        journal.parser.result.clear();
+        journal.config.merge_requests = "link".to_owned();
+        journal.config.colored_output = false;
+        journal.config.repository_url =
+            Some("https://gitlab.com/group/project".to_owned());
+        journal.parse_log(&options).unwrap();
+        assert_eq!(pull_requests(&journal), vec![Some(7), None]);
+        let output = journal.render_release("Unreleased", false, None).unwrap();
+        assert!(output.contains(
+            "- [Added] the login \
+             ([!7](https://gitlab.com/group/project/-/merge_requests/7))"
+        ));
+
+        let commit = journal
+            .parser
+            .parse_commit_message(
+                "Added the logout\n\nSee merge request group/project!8\n",
+                None,
+            )
+            .unwrap();
+        assert_eq!(commit.summary.merge_request, Some(8));
+        assert!(commit.body.is_empty());
+
+        journal.config.merge_requests = "wrong".to_owned();
+        assert!(journal.parse_log(&options).is_err());
+        fs::remove_dir_all(path).unwrap();
     }
 
     #[test]
-    fn prepare_message_success_2() {
-        let journal = GitJournal::new(".").unwrap();
-        assert!(journal
-            .prepare("./tests/commit_messages/prepare_1", None)
-            .is_ok());
+    fn parse_branches_with_cherry_picks() {
+        let (path, repo) = create_test_repo("branches");
+        let add_file = |name: &str| {
+            fs::write(path.join(name), name).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(name)).unwrap();
+            index.write().unwrap();
+        };
+        add_file("a");
+        let c1 = commit(&repo, "Added a", 1_000);
+        let c1 = repo.find_commit(c1).unwrap();
+        repo.branch("release/1.x", &c1, false).unwrap();
+        add_file("b");
+        let c2 = commit(&repo, "Fixed b", 2_000);
+        repo.branch("release/2.x", &repo.find_commit(c2).unwrap(), false)
+            .unwrap();
+
+        // Cherry-pick the fix onto the maintenance branch
+        repo.set_head("refs/heads/release/1.x").unwrap();
+        let mut index = repo.index().unwrap();
+        index.read_tree(&c1.tree().unwrap()).unwrap();
+        index.write().unwrap();
+        add_file("b");
+        let c3 = commit(&repo, "Fixed b", 3_000);
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(
+            journal.branch_names("release/*").unwrap(),
+            vec!["release/1.x", "release/2.x"]
+        );
+        let results = journal
+            .parse_branches("release/*", &ParseOptions::default())
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        let commits =
+            |index: usize| results[index].1.releases()[0].commits.clone();
+        assert_eq!(commits(0)[0].oid, Some(c3));
+        assert_eq!(commits(0)[0].cherry_picks, vec!["release/2.x"]);
+        assert!(commits(0)[1].cherry_picks.is_empty());
+        assert_eq!(commits(1)[0].oid, Some(c2));
+        assert_eq!(commits(1)[0].cherry_picks, vec!["release/1.x"]);
+        assert!(commits(1)[1].cherry_picks.is_empty());
     }
 
     #[test]
-    fn prepare_message_success_3() {
-        let journal = GitJournal::new(".").unwrap();
-        assert!(journal
-            .prepare("./tests/commit_messages/prepare_2", None)
-            .is_ok());
+    fn parse_log_deduplicate_cherry_picks() {
+        let (path, repo) = create_test_repo("cherry_picks");
+        let add_file = |name: &str| {
+            fs::write(path.join(name), name).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(name)).unwrap();
+            index.write().unwrap();
+        };
+        add_file("a");
+        let c1 = repo.find_commit(commit(&repo, "Added a", 1_000)).unwrap();
+        repo.branch("release/1.x", &c1, false).unwrap();
+        add_file("b");
+        let c2 = commit(&repo, "Fixed b", 2_000);
+
+        // Cherry-pick the fix and merge the branch back
+        repo.set_head("refs/heads/release/1.x").unwrap();
+        let mut index = repo.index().unwrap();
+        index.read_tree(&c1.tree().unwrap()).unwrap();
+        index.write().unwrap();
+        add_file("b");
+        let c3 = repo.find_commit(commit(&repo, "Fixed b", 3_000)).unwrap();
+        repo.set_head("refs/heads/master").unwrap();
+        let c2 = repo.find_commit(c2).unwrap();
+        let signature =
+            Signature::new("Test", "test@example.com", &Time::new(4_000, 0))
+                .unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Merge branch 'release/1.x'",
+            &c2.tree().unwrap(),
+            &[&c2, &c3],
+        )
+        .unwrap();
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let options = ParseOptions::default();
+        journal.parse_log(&options).unwrap();
+        assert_eq!(journal.releases()[0].commits.len(), 3);
+
+        journal.config.deduplicate_cherry_picks = true;
+        journal.config.show_backports = true;
+        journal.config.colored_output = false;
+        journal.parser.result.clear();
+        journal.parse_log(&options).unwrap();
+        let commits = &journal.releases()[0].commits;
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].oid, Some(c2.id()));
+        assert!(commits[0].summary.backported);
+        assert!(!commits[1].summary.backported);
+        let output = journal.render_release("Unreleased", true, None).unwrap();
+        assert!(output.contains("- [Fixed] b (backported)\n"));
     }
 
     #[test]
-    fn prepare_message_success_4() {
-        let journal = GitJournal::new(".").unwrap();
-        assert!(journal
-            .prepare("./tests/commit_messages/prepare_4", None)
-            .is_ok());
+    fn parse_log_category_order() {
+        let (path, repo) = create_test_repo("category_order");
+        commit(&repo, "Added file1", 1000);
+        commit(&repo, "Fixed file1", 2000);
+        commit(&repo, "Security file1", 3000);
+        commit(&repo, "Changed file1", 4000);
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.categories.push("Security".to_owned());
+        let categories = |journal: &mut GitJournal| {
+            journal.parser.result.clear();
+            journal.parse_log(&ParseOptions::new("HEAD")).unwrap();
+            journal.releases()[0]
+                .commits
+                .iter()
+                .map(|c| c.summary.category.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            categories(&mut journal),
+            vec!["Changed", "Security", "Fixed", "Added"]
+        );
+
+        journal.config.category_order =
+            vec!["Security".to_owned(), "Fixed".to_owned()];
+        assert_eq!(
+            categories(&mut journal),
+            vec!["Security", "Fixed", "Changed", "Added"]
+        );
+
+        journal.config.sort_by = "name".to_owned();
+        assert_eq!(
+            categories(&mut journal),
+            vec!["Security", "Fixed", "Added", "Changed"]
+        );
     }
 
     #[test]
-    fn prepare_message_failure_1() {
-        let journal = GitJournal::new(".").unwrap();
-        assert!(journal.prepare("TEST", None).is_err());
-        assert!(journal.prepare("TEST", Some("message")).is_err());
+    fn parse_log_exclude_patterns() {
+        let (path, repo) = create_test_repo("exclude_patterns");
+        commit(&repo, "Added file1", 1000);
+        commit(&repo, "Fixed file1 [skip changelog]", 2000);
+        commit(&repo, "Merge branch 'feature'", 3000);
+        commit(&repo, "amend! Fixed file1\n\nFixed file1 again", 3500);
+        commit(&repo, "Changed file1 by bot", 4000);
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let texts = |journal: &mut GitJournal| {
+            journal.parser.result.clear();
+            let options = ParseOptions::new("HEAD");
+            assert!(journal.parse_log(&options).unwrap().is_empty());
+            journal.releases()[0]
+                .commits
+                .iter()
+                .map(|c| c.summary.text.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(texts(&mut journal), vec!["file1 by bot", "file1"]);
+
+        journal.config.exclude_patterns.push("by bot$".to_owned());
+        assert_eq!(texts(&mut journal), vec!["file1"]);
+
+        journal.config.exclude_patterns = vec!["(".to_owned()];
+        assert!(journal.parse_log(&ParseOptions::new("HEAD")).is_err());
     }
 
     #[test]
-    fn prepare_message_failure_2() {
-        let journal = GitJournal::new(".").unwrap();
-        assert!(journal
-            .prepare("./tests/commit_messages/prepare_3", Some("message"))
-            .is_err());
+    fn parse_log_transformations() {
+        let (path, repo) = create_test_repo("transformations");
+        commit(&repo, "feat: file1 on db1.corp.example.com", 1000);
+        commit(
+            &repo,
+            "Fixed file1\n\n- [Changed] on db2.corp\n\nSigned: ACME",
+            2000,
+        );
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal
+            .config
+            .message_replacements
+            .insert("(?m)^Signed: .*$".to_owned(), String::new());
+        journal.config.entry_replacements.insert(
+            r"db\d\.corp(\.example\.com)?".to_owned(),
+            "<db>".to_owned(),
+        );
+        journal.add_message_hook(|message| message.replace("feat:", "Added"));
+        journal.add_entry_hook(|commit| commit.summary.text.push('!'));
+        journal.parse_log(&ParseOptions::new("HEAD")).unwrap();
+        let commits = &journal.releases()[0].commits;
+        assert_eq!(commits[0].summary.text, "file1!");
+        assert_eq!(commits[0].body.len(), 1);
+        match commits[0].body[0] {
+            BodyElement::List(ref list) => assert_eq!(list[0].text, "on <db>"),
+            _ => panic!("Expected a list"),
+        }
+        assert_eq!(commits[1].summary.category, "Added");
+        assert_eq!(commits[1].summary.text, "file1 on <db>!");
+
+        journal
+            .config
+            .entry_replacements
+            .insert("(".to_owned(), String::new());
+        assert!(journal.parse_log(&ParseOptions::new("HEAD")).is_err());
     }
 
     #[test]
-    fn install_git_hook() {
-        let journal = GitJournal::new(".").unwrap();
-        assert!(journal.install_git_hook("test", "echo 1\n").is_ok());
-        assert!(journal.install_git_hook("test", "echo 1\n").is_ok());
-        assert!(journal.install_git_hook("test", "echo 2\n").is_ok());
+    fn parse_log_redact() {
+        let (path, repo) = create_test_repo("redact");
+        let oid = commit(
+            &repo,
+            "Added file1 on db1.corp\n\n- [Fixed] db2.corp\n\n\
+             Reviewed-by: admin@db3.corp",
+            1000,
+        );
+        tag(&repo, "v1", oid, "Release for db4.corp");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.redact.push(r"db\d\.corp".to_owned());
+        journal.add_entry_hook(|commit| commit.summary.text += " of db5.corp");
+        journal.parse_log(&ParseOptions::new("HEAD")).unwrap();
+        let release = &journal.releases()[0];
+        assert_eq!(release.title.as_deref(), Some("Release for [redacted]"));
+        let commit = &release.commits[0];
+        assert_eq!(commit.summary.text, "file1 on [redacted] of [redacted]");
+        assert_eq!(commit.texts()[1], "[redacted]");
+        assert_eq!(commit.footer[0].value, "admin@[redacted]");
+
+        assert!(journal.add_entry("Added docs for db6.corp").is_ok());
+        let entry = &journal.releases()[0].commits[0];
+        assert_eq!(entry.summary.text, "docs for [redacted] of [redacted]");
+
+        journal.config.redact.push("(".to_owned());
+        assert!(journal.parse_log(&ParseOptions::new("HEAD")).is_err());
+        fs::remove_dir_all(path).unwrap();
     }
 
     #[test]
-    fn generate_template_1() {
-        let mut journal = GitJournal::new("./tests/test_repo").unwrap();
-        assert!(journal.generate_template().is_ok());
+    fn parse_log_retain_categories() {
+        let path = create_tagged_test_repo("retain_categories");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .unwrap();
+        assert_eq!(journal.releases().len(), 3);
+        journal.retain_categories(&["Removed", "Fixed"]);
+        assert_eq!(journal.releases().len(), 1);
+        assert_eq!(journal.releases()[0].name, "v2");
+        assert_eq!(journal.releases()[0].commits.len(), 1);
+        assert_eq!(journal.releases()[0].commits[0].summary.text, "file1");
+        journal.retain_categories::<&str>(&[]);
+        assert!(journal.releases().is_empty());
+    }
+
+    #[test]
+    fn search_parsed_log() {
+        let path = create_tagged_test_repo("search");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.colored_output = false;
+        journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .unwrap();
+        let results = journal.search("FILE2");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].release, "Unreleased");
+        assert_eq!(results[0].category, "Changed");
+        assert_eq!(results[1].release, "v2");
+        assert_eq!(results[1].commit.summary.text, "file2");
+        assert!(journal.search("nothing").is_empty());
+
+        let output = journal.render_search("file1", false, None).unwrap();
+        assert!(output.starts_with("# Search results for 'file1'\n"));
+        assert!(output.contains("- [Fixed] file1\n"));
+        assert!(!output.contains("file2"));
+        assert!(!output.contains("Unreleased"));
+    }
+
+    #[test]
+    fn releases_touching_path_and_symbol() {
+        let (path, repo) = create_test_repo("releases_touching");
+        let add_file = |name: &str, content: &str| {
+            fs::create_dir_all(path.join(name).parent().unwrap()).unwrap();
+            fs::write(path.join(name), content).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(name)).unwrap();
+            index.write().unwrap();
+        };
+        add_file("src/parser.rs", "fn parse() {}\n");
+        let c1 = commit(&repo, "Added the parser", 1_000);
+        tag(&repo, "v1", c1, "Version 1");
+        add_file("README.md", "Call parse()\n");
+        let c2 = commit(&repo, "Added the docs", 2_000);
+        tag(&repo, "v2", c2, "Version 2");
+        add_file("src/parser.rs", "fn parse_all() {}\n");
+        commit(&repo, "Changed the parser", 3_000);
+
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal
+            .parse_log(&ParseOptions::new("HEAD").all(true))
+            .unwrap();
+        let releases = |query: &str| {
+            journal
+                .releases_touching(query)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.release)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(releases("src/parser.rs"), vec!["Unreleased", "v1"]);
+        assert_eq!(releases("src"), vec!["Unreleased", "v1"]);
+        assert_eq!(releases("parse_all"), vec!["Unreleased"]);
+        assert_eq!(releases("parse()"), vec!["Unreleased", "v2", "v1"]);
+        assert!(releases("nothing").is_empty());
+    }
+
+    #[test]
+    fn parse_log_warnings() {
+        let (path, repo) = create_test_repo("warnings");
+        commit(&repo, "Added file1", 2_000);
+        let invalid = commit(&repo, "Some invalid message", 1_000);
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let warnings = journal.parse_log(&ParseOptions::new("HEAD")).unwrap();
+        assert_eq!(warnings.len(), 2);
+        let parent = repo.find_commit(invalid).unwrap().parent_id(0).unwrap();
+        assert_eq!(
+            warnings.iter().next(),
+            Some(&Warning::ClockSkew {
+                oid: invalid,
+                parent
+            })
+        );
+        match warnings.into_iter().nth(1) {
+            Some(Warning::InvalidCommit { oid, .. }) => {
+                assert_eq!(oid, invalid)
+            }
+            warning => panic!("Unexpected warning {:?}", warning),
+        }
+        assert_eq!(journal.releases()[0].commits.len(), 1);
+    }
+
+    #[test]
+    fn amend_last_commit() {
+        let (path, repo) = create_test_repo("amend_last");
+        commit(&repo, "Added file1", 1_000);
+        let journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let oid = journal
+            .amend_last("Fixed", "the file1 ", Some("- [Added] file2\n"))
+            .unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.id(), oid);
+        assert_eq!(
+            head.message(),
+            Some("Fixed the file1\n\n- [Added] file2\n")
+        );
+        assert_eq!(head.parent_count(), 0);
+
+        assert!(journal.amend_last("Wrong", "the file1", None).is_err());
+        assert_eq!(repo.head().unwrap().target(), Some(oid));
+
+        // The prefix and the trailers of the last commit are kept
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        head.amend(
+            Some("HEAD"),
+            None,
+            None,
+            None,
+            Some("JIRA-12 wip\n\nSigned-off-by: Test <test@example.com>\n"),
+            None,
+        )
+        .unwrap();
+        journal.amend_last("Fixed", "the file1", None).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(
+            head.message(),
+            Some(
+                "JIRA-12 Fixed the file1\n\n\
+                 Signed-off-by: Test <test@example.com>\n"
+            )
+        );
+    }
+
+    #[test]
+    fn parse_log_empty_releases() {
+        let (path, repo) = create_test_repo("empty_releases");
+        let c1 = commit(&repo, "Added file1", 1_000);
+        tag(&repo, "v1", c1, "Version 1");
+        let c2 = commit(&repo, "Some invalid message", 2_000);
+        tag(&repo, "v2", c2, "Version 2");
+        commit(&repo, "Fixed file1", 3_000);
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let options = ParseOptions::new("HEAD").all(true);
+        let warnings = journal.parse_log(&options).unwrap();
+        assert_eq!(
+            warnings.iter().last(),
+            Some(&Warning::EmptyRelease("v2".to_owned()))
+        );
+        let names = journal.releases().iter().map(|r| r.name.clone());
+        assert_eq!(names.collect::<Vec<_>>(), vec!["Unreleased", "v1"]);
+
+        journal.parser.result.clear();
+        journal.config.empty_releases = "off".to_owned();
+        let warnings = journal.parse_log(&options).unwrap();
+        assert_eq!(warnings.len(), 1);
+
+        journal.parser.result.clear();
+        journal.config.empty_releases = "error".to_owned();
+        let error = journal.parse_log(&options).unwrap_err();
+        assert_eq!(error.to_string(), "Releases without any valid commit: v2");
+    }
+
+    #[test]
+    fn parse_log_shallow_clone() {
+        let (path, repo) = create_test_repo("shallow");
+        let oid = commit(&repo, "Added file1", 1_000);
+        commit(&repo, "Fixed file1", 2_000);
+        fs::write(path.join(".git/shallow"), format!("{}\n", oid)).unwrap();
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let warnings = journal.parse_log(&ParseOptions::new("HEAD")).unwrap();
+        assert_eq!(warnings.iter().next(), Some(&Warning::ShallowClone));
+        assert_eq!(journal.releases()[0].commits.len(), 2);
+    }
+
+    #[test]
+    fn repository_links_from_remote() {
+        let (path, repo) = create_test_repo("repository_links");
+        repo.remote("origin", "git@codeberg.org:user/project.git")
+            .unwrap();
+        let journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(journal.config.repository_url, None);
+        assert_eq!(journal.commit_url("0123456").unwrap(), None);
+
+        let mut config = Config::new();
+        config.provider = Some("forgejo".to_owned());
+        config.save_default_config(path.to_str().unwrap()).unwrap();
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(
+            journal.config.repository_url.as_ref().unwrap(),
+            "https://codeberg.org/user/project"
+        );
+        assert_eq!(
+            journal.compare_url("v1", "v2").unwrap().unwrap(),
+            "https://codeberg.org/user/project/compare/v1...v2"
+        );
+        journal.config.provider = Some("svn".to_owned());
+        assert!(journal.commit_url("0123456").is_err());
+    }
+
+    #[test]
+    fn fetch_tags_from_remote() {
+        let (remote_path, remote) = create_test_repo("fetch_tags_remote");
+        let c1 = commit(&remote, "Added file1", 1_000);
+        let (path, repo) = create_test_repo("fetch_tags");
+        repo.remote("origin", remote_path.to_str().unwrap())
+            .unwrap();
+        repo.find_remote("origin")
+            .unwrap()
+            .fetch(&["+refs/heads/*:refs/heads/*"], None, None)
+            .unwrap();
+        tag(&remote, "v1", c1, "Version 1");
+
+        let journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert!(journal.tags.is_empty());
+        let mut config = Config::new();
+        config.fetch_tags = true;
+        config.save_default_config(path.to_str().unwrap()).unwrap();
+        let journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(journal.tags, vec![(c1, "v1".to_owned())]);
+
+        // An unavailable remote keeps the local tags
+        config.fetch_remote = "missing".to_owned();
+        config.save_default_config(path.to_str().unwrap()).unwrap();
+        assert_eq!(
+            GitJournal::new(path.to_str().unwrap()).unwrap().tags.len(),
+            1
+        );
+    }
+
+    #[test]
+    fn parse_remote_ref_of_bare_repo() {
+        let mut path = env::temp_dir();
+        path.push(format!("git-journal-bare-{}", std::process::id()));
+        if path.exists() {
+            fs::remove_dir_all(&path).unwrap();
+        }
+        let repo = Repository::init_bare(&path).unwrap();
+        let mut config = Config::new();
+        config.categories = vec!["Security".to_owned()];
+        let toml = toml::to_string(&config).unwrap();
+        let blob = repo.blob(toml.as_bytes()).unwrap();
+        let mut builder = repo.treebuilder(None).unwrap();
+        builder.insert(CONFIG_FILE_NAME, blob, 0o100_644).unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+        let signature =
+            Signature::new("Test", "test@example.com", &Time::new(0, 0))
+                .unwrap();
+        let oid = repo
+            .commit(
+                Some("refs/remotes/origin/main"),
+                &signature,
+                &signature,
+                "Security fix",
+                &tree,
+                &[],
+            )
+            .unwrap();
+
+        // Without any HEAD the default configuration is used
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(journal.config, Config::new());
+        assert!(journal.load_config_from("HEAD").is_err());
+        journal.load_config_from("origin/main").unwrap();
+        assert_eq!(journal.config.categories, config.categories);
+        journal
+            .parse_log(&ParseOptions::new("origin/main"))
+            .unwrap();
+        assert_eq!(journal.releases()[0].commits[0].oid, Some(oid));
+
+        repo.reference("refs/heads/master", oid, false, "").unwrap();
+        let journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(journal.config.categories, config.categories);
+    }
+
+    #[test]
+    fn parse_log_cancelled() {
+        let path = create_tagged_test_repo("cancelled");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        let cancellation = Cancellation::new();
+        let options = ParseOptions::new("HEAD")
+            .all(true)
+            .cancellation(&cancellation);
+        assert!(journal.parse_log(&options).is_ok());
+
+        journal.parser.result.clear();
+        cancellation.cancel();
+        let report = Report::from(journal.parse_log(&options).unwrap_err());
+        assert_eq!(report.kind(), Some(&ErrorKind::Cancelled));
+        assert!(journal.releases().is_empty());
+        assert!(journal.parse_branches("*", &options).is_err());
+    }
+
+    #[test]
+    fn install_hooks_into_hooks_path_and_worktree() {
+        let (path, repo) = create_test_repo("hooks_path");
+        commit(&repo, "Added file1", 1_000);
+        let journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(journal.hooks_dir().unwrap(), path.join(".git/hooks"));
+
+        repo.config()
+            .unwrap()
+            .set_str("core.hooksPath", "shared-hooks")
+            .unwrap();
+        journal
+            .install_git_hook("commit-msg", "git journal v $1\n")
+            .unwrap();
+        let hook = fs::read_to_string(path.join("shared-hooks/commit-msg"));
+        assert!(hook.unwrap().contains("git journal v $1"));
+        repo.config().unwrap().remove("core.hooksPath").unwrap();
+
+        // A linked worktree shares the hooks of the main repository
+        let worktree = path.join("worktree");
+        let git_dir = path.join(".git/worktrees/worktree");
+        fs::create_dir_all(&worktree).unwrap();
+        fs::create_dir_all(&git_dir).unwrap();
+        fs::write(git_dir.join("commondir"), "../..\n").unwrap();
+        fs::write(git_dir.join("gitdir"), "../../../worktree/.git\n").unwrap();
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/master\n").unwrap();
+        fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", git_dir.display()),
+        )
+        .unwrap();
+        let journal = GitJournal::new(worktree.to_str().unwrap()).unwrap();
+        journal
+            .install_git_hook("commit-msg", "git journal v $1\n")
+            .unwrap();
+        assert!(path.join(".git/hooks/commit-msg").is_file());
+        assert!(!git_dir.join("hooks").exists());
+    }
+
+    #[test]
+    fn parse_log_revwalk_sorting() {
+        let path = create_tagged_test_repo("revwalk_sorting");
+        let mut journal = GitJournal::new(path.to_str().unwrap()).unwrap();
+        journal.config.revwalk_sorting =
+            vec!["topological".to_owned(), "reverse".to_owned()];
         assert!(journal
-            .parse_log("HEAD", "rc", &0, &true, &false, None)
+            .parse_log(&ParseOptions::new("HEAD").all(true))
             .is_ok());
-        assert!(journal.generate_template().is_ok());
+        assert_eq!(journal.parser.result.len(), 3);
+        assert_eq!(journal.parser.result[0].name, "v1");
+        assert_eq!(journal.parser.result[1].name, "v2");
+        assert_eq!(journal.parser.result[1].commits[0].summary.text, "file1");
+        assert_eq!(journal.parser.result[2].name, "Unreleased");
+
+        journal.config.revwalk_sorting = vec!["wrong".to_owned()];
+        let report = Report::from(
+            journal
+                .parse_log(&ParseOptions::new("HEAD").all(true))
+                .unwrap_err(),
+        );
+        assert_eq!(
+            report.kind(),
+            Some(&ErrorKind::Config("revwalk_sorting".to_owned()))
+        );
+        assert_eq!(
+            format!("{:#}", report),
+            "Configuration key 'revwalk_sorting': \
+             Unknown revwalk sorting 'wrong'"
+        );
+    }
+
+    #[test]
+    fn translated_path() {
+        assert_eq!(
+            GitJournal::get_translated_path("CHANGELOG.md", "de"),
+            "CHANGELOG.de.md"
+        );
+        assert_eq!(
+            GitJournal::get_translated_path("out/CHANGELOG", "fr"),
+            "out/CHANGELOG.fr"
+        );
     }
 
     #[test]
     fn path_failure() {
         assert!(GitJournal::new("/etc/").is_err());
     }
-
 }
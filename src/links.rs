@@ -0,0 +1,285 @@
+//! Links to the web interface of the hosted repository, where the shapes of
+//! the commit, compare and pull request URLs depend on the provider.
+
+// The remote and compare links are only used by a `GitJournal`
+#![cfg_attr(not(feature = "git"), allow(dead_code))]
+
+use crate::{config::Config, error::ErrorKind};
+use failure::{format_err, Error};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::borrow::Cow;
+
+lazy_static! {
+    /// The references to Azure Boards work items like "AB#1234"
+    pub(crate) static ref RE_WORK_ITEM: Regex =
+        Regex::new(r"\bAB#(\d+)\b").unwrap();
+}
+
+/// A hosting provider of repositories.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Provider {
+    /// GitHub or GitHub Enterprise
+    GitHub,
+
+    /// GitLab.com or a self-hosted GitLab
+    GitLab,
+
+    /// Bitbucket Cloud at bitbucket.org
+    Bitbucket,
+
+    /// A self-hosted Bitbucket Server or Data Center
+    BitbucketServer,
+
+    /// Gitea or its fork Forgejo, e.g. Codeberg
+    Gitea,
+}
+
+impl Provider {
+    /// Returns the provider of the configured name, which is one of "github",
+    /// "gitlab", "bitbucket", "bitbucket-server", "gitea" or "forgejo".
+    ///
+    /// # Errors
+    /// If the name is unknown.
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        Ok(match name {
+            "github" => Provider::GitHub,
+            "gitlab" => Provider::GitLab,
+            "bitbucket" => Provider::Bitbucket,
+            "bitbucket-server" => Provider::BitbucketServer,
+            "gitea" | "forgejo" => Provider::Gitea,
+            _ => {
+                return Err(ErrorKind::Config("provider".to_owned())
+                    .wrap(format_err!("Unknown provider '{}'", name)))
+            }
+        })
+    }
+
+    /// Detects the provider from the host name of the repository URL.
+    pub fn detect(url: &str) -> Option<Self> {
+        let host = url.split("://").last()?.split(['/', ':']).next()?;
+        let host = host.rsplit('@').next()?.to_lowercase();
+        if host.contains("github") {
+            Some(Provider::GitHub)
+        } else if host.contains("gitlab") {
+            Some(Provider::GitLab)
+        } else if host == "bitbucket.org" {
+            Some(Provider::Bitbucket)
+        } else if host.contains("bitbucket") {
+            Some(Provider::BitbucketServer)
+        } else if ["gitea", "forgejo", "codeberg"]
+            .iter()
+            .any(|name| host.contains(name))
+        {
+            Some(Provider::Gitea)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the configured or detected provider together with the
+    /// repository URL, if links are configured.
+    ///
+    /// # Errors
+    /// If the configured provider is unknown.
+    pub fn from_config(config: &Config) -> Result<Option<(Self, &str)>, Error> {
+        let url = match config.repository_url {
+            Some(ref url) => url.trim_end_matches('/'),
+            None => return Ok(None),
+        };
+        let provider = match config.provider {
+            Some(ref name) => Some(Self::from_name(name)?),
+            None => Self::detect(url),
+        };
+        Ok(provider.map(|provider| (provider, url)))
+    }
+
+    /// Returns the link to a commit.
+    pub fn commit_url(self, url: &str, oid: &str) -> String {
+        match self {
+            Provider::GitHub | Provider::Gitea => {
+                format!("{}/commit/{}", url, oid)
+            }
+            Provider::GitLab => format!("{}/-/commit/{}", url, oid),
+            Provider::Bitbucket | Provider::BitbucketServer => {
+                format!("{}/commits/{}", url, oid)
+            }
+        }
+    }
+
+    /// Returns the link to the changes between two revisions like tags.
+    pub fn compare_url(self, url: &str, from: &str, to: &str) -> String {
+        match self {
+            Provider::GitHub | Provider::Gitea => {
+                format!("{}/compare/{}...{}", url, from, to)
+            }
+            Provider::GitLab => format!("{}/-/compare/{}...{}", url, from, to),
+            Provider::Bitbucket => {
+                format!("{}/branches/compare/{}%0D{}", url, to, from)
+            }
+            Provider::BitbucketServer => format!(
+                "{}/compare/commits?sourceBranch={}&targetBranch={}",
+                url, to, from
+            ),
+        }
+    }
+
+    /// Returns the link to the pull requests without their number.
+    pub fn pull_request_url(self, url: &str) -> String {
+        match self {
+            Provider::GitHub => format!("{}/pull/", url),
+            Provider::GitLab => format!("{}/-/merge_requests/", url),
+            Provider::Bitbucket | Provider::BitbucketServer => {
+                format!("{}/pull-requests/", url)
+            }
+            Provider::Gitea => format!("{}/pulls/", url),
+        }
+    }
+}
+
+/// Converts the URL of a git remote like `git@github.com:user/project.git`
+/// into the URL of its web interface like `https://github.com/user/project`.
+/// The clone URLs of Bitbucket Server like `ssh://git@host:7999/proj/repo.git`
+/// are mapped to `https://host/projects/PROJ/repos/repo`.
+pub fn web_url(remote: &str, provider: Option<Provider>) -> Option<String> {
+    let remote = remote.trim().trim_end_matches('/').trim_end_matches(".git");
+    let (host, path) = match remote.find("://") {
+        Some(index) => {
+            let rest = &remote[index + 3..];
+            let slash = rest.find('/')?;
+            (&rest[..slash], &rest[slash + 1..])
+        }
+        None => {
+            let colon = remote.find(':')?;
+            (&remote[..colon], &remote[colon + 1..])
+        }
+    };
+    let host = host.rsplit('@').next()?.split(':').next()?;
+    match provider.or_else(|| Provider::detect(host)) {
+        Some(Provider::BitbucketServer) => {
+            let mut parts = path.rsplit('/');
+            let repo = parts.next()?;
+            let project = parts.next()?;
+            Some(format!(
+                "https://{}/projects/{}/repos/{}",
+                host,
+                project.to_uppercase(),
+                repo
+            ))
+        }
+        _ => Some(format!("https://{}/{}", host, path)),
+    }
+}
+
+/// Returns the link to the Azure Boards work item of the project.
+pub fn work_item_url(url: &str, id: &str) -> String {
+    format!("{}/_workitems/edit/{}", url.trim_end_matches('/'), id)
+}
+
+/// Links the work item references like "AB#1234" within the text if the
+/// `azure_boards_url` is configured.
+pub fn link_work_items<'a>(text: &'a str, config: &Config) -> Cow<'a, str> {
+    match config.azure_boards_url {
+        Some(ref url) => RE_WORK_ITEM.replace_all(text, |c: &Captures| {
+            format!("[{}]({})", &c[0], work_item_url(url, &c[1]))
+        }),
+        None => Cow::Borrowed(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_providers() {
+        assert_eq!(
+            Provider::detect("https://github.com/a/b"),
+            Some(Provider::GitHub)
+        );
+        assert_eq!(
+            Provider::detect("https://gitlab.example.com/a/b"),
+            Some(Provider::GitLab)
+        );
+        assert_eq!(
+            Provider::detect("https://bitbucket.org/a/b"),
+            Some(Provider::Bitbucket)
+        );
+        assert_eq!(
+            Provider::detect("https://bitbucket.example.com/projects/A"),
+            Some(Provider::BitbucketServer)
+        );
+        assert_eq!(
+            Provider::detect("https://codeberg.org/a/b"),
+            Some(Provider::Gitea)
+        );
+        assert_eq!(Provider::detect("https://git.example.com/a/b"), None);
+        assert_eq!(Provider::from_name("forgejo").unwrap(), Provider::Gitea);
+        assert!(Provider::from_name("svn").is_err());
+    }
+
+    #[test]
+    fn provider_urls() {
+        let url = "https://host/a/b";
+        assert_eq!(
+            Provider::GitLab.commit_url(url, "abc"),
+            "https://host/a/b/-/commit/abc"
+        );
+        assert_eq!(
+            Provider::Bitbucket.commit_url(url, "abc"),
+            "https://host/a/b/commits/abc"
+        );
+        assert_eq!(
+            Provider::GitHub.compare_url(url, "v1", "v2"),
+            "https://host/a/b/compare/v1...v2"
+        );
+        assert_eq!(
+            Provider::Bitbucket.compare_url(url, "v1", "v2"),
+            "https://host/a/b/branches/compare/v2%0Dv1"
+        );
+        assert_eq!(
+            Provider::BitbucketServer.compare_url(url, "v1", "v2"),
+            "https://host/a/b/compare/commits?sourceBranch=v2&targetBranch=v1"
+        );
+        assert_eq!(
+            Provider::Gitea.pull_request_url(url),
+            "https://host/a/b/pulls/"
+        );
+    }
+
+    #[test]
+    fn work_item_links() {
+        let mut config = Config::new();
+        assert_eq!(link_work_items("Fixed AB#12", &config), "Fixed AB#12");
+        config.azure_boards_url =
+            Some("https://dev.azure.com/org/project/".to_owned());
+        assert_eq!(
+            link_work_items("Fixed AB#12 and AB#3, not TAB#4", &config),
+            "Fixed [AB#12](https://dev.azure.com/org/project/_workitems/edit/12) \
+             and [AB#3](https://dev.azure.com/org/project/_workitems/edit/3), \
+             not TAB#4"
+        );
+    }
+
+    #[test]
+    fn remote_web_urls() {
+        assert_eq!(
+            web_url("git@github.com:user/project.git", None).unwrap(),
+            "https://github.com/user/project"
+        );
+        assert_eq!(
+            web_url("https://user@gitea.example.com/user/project", None)
+                .unwrap(),
+            "https://gitea.example.com/user/project"
+        );
+        assert_eq!(
+            web_url(
+                "ssh://git@git.example.com:7999/proj/repo.git",
+                Some(Provider::BitbucketServer)
+            )
+            .unwrap(),
+            "https://git.example.com/projects/PROJ/repos/repo"
+        );
+        assert!(web_url("/local/path", None).is_none());
+    }
+}
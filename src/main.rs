@@ -1,8 +1,100 @@
 use clap::{crate_version, load_yaml, App, Shell};
 use failure::{bail, format_err, Error};
-use gitjournal::GitJournal;
-use log::info;
-use std::{env, fs};
+use gitjournal::{
+    annotations, quality, GitJournal, ParseOptions, Report, Warning,
+    JSON_SCHEMA,
+};
+use log::{info, warn};
+use std::{env, fs, io, path::Path};
+use yaml_rust::Yaml;
+
+#[cfg(feature = "tui")]
+fn browse(journal: &GitJournal) -> Result<(), Error> {
+    gitjournal::tui::browse(&journal.parsed())
+}
+
+#[cfg(not(feature = "tui"))]
+fn browse(_journal: &GitJournal) -> Result<(), Error> {
+    bail!("The terminal browser requires the 'tui' feature")
+}
+
+#[cfg(feature = "network")]
+fn notify(
+    journal: &GitJournal,
+    release: &str,
+    compact: bool,
+    template: Option<&str>,
+) -> Result<(), Error> {
+    journal.notify(release, compact, template)
+}
+
+#[cfg(not(feature = "network"))]
+fn notify(
+    _journal: &GitJournal,
+    _release: &str,
+    _compact: bool,
+    _template: Option<&str>,
+) -> Result<(), Error> {
+    bail!("The webhook notification requires the 'network' feature")
+}
+
+#[cfg(feature = "server")]
+fn serve(path: &str, address: &str) -> Result<(), Error> {
+    gitjournal::server::serve(path, address)
+}
+
+#[cfg(not(feature = "server"))]
+fn serve(_path: &str, _address: &str) -> Result<(), Error> {
+    bail!("The HTTP server requires the 'server' feature")
+}
+
+/// Verifies the commits of the range and prints the invalid ones, as CI
+/// annotations if requested or detected.
+fn verify_range(
+    journal: &GitJournal,
+    range: &str,
+    format: Option<&str>,
+) -> Result<(), Error> {
+    let warnings = journal.verify_range(range)?;
+    match format.or(annotations::detect()) {
+        Some("github") => print!("{}", annotations::github(&warnings)),
+        Some("gitlab") => print!("{}", annotations::gitlab(&warnings)),
+        _ => {
+            for warning in &warnings {
+                if let Warning::InvalidCommit { oid, reason, .. } = warning {
+                    warn!("Commit {:.7} is invalid: {}", oid, reason);
+                }
+            }
+        }
+    }
+    if !warnings.is_empty() {
+        bail!("{} invalid commit messages in '{}'", warnings.len(), range);
+    }
+    info!("All commit messages in '{}' are valid.", range);
+    Ok(())
+}
+
+/// Returns the output file of a branch, e.g. `CHANGELOG.release-1.x.md`.
+fn get_branch_path(output: &str, branch: &str) -> String {
+    let branch = branch.replace('/', "-");
+    let path = Path::new(output);
+    let file_name = match (path.file_stem(), path.extension()) {
+        (Some(stem), Some(extension)) => format!(
+            "{}.{}.{}",
+            stem.to_string_lossy(),
+            branch,
+            extension.to_string_lossy()
+        ),
+        _ => format!(
+            "{}.{}",
+            path.file_name().unwrap_or_default().to_string_lossy(),
+            branch
+        ),
+    };
+    path.with_file_name(file_name)
+        .to_string_lossy()
+        .into_owned()
+}
 
 fn is_program_in_path(program: &str) -> bool {
     if let Ok(path) = env::var("PATH") {
@@ -16,20 +108,109 @@ fn is_program_in_path(program: &str) -> bool {
     false
 }
 
+/// Adds the tag names as possible values to all arguments taking a git TAG,
+/// which is only used for the shell completions.
+fn add_tag_names(yaml: &mut Yaml, tag_names: &[String]) {
+    let key = |name: &str| Yaml::String(name.to_owned());
+    let tag_names =
+        Yaml::Array(tag_names.iter().map(|name| key(name)).collect());
+    if let Yaml::Hash(ref mut app) = *yaml {
+        if let Some(&mut Yaml::Array(ref mut args)) = app.get_mut(&key("args"))
+        {
+            for arg in args.iter_mut() {
+                if let Yaml::Hash(ref mut arg) = *arg {
+                    for settings in arg.values_mut() {
+                        if let Yaml::Hash(ref mut settings) = *settings {
+                            if settings.get(&key("value_name"))
+                                == Some(&key("TAG"))
+                            {
+                                settings.insert(
+                                    key("possible_values"),
+                                    tag_names.clone(),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 fn main() -> Result<(), Error> {
     // Load the CLI parameters from the yaml file
     let yaml = load_yaml!("cli.yaml");
-    let mut app = App::from_yaml(yaml).version(crate_version!());
-    let matches = app.clone().get_matches();
+    let matches = App::from_yaml(yaml).version(crate_version!()).get_matches();
     let path = matches
         .value_of("path")
         .ok_or_else(|| format_err!("No CLI 'path' provided"))?;
 
     // Create the journal
     let mut journal = GitJournal::new(path)?;
+    if let Some(revision) = matches.value_of("config_from") {
+        journal.load_config_from(revision)?;
+    }
+
+    // The application used for the completions knows the tag names
+    let mut completion_yaml = yaml.clone();
+    add_tag_names(
+        &mut completion_yaml,
+        &journal.tag_names().unwrap_or_default(),
+    );
+    let mut completion_app =
+        App::from_yaml(&completion_yaml).version(crate_version!());
 
     // Check for the subcommand
     match matches.subcommand_name() {
+        Some("bump") => {
+            // Bump the version of the manifests
+            if let Some(sub_matches) = matches.subcommand_matches("bump") {
+                journal.parse_log(&ParseOptions::new("HEAD"))?;
+                print!(
+                    "{}",
+                    journal
+                        .bump_cargo_version(sub_matches.is_present("write"))?
+                );
+            }
+        }
+        Some("completions") => {
+            // Print the completion script to stdout
+            if let Some(sub_matches) = matches.subcommand_matches("completions")
+            {
+                let shell = sub_matches
+                    .value_of("shell")
+                    .ok_or_else(|| format_err!("No CLI 'shell' provided"))?
+                    .parse::<Shell>()
+                    .map_err(|e| format_err!("{}", e))?;
+                completion_app.gen_completions_to(
+                    "git-journal",
+                    shell,
+                    &mut io::stdout(),
+                );
+            }
+        }
+        Some("pre-release") => {
+            // Write the changelog for the release of a new version
+            if let Some(sub_matches) = matches.subcommand_matches("pre-release")
+            {
+                let version = sub_matches
+                    .value_of("version")
+                    .map(|v| v.to_owned())
+                    .or_else(|| env::var("NEW_VERSION").ok());
+                let dry_run =
+                    env::var("DRY_RUN").ok().as_deref() == Some("true");
+                let changelog = journal.pre_release(
+                    version.as_deref(),
+                    sub_matches.value_of("output").ok_or_else(|| {
+                        format_err!("No CLI 'output' provided")
+                    })?,
+                    dry_run,
+                )?;
+                if dry_run {
+                    print!("{}", changelog);
+                }
+            }
+        }
         Some("prepare") => {
             // Prepare a commit message before editing by the user
             if let Some(sub_matches) = matches.subcommand_matches("prepare") {
@@ -41,39 +222,141 @@ fn main() -> Result<(), Error> {
                 ) {
                     Ok(()) => info!("Commit message prepared."),
                     Err(error) => {
-                        bail!("Commit message preparation failed {}", &error)
+                        bail!(
+                            "Commit message preparation failed {:#}",
+                            Report::from(error)
+                        )
                     }
                 }
             }
         }
+        Some("highlights") => {
+            // Print the highlights of a single release
+            if let Some(sub_matches) = matches.subcommand_matches("highlights")
+            {
+                journal.parse_log(&ParseOptions::new("HEAD").all(true))?;
+                println!(
+                    "{}",
+                    journal.highlights(sub_matches.value_of("release"))?
+                );
+            }
+        }
+        Some("quality") => {
+            // Print the commit hygiene of all releases
+            if let Some(sub_matches) = matches.subcommand_matches("quality") {
+                journal.parse_log(&ParseOptions::new("HEAD").all(true))?;
+                let qualities = journal.quality();
+                if sub_matches.is_present("json") {
+                    println!("{}", serde_json::to_string_pretty(&qualities)?);
+                } else {
+                    print!("{}", quality::table(&qualities));
+                }
+            }
+        }
+        Some("rebase") => {
+            // Print the todo list for an interactive rebase
+            if let Some(sub_matches) = matches.subcommand_matches("rebase") {
+                print!(
+                    "{}",
+                    journal.generate_rebase_todo(
+                        sub_matches.value_of("range").ok_or_else(|| {
+                            format_err!("No CLI 'range' provided")
+                        })?
+                    )?
+                );
+            }
+        }
+        Some("schema") => {
+            // Print the schema of the structured output
+            print!("{}", JSON_SCHEMA);
+        }
+        Some("serve") => {
+            // Serve the release notes until the process is stopped
+            if let Some(sub_matches) = matches.subcommand_matches("serve") {
+                serve(
+                    path,
+                    sub_matches.value_of("http").ok_or_else(|| {
+                        format_err!("No CLI 'http' address provided")
+                    })?,
+                )?;
+            }
+        }
         Some("setup") => {
             // Do the setup procedure
             journal.setup()?;
 
             // Generate completions if necessary
             if is_program_in_path("bash") {
-                app.gen_completions("git-journal", Shell::Bash, path);
+                completion_app.gen_completions(
+                    "git-journal",
+                    Shell::Bash,
+                    path,
+                );
                 info!("Installed bash completions to the current path.");
             }
             if is_program_in_path("fish") {
-                app.gen_completions("git-journal", Shell::Fish, path);
+                completion_app.gen_completions(
+                    "git-journal",
+                    Shell::Fish,
+                    path,
+                );
                 info!("Installed fish completions to the current path.");
             }
             if is_program_in_path("zsh") {
-                app.gen_completions("git-journal", Shell::Zsh, path);
+                completion_app.gen_completions("git-journal", Shell::Zsh, path);
                 info!("Installed zsh completions to the current path.");
             }
         }
+        Some("upgrade-guide") => {
+            // Print the breaking changes between two releases
+            if let Some(sub_matches) =
+                matches.subcommand_matches("upgrade-guide")
+            {
+                let release = |name: &str| {
+                    sub_matches.value_of(name).ok_or_else(|| {
+                        format_err!("No CLI '{}' release provided", name)
+                    })
+                };
+                journal.parse_log(&ParseOptions::new("HEAD").all(true))?;
+                print!(
+                    "{}",
+                    journal.upgrade_guide(release("from")?, release("to")?)?
+                );
+            }
+        }
         Some("verify") => {
             // Verify a commit message
             if let Some(sub_matches) = matches.subcommand_matches("verify") {
-                match journal.verify(
-                    sub_matches.value_of("message").ok_or_else(|| {
-                        format_err!("No CLI 'message' provided")
-                    })?,
-                ) {
+                if sub_matches.is_present("pre_merge") {
+                    journal.config.fixup_commits = "reject".to_owned();
+                }
+                if let Some(range) = sub_matches.value_of("range") {
+                    return verify_range(
+                        &journal,
+                        range,
+                        sub_matches.value_of("annotations"),
+                    );
+                }
+                let message = sub_matches
+                    .value_of("message")
+                    .ok_or_else(|| format_err!("No CLI 'message' provided"))?;
+                let format = sub_matches.is_present("format");
+                if format && journal.config.wrap_body_lines {
+                    journal.format(message)?;
+                }
+                match journal.verify(message) {
+                    Ok(()) if format => {
+                        journal.format(message)?;
+                        info!("Commit message valid and formatted.")
+                    }
                     Ok(()) => info!("Commit message valid."),
-                    Err(error) => bail!("Commit message invalid {}", &error),
+                    Err(error) => {
+                        eprintln!("{}", journal.grammar_help());
+                        bail!(
+                            "Commit message invalid {:#}",
+                            Report::from(error)
+                        )
+                    }
                 }
             }
         }
@@ -91,31 +374,232 @@ fn main() -> Result<(), Error> {
                 .value_of("tags_count")
                 .ok_or_else(|| format_err!("No CLI 'tags_count' provided"))?;
             let max_tags = tags_count.parse::<u32>()?;
-            let ignore_tags: Option<Vec<&str>> = matches
-                .value_of("ignore_tags")
-                .map(|s| s.split(",").collect());
+            let release = matches.value_of("release");
+
+            // A single release can only be found when parsing all tags
+            let mut options = ParseOptions::new(revision_range)
+                .tag_skip_pattern(tag_skip_pattern)
+                .max_tags_count(max_tags)
+                .all(matches.is_present("all") || release.is_some())
+                .skip_unreleased(matches.is_present("skip_unreleased"));
+            if let Some(ignore_tags) = matches.value_of("ignore_tags") {
+                options = options
+                    .ignore_tags(&ignore_tags.split(',').collect::<Vec<_>>());
+            }
+
+            // Override the configuration if needed
+            if matches.is_present("no_pager") {
+                journal.config.use_pager = false;
+            }
+            if matches.is_present("reproducible") {
+                journal.config.reproducible = true;
+            }
+            if journal.config.reproducible {
+                journal.config.colored_output = false;
+                journal.config.use_pager = false;
+            }
+            if matches.is_present("first_parent") {
+                journal.config.first_parent = true;
+            }
+            if let Some(sorting) = matches.value_of("revwalk_sorting") {
+                journal.config.revwalk_sorting =
+                    sorting.split(',').map(|s| s.to_owned()).collect();
+            }
+            if let Some(sorting) = matches.value_of("tag_sorting") {
+                journal.config.tag_sorting = sorting.to_owned();
+            }
+            if let Some(version) = matches.value_of("min_version") {
+                journal.config.min_version = Some(version.to_owned());
+            }
+            if let Some(major) = matches.value_of("major_version") {
+                journal.config.major_version = Some(major.parse()?);
+            }
+            if let Some(entries) = matches.values_of("add_entry") {
+                for entry in entries {
+                    journal.add_entry(entry)?;
+                }
+            }
+            if let Some(header) = matches.value_of("header") {
+                journal.config.changelog_header = Some(header.to_owned());
+            }
+            if let Some(footer) = matches.value_of("footer") {
+                journal.config.changelog_footer = Some(footer.to_owned());
+            }
+
+            // Parse and print the log of every matching branch separately
+            if let Some(pattern) = matches.value_of("branches") {
+                for (branch, parsed) in
+                    journal.parse_branches(pattern, &options)?
+                {
+                    let log = parsed.render(
+                        matches.is_present("short"),
+                        matches.value_of("template"),
+                    )?;
+                    match matches.value_of("output") {
+                        Some(output) => journal.write_output(
+                            &get_branch_path(output, &branch),
+                            log.into_bytes(),
+                            false,
+                        )?,
+                        None => print!("\n# Branch {}\n{}", branch, log),
+                    }
+                }
+                return Ok(());
+            }
+
+            // Print the cached log without parsing if the inputs are unchanged
+            let plain = [
+                "from_tag",
+                "only_categories",
+                "generate",
+                "tui",
+                "search",
+                "notify",
+                "release",
+                "github_actions",
+                "inject",
+            ]
+            .iter()
+            .all(|arg| !matches.is_present(arg));
+            if plain
+                && journal.config.cache_dir.is_some()
+                && journal.config.translation_profiles.is_empty()
+                && matches.value_of("format") == Some("markdown")
+            {
+                return journal.print_cached(
+                    &options,
+                    matches.is_present("short"),
+                    matches.value_of("template"),
+                    matches.value_of("output"),
+                );
+            }
 
             // Parse the log
-            if let Err(error) = journal.parse_log(
-                revision_range,
-                tag_skip_pattern,
-                &max_tags,
-                &matches.is_present("all"),
-                &matches.is_present("skip_unreleased"),
-                ignore_tags,
-            ) {
-                bail!("Log parsing error {}", &error);
+            let result = match matches.value_of("from_tag") {
+                Some(from_tag) => journal.parse_log_between_tags(
+                    from_tag,
+                    matches.value_of("to_tag"),
+                    &options,
+                ),
+                None => journal.parse_log(&options),
+            };
+            match result {
+                Ok(warnings) => {
+                    for warning in warnings {
+                        warn!("{}", warning);
+                    }
+                }
+                Err(error) => {
+                    bail!("Log parsing error {:#}", Report::from(error))
+                }
+            }
+            if let Some(categories) = matches.value_of("only_categories") {
+                journal.retain_categories(
+                    &categories.split(',').collect::<Vec<_>>(),
+                );
             }
 
             // Generate the template or print the log
+            let format = matches.value_of("format").unwrap_or("markdown");
             if matches.is_present("generate") {
                 journal.generate_template()?;
+            } else if matches.is_present("tui") {
+                browse(&journal)?;
+            } else if let Some(keyword) = matches.value_of("search") {
+                let results = journal.render_search(
+                    keyword,
+                    matches.is_present("short"),
+                    matches.value_of("template"),
+                )?;
+                match matches.value_of("output") {
+                    Some(output) => journal.write_output(
+                        output,
+                        results.into_bytes(),
+                        false,
+                    )?,
+                    None => print!("{}", results),
+                }
+            } else if format == "email" {
+                let message = journal.announce(
+                    release.ok_or_else(|| {
+                        format_err!("No CLI 'release' provided")
+                    })?,
+                    matches.is_present("short"),
+                    matches.value_of("template"),
+                )?;
+                match matches.value_of("output") {
+                    Some(output) => journal.write_output(
+                        output,
+                        message.into_bytes(),
+                        false,
+                    )?,
+                    None => print!("{}", message),
+                }
+            } else if format == "mdbook" {
+                journal.write_mdbook(
+                    matches.value_of("output").ok_or_else(|| {
+                        format_err!("No CLI 'output' directory provided")
+                    })?,
+                    matches.is_present("short"),
+                    matches.value_of("template"),
+                )?;
+            } else if format == "hugo" || format == "zola" {
+                journal.write_site(
+                    matches.value_of("output").ok_or_else(|| {
+                        format_err!("No CLI 'output' directory provided")
+                    })?,
+                    format,
+                    matches.is_present("short"),
+                    matches.value_of("template"),
+                )?;
+            } else if format != "markdown" {
+                match matches.value_of("output") {
+                    Some(output) => journal.write_export(format, output)?,
+                    None => print!("{}", journal.export(format)?),
+                }
+            } else if matches.is_present("notify") {
+                notify(
+                    &journal,
+                    release.ok_or_else(|| {
+                        format_err!("No CLI 'release' provided")
+                    })?,
+                    matches.is_present("short"),
+                    matches.value_of("template"),
+                )?;
+            } else if let Some(path) = matches.value_of("inject") {
+                journal.inject_release_notes(
+                    path,
+                    release,
+                    matches.is_present("short"),
+                    matches.value_of("template"),
+                )?;
+            } else if let Some(release) = release {
+                journal.print_release(
+                    release,
+                    matches.is_present("short"),
+                    matches.value_of("template"),
+                    matches.value_of("output"),
+                )?;
             } else {
                 journal.print_log(
                     matches.is_present("short"),
                     matches.value_of("template"),
                     matches.value_of("output"),
                 )?;
+                if let Some(output) = matches.value_of("output") {
+                    journal.print_translated_logs(
+                        matches.is_present("short"),
+                        matches.value_of("template"),
+                        output,
+                    )?;
+                }
+            }
+            if matches.is_present("github_actions") {
+                journal.write_github_outputs(
+                    release,
+                    matches.is_present("short"),
+                    matches.value_of("template"),
+                )?;
             }
         }
     };
@@ -0,0 +1,55 @@
+//! Injection of the release notes into arbitrary files like a README, where
+//! only the region between the journal markers is replaced.
+
+use failure::{bail, Error};
+
+/// The marker before the injected release notes.
+pub static START_MARKER: &str = "<!-- journal:start -->";
+
+/// The marker after the injected release notes.
+pub static END_MARKER: &str = "<!-- journal:end -->";
+
+/// Replaces the region between the first start and the following end marker
+/// of the content with the notes, keeping the markers themselves.
+///
+/// # Errors
+/// If one of the markers is missing.
+pub fn replace(content: &str, notes: &str) -> Result<String, Error> {
+    let start = match content.find(START_MARKER) {
+        Some(start) => start + START_MARKER.len(),
+        None => bail!("The start marker '{}' is missing.", START_MARKER),
+    };
+    let end = match content[start..].find(END_MARKER) {
+        Some(end) => start + end,
+        None => bail!("The end marker '{}' is missing.", END_MARKER),
+    };
+    Ok(format!(
+        "{}\n{}\n{}",
+        &content[..start],
+        notes.trim(),
+        &content[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_between_markers() {
+        let content = "# Project\n\n<!-- journal:start -->\nOld\n\
+                       <!-- journal:end -->\n\n## Usage\n";
+        let replaced = replace(content, "\n# v2\n- [Added] a\n\n").unwrap();
+        assert_eq!(
+            replaced,
+            "# Project\n\n<!-- journal:start -->\n# v2\n- [Added] a\n\
+             <!-- journal:end -->\n\n## Usage\n"
+        );
+        assert_eq!(replace(&replaced, "# v2\n- [Added] a").unwrap(), replaced);
+
+        assert!(replace("<!-- journal:end -->", "").is_err());
+        assert!(
+            replace("<!-- journal:end --><!-- journal:start -->", "").is_err()
+        );
+    }
+}
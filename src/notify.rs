@@ -0,0 +1,75 @@
+//! Announcement of rendered releases via chat webhooks, which is only
+//! available if the `network` feature is enabled.
+
+use failure::{bail, Error};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// The maximum length of a Discord message in characters.
+static DISCORD_MAX_LENGTH: usize = 2000;
+
+/// Posts the rendered release to the webhook, where the format is one of
+/// "slack", "teams" or "discord".
+pub fn post(
+    url: &str,
+    format: &str,
+    title: &str,
+    text: &str,
+) -> Result<(), Error> {
+    let payload = payload(format, title, text)?;
+    ureq::post(url)
+        .timeout(Duration::from_secs(10))
+        .send_json(payload)?;
+    Ok(())
+}
+
+/// Returns the JSON payload of the webhook for the given format.
+fn payload(format: &str, title: &str, text: &str) -> Result<Value, Error> {
+    let text = text.trim();
+    Ok(match format {
+        "slack" => json!({ "text": text }),
+        "teams" => json!({
+            "@type": "MessageCard",
+            "@context": "https://schema.org/extensions",
+            "summary": title,
+            "title": title,
+            "text": text,
+        }),
+        "discord" => {
+            let content = if text.chars().count() > DISCORD_MAX_LENGTH {
+                let mut content: String =
+                    text.chars().take(DISCORD_MAX_LENGTH - 1).collect();
+                content.push('…');
+                content
+            } else {
+                text.to_owned()
+            };
+            json!({ "content": content })
+        }
+        _ => bail!("Unknown webhook format '{}'", format),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn webhook_payloads() {
+        assert_eq!(
+            payload("slack", "v1", "\n# v1\n- [Added] a\n").unwrap(),
+            json!({ "text": "# v1\n- [Added] a" })
+        );
+        let teams = payload("teams", "v1", "- [Added] a").unwrap();
+        assert_eq!(teams["title"], "v1");
+        assert_eq!(teams["text"], "- [Added] a");
+
+        let long = "a".repeat(DISCORD_MAX_LENGTH + 1);
+        let discord = payload("discord", "v1", &long).unwrap();
+        let content = discord["content"].as_str().unwrap();
+        assert_eq!(content.chars().count(), DISCORD_MAX_LENGTH);
+        assert!(content.ends_with('…'));
+
+        assert!(payload("irc", "v1", "").is_err());
+    }
+}
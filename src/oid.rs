@@ -0,0 +1,80 @@
+//! The object id of the commits, which is the one of `git2` if the `git`
+//! feature is enabled. Without it only the parser is available, for example
+//! on `wasm32-unknown-unknown`, and the ids are a plain SHA-1 hash instead.
+
+#[cfg(feature = "git")]
+pub use git2::Oid;
+
+#[cfg(not(feature = "git"))]
+pub use self::plain::Oid;
+
+#[cfg(not(feature = "git"))]
+mod plain {
+    use failure::{format_err, Error};
+    use std::fmt;
+
+    /// A SHA-1 object id, which behaves like the one of `git2`.
+    #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Oid([u8; 20]);
+
+    impl Oid {
+        /// Parses a full or abbreviated hex string, where the missing digits
+        /// are filled with zeros.
+        ///
+        /// # Errors
+        /// If the string is empty, too long or no hex string.
+        pub fn from_str(s: &str) -> Result<Self, Error> {
+            if s.is_empty() || s.len() > 40 {
+                return Err(format_err!("Invalid object id '{}'", s));
+            }
+            let mut bytes = [0; 20];
+            for (index, digit) in s.chars().enumerate() {
+                let value = digit
+                    .to_digit(16)
+                    .ok_or_else(|| format_err!("Invalid object id '{}'", s))?
+                    as u8;
+                bytes[index / 2] |= value << (4 * (1 - index % 2));
+            }
+            Ok(Oid(bytes))
+        }
+
+        /// Returns the raw bytes of the id.
+        pub fn as_bytes(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    impl fmt::Display for Oid {
+        /// Prints the hex string, which can be abbreviated via the precision
+        /// like `{:.7}`.
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let hex: String =
+                self.0.iter().map(|byte| format!("{:02x}", byte)).collect();
+            f.pad(&hex)
+        }
+    }
+
+    impl fmt::Debug for Oid {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}", self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oid_from_str() {
+        let oid = Oid::from_str("0123456789abcdef").unwrap();
+        assert_eq!(
+            oid.to_string(),
+            format!("0123456789abcdef{}", "0".repeat(24))
+        );
+        assert_eq!(format!("{:.7}", oid), "0123456");
+        assert_eq!(oid.as_bytes()[..2], [0x01, 0x23]);
+        assert!(Oid::from_str("xyz").is_err());
+        assert!(Oid::from_str(&"1".repeat(41)).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//! The options for parsing the log of a repository.
+
+use crate::error::ErrorKind;
+use failure::Error;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Selects which part of the log will be parsed by `GitJournal::parse_log`.
+/// The options are created via `ParseOptions::new` and adapted via their
+/// builder methods, every option not set explicitly keeps its default.
+///
+/// # Examples
+///
+/// ```
+/// use gitjournal::ParseOptions;
+///
+/// let options = ParseOptions::new("HEAD~10..HEAD")
+///     .tag_skip_pattern("beta")
+///     .all(true)
+///     .skip_unreleased(true);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseOptions {
+    pub(crate) revision_range: String,
+    pub(crate) tag_skip_pattern: String,
+    pub(crate) max_tags_count: u32,
+    pub(crate) all: bool,
+    pub(crate) skip_unreleased: bool,
+    pub(crate) ignore_tags: Option<Vec<String>>,
+    pub(crate) cancellation: Option<Cancellation>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new("HEAD")
+    }
+}
+
+impl ParseOptions {
+    /// Constructs new options for the given revision range, which can be a
+    /// single commit like `HEAD` or a range like `v1.0.0..HEAD`. By default
+    /// tags containing `rc` will be skipped and only the latest release will
+    /// be parsed together with the unreleased commits.
+    pub fn new(revision_range: &str) -> Self {
+        ParseOptions {
+            revision_range: revision_range.to_owned(),
+            tag_skip_pattern: "rc".to_owned(),
+            max_tags_count: 1,
+            all: false,
+            skip_unreleased: false,
+            ignore_tags: None,
+            cancellation: None,
+        }
+    }
+
+    /// Returns the options which influence the parsing results as text,
+    /// which is part of the key of the cached logs.
+    #[cfg(feature = "git")]
+    pub(crate) fn cache_key(&self) -> String {
+        format!(
+            "{}\n{}\n{}\n{}\n{}\n{:?}",
+            self.revision_range,
+            self.tag_skip_pattern,
+            self.max_tags_count,
+            self.all,
+            self.skip_unreleased,
+            self.ignore_tags
+        )
+    }
+
+    /// Sets the revision range to be parsed.
+    pub fn revision_range(mut self, revision_range: &str) -> Self {
+        self.revision_range = revision_range.to_owned();
+        self
+    }
+
+    /// Skips every tag whose name contains the given pattern. An empty
+    /// pattern skips no tags at all.
+    pub fn tag_skip_pattern(mut self, tag_skip_pattern: &str) -> Self {
+        self.tag_skip_pattern = tag_skip_pattern.to_owned();
+        self
+    }
+
+    /// Sets the number of releases to be parsed, which is ignored if `all`
+    /// releases should be parsed.
+    pub fn max_tags_count(mut self, max_tags_count: u32) -> Self {
+        self.max_tags_count = max_tags_count;
+        self
+    }
+
+    /// Parses all releases within the revision range.
+    pub fn all(mut self, all: bool) -> Self {
+        self.all = all;
+        self
+    }
+
+    /// Skips the unreleased commits on top of the latest release.
+    pub fn skip_unreleased(mut self, skip_unreleased: bool) -> Self {
+        self.skip_unreleased = skip_unreleased;
+        self
+    }
+
+    /// Excludes the commits containing one of the given tags.
+    pub fn ignore_tags<T: AsRef<str>>(mut self, ignore_tags: &[T]) -> Self {
+        self.ignore_tags =
+            Some(ignore_tags.iter().map(|t| t.as_ref().to_owned()).collect());
+        self
+    }
+
+    /// Aborts the parsing with an `ErrorKind::Cancelled` error as soon as the
+    /// given cancellation is triggered.
+    pub fn cancellation(mut self, cancellation: &Cancellation) -> Self {
+        self.cancellation = Some(cancellation.clone());
+        self
+    }
+
+    /// Fails if the parsing has been cancelled.
+    #[cfg_attr(not(feature = "git"), allow(dead_code))]
+    pub(crate) fn check_cancelled(&self) -> Result<(), Error> {
+        match self.cancellation {
+            Some(ref cancellation) if cancellation.is_cancelled() => {
+                Err(ErrorKind::Cancelled.into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A token to abort long running operations like `GitJournal::parse_log`
+/// from another thread, for example if the user closed the window showing
+/// the results. All clones of a token share the same state.
+///
+/// # Examples
+///
+/// ```
+/// use gitjournal::{Cancellation, GitJournal, ParseOptions};
+/// use std::thread;
+///
+/// let cancellation = Cancellation::new();
+/// let handle = {
+///     let cancellation = cancellation.clone();
+///     thread::spawn(move || {
+///         let mut journal = GitJournal::new(".").unwrap();
+///         let options = ParseOptions::new("HEAD")
+///             .all(true)
+///             .cancellation(&cancellation);
+///         journal.parse_log(&options).is_ok()
+///     })
+/// };
+/// cancellation.cancel();
+/// handle.join().unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Cancellation(Arc<AtomicBool>);
+
+impl Cancellation {
+    /// Constructs a new token, which is not cancelled yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the operations using this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst)
+    }
+
+    /// Returns whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+impl PartialEq for Cancellation {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_builder() {
+        let options = ParseOptions::new("v1..v2")
+            .tag_skip_pattern("")
+            .max_tags_count(3)
+            .all(true)
+            .skip_unreleased(true)
+            .ignore_tags(&["internal"]);
+        assert_eq!(options.revision_range, "v1..v2");
+        assert_eq!(options.tag_skip_pattern, "");
+        assert_eq!(options.max_tags_count, 3);
+        assert!(options.all);
+        assert!(options.skip_unreleased);
+        assert_eq!(options.ignore_tags, Some(vec!["internal".to_owned()]));
+        assert_eq!(
+            ParseOptions::default().revision_range("v1..v2"),
+            ParseOptions::new("v1..v2")
+        );
+    }
+
+    #[test]
+    fn cancel_options() {
+        let cancellation = Cancellation::new();
+        let options = ParseOptions::new("HEAD").cancellation(&cancellation);
+        assert!(options.check_cancelled().is_ok());
+        assert_ne!(options, ParseOptions::new("HEAD"));
+        assert_ne!(
+            options,
+            ParseOptions::new("HEAD").cancellation(&Cancellation::new())
+        );
+
+        cancellation.clone().cancel();
+        assert!(cancellation.is_cancelled());
+        assert!(options.check_cancelled().is_err());
+    }
+}
@@ -0,0 +1,545 @@
+//! Everything related to the terminal output. The printing layer writes to a
+//! `ColorWriter` trait object, which decouples the log generation from the
+//! terminal coloring. Colored output via ANSI escape sequences is only
+//! available if the `color` feature is enabled and the terminal supports it,
+//! which includes the Windows console via its virtual terminal processing.
+
+use crate::width;
+use failure::Error;
+use serde_derive::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    env,
+    ffi::OsString,
+    io::{self, prelude::*, IsTerminal},
+    process::{Child, Command, Stdio},
+    rc::Rc,
+};
+
+/// The colors used for the terminal output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Color {
+    /// Used for the tags of releases
+    BrightBlue,
+
+    /// Used for section headers and footers
+    BrightRed,
+
+    /// Used for the release names
+    Green,
+
+    /// Used for the text of categories
+    White,
+
+    /// Used for the release dates
+    Yellow,
+}
+
+/// A writer which is able to change the foreground color of its output.
+pub trait ColorWriter: Write {
+    /// Sets the foreground color for everything written afterwards.
+    fn fg(&mut self, color: Color) -> Result<(), Error>;
+
+    /// Resets the color to the default of the writer.
+    fn reset(&mut self) -> Result<(), Error>;
+
+    /// Finishes the output after everything has been written.
+    fn finish(&mut self) -> Result<(), Error> {
+        self.flush()?;
+        Ok(())
+    }
+}
+
+/// A writer to stdout without any colors.
+pub struct PlainWriter(io::Stdout);
+
+impl PlainWriter {
+    /// Constructs a new `PlainWriter` for stdout.
+    pub fn new() -> Self {
+        PlainWriter(io::stdout())
+    }
+}
+
+impl Default for PlainWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Write for PlainWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl ColorWriter for PlainWriter {
+    fn fg(&mut self, _color: Color) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// A writer which discards everything written to it, used for rendering
+/// without any terminal output.
+#[derive(Default)]
+pub struct SilentWriter;
+
+impl Write for SilentWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl ColorWriter for SilentWriter {
+    fn fg(&mut self, _color: Color) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// A part of the output recorded by a `Recorder`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Segment {
+    /// Written text
+    Text(String),
+
+    /// A change of the foreground color
+    Fg(Color),
+
+    /// A reset of the color
+    Reset,
+}
+
+/// A writer which records the output including its colors instead of
+/// printing it, where the segments are shared with the handle returned by
+/// `Recorder::new`. The recorded output can be printed later via `replay`.
+pub struct Recorder(Rc<RefCell<Vec<Segment>>>);
+
+impl Recorder {
+    /// Constructs a new `Recorder` and the handle of its segments.
+    pub fn new() -> (Self, Rc<RefCell<Vec<Segment>>>) {
+        let segments = Rc::new(RefCell::new(vec![]));
+        (Recorder(segments.clone()), segments)
+    }
+}
+
+impl Write for Recorder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        let mut segments = self.0.borrow_mut();
+        match segments.last_mut() {
+            Some(Segment::Text(ref mut last)) => last.push_str(&text),
+            _ => segments.push(Segment::Text(text.into_owned())),
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl ColorWriter for Recorder {
+    fn fg(&mut self, color: Color) -> Result<(), Error> {
+        self.0.borrow_mut().push(Segment::Fg(color));
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), Error> {
+        self.0.borrow_mut().push(Segment::Reset);
+        Ok(())
+    }
+}
+
+/// Writes the segments recorded by a `Recorder` to the writer.
+pub fn replay(
+    segments: &[Segment],
+    term: &mut dyn ColorWriter,
+) -> Result<(), Error> {
+    for segment in segments {
+        match *segment {
+            Segment::Text(ref text) => term.write_all(text.as_bytes())?,
+            Segment::Fg(color) => term.fg(color)?,
+            Segment::Reset => term.reset()?,
+        }
+    }
+    Ok(())
+}
+
+/// Returns the ANSI escape sequence code of the color.
+fn ansi_code(color: Color) -> u8 {
+    match color {
+        Color::BrightBlue => 94,
+        Color::BrightRed => 91,
+        Color::Green => 32,
+        Color::White => 37,
+        Color::Yellow => 33,
+    }
+}
+
+/// A colored writer to stdout using ANSI escape sequences.
+#[cfg(feature = "color")]
+pub struct AnsiWriter(io::Stdout);
+
+#[cfg(feature = "color")]
+impl AnsiWriter {
+    /// Constructs a new `AnsiWriter` if stdout is a terminal which supports
+    /// colors.
+    pub fn new() -> Option<Self> {
+        if supports_color() {
+            Some(AnsiWriter(io::stdout()))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "color")]
+impl Write for AnsiWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+#[cfg(feature = "color")]
+impl ColorWriter for AnsiWriter {
+    fn fg(&mut self, color: Color) -> Result<(), Error> {
+        write!(self.0, "\x1b[{}m", ansi_code(color))?;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), Error> {
+        write!(self.0, "\x1b[0m")?;
+        Ok(())
+    }
+}
+
+/// Returns whether stdout supports colored output, where `NO_COLOR` disables
+/// and `CLICOLOR_FORCE` enforces the colors.
+pub fn supports_color() -> bool {
+    forced_color(|key| env::var_os(key)).unwrap_or_else(|| {
+        env::var("TERM").map_or(true, |term| term != "dumb")
+            && io::stdout().is_terminal()
+            && enable_ansi_support()
+    })
+}
+
+/// Returns whether the colors are disabled by `NO_COLOR` or enforced by
+/// `CLICOLOR_FORCE`, where the environment variables are looked up via `var`.
+fn forced_color<F>(var: F) -> Option<bool>
+where
+    F: Fn(&str) -> Option<OsString>,
+{
+    if var("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        Some(false)
+    } else if var("CLICOLOR_FORCE").is_some_and(|v| v != *"0") {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// Enables the processing of ANSI escape sequences, which is always the case
+/// for terminals on unix.
+#[cfg(not(windows))]
+fn enable_ansi_support() -> bool {
+    true
+}
+
+/// Enables the processing of ANSI escape sequences, which fails on consoles
+/// older than Windows 10. Terminals like the one of Git Bash are no consoles
+/// at all, but support the sequences natively.
+#[cfg(windows)]
+fn enable_ansi_support() -> bool {
+    use winapi::um::{
+        consoleapi::{GetConsoleMode, SetConsoleMode},
+        processenv::GetStdHandle,
+        winbase::STD_OUTPUT_HANDLE,
+        wincon::ENABLE_VIRTUAL_TERMINAL_PROCESSING,
+    };
+    unsafe {
+        let handle = GetStdHandle(STD_OUTPUT_HANDLE);
+        let mut mode = 0;
+        if GetConsoleMode(handle, &mut mode) == 0 {
+            return true;
+        }
+        mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+            || SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING)
+                != 0
+    }
+}
+
+/// A writer which buffers the output using ANSI colors and shows it in a
+/// pager if it does not fit into the terminal. Lines which are wider than
+/// the terminal count as multiple rows.
+pub struct PagedWriter {
+    buffer: Vec<u8>,
+    width: usize,
+    height: usize,
+    command: String,
+}
+
+impl PagedWriter {
+    /// Constructs a new `PagedWriter` for the given terminal size, which uses
+    /// the configured pager like `core.pager` if `$GIT_PAGER` is not set.
+    pub fn new(width: usize, height: usize, pager: Option<&str>) -> Self {
+        PagedWriter {
+            buffer: vec![],
+            width,
+            height,
+            command: Self::get_pager_command(pager, |key| env::var(key).ok()),
+        }
+    }
+
+    /// Returns the pager command in the order of precedence of git, which is
+    /// `$GIT_PAGER`, the configured pager, `$PAGER` and `less`, where the
+    /// environment variables are looked up via `var`.
+    fn get_pager_command<F>(pager: Option<&str>, var: F) -> String
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        var("GIT_PAGER")
+            .or_else(|| pager.map(str::to_owned))
+            .or_else(|| var("PAGER"))
+            .unwrap_or_else(|| "less".to_owned())
+    }
+
+    fn spawn(&self) -> io::Result<Child> {
+        Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .env(
+                "LESS",
+                env::var("LESS").unwrap_or_else(|_| "FRX".to_owned()),
+            )
+            .stdin(Stdio::piped())
+            .spawn()
+    }
+
+    fn page(&self, mut child: Child) -> Result<(), Error> {
+        if let Some(ref mut stdin) = child.stdin {
+            // The pager may be quit before everything has been written
+            if let Err(e) = stdin.write_all(&self.buffer) {
+                if e.kind() != io::ErrorKind::BrokenPipe {
+                    return Err(e.into());
+                }
+            }
+        }
+        child.wait()?;
+        Ok(())
+    }
+}
+
+impl Write for PagedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl ColorWriter for PagedWriter {
+    fn fg(&mut self, color: Color) -> Result<(), Error> {
+        write!(self.buffer, "\x1b[{}m", ansi_code(color))?;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), Error> {
+        write!(self.buffer, "\x1b[0m")?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        let rows =
+            width::rows(&String::from_utf8_lossy(&self.buffer), self.width);
+        if rows >= self.height
+            && !self.command.is_empty()
+            && self.command != "cat"
+        {
+            // Only fall back to stdout if the pager could not be started,
+            // since it may have shown parts of the output already otherwise
+            if let Ok(child) = self.spawn() {
+                return self.page(child);
+            }
+        }
+        let mut stdout = io::stdout();
+        stdout.write_all(&self.buffer)?;
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+/// Returns the width and height of the terminal if stdout is a terminal.
+#[cfg(unix)]
+pub fn terminal_size() -> Option<(usize, usize)> {
+    unsafe {
+        if libc::isatty(libc::STDOUT_FILENO) != 1 {
+            return None;
+        }
+        let mut size: libc::winsize = std::mem::zeroed();
+        if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) == 0
+            && size.ws_row > 0
+        {
+            Some((size.ws_col as usize, size.ws_row as usize))
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the width and height of the terminal if stdout is a terminal.
+#[cfg(not(unix))]
+pub fn terminal_size() -> Option<(usize, usize)> {
+    None
+}
+
+/// Returns a paging writer if stdout is a terminal, which uses the given
+/// pager command like the `core.pager` of git if `$GIT_PAGER` is not set.
+pub fn pager(pager: Option<&str>) -> Option<Box<dyn ColorWriter>> {
+    terminal_size().map(|(width, height)| {
+        Box::new(PagedWriter::new(width, height, pager)) as Box<dyn ColorWriter>
+    })
+}
+
+/// Returns the writer for stdout, which will be colored if the `color`
+/// feature is enabled and stdout is a terminal supporting colors.
+pub fn stdout() -> Box<dyn ColorWriter> {
+    #[cfg(feature = "color")]
+    {
+        if let Some(writer) = AnsiWriter::new() {
+            return Box::new(writer);
+        }
+    }
+    Box::new(PlainWriter::new())
+}
+
+/// Returns the writer for printing the log, which is the pager if `use_pager`
+/// is set and stdout is a terminal, otherwise stdout.
+pub fn terminal(
+    use_pager: bool,
+    command: Option<&str>,
+) -> Box<dyn ColorWriter> {
+    match pager(command) {
+        Some(pager) if use_pager => pager,
+        _ => stdout(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn silent_writer() {
+        let mut writer = SilentWriter;
+        assert!(writer.fg(Color::Green).is_ok());
+        assert_eq!(writer.write(b"Hello").unwrap(), 5);
+        assert!(writer.reset().is_ok());
+    }
+
+    #[test]
+    fn recorder() {
+        let (mut writer, segments) = Recorder::new();
+        write!(writer, "# ").unwrap();
+        writer.fg(Color::Green).unwrap();
+        write!(writer, "v1").unwrap();
+        write!(writer, ".0").unwrap();
+        writer.reset().unwrap();
+        writeln!(writer).unwrap();
+        let recorded = segments.borrow().clone();
+        assert_eq!(
+            recorded,
+            vec![
+                Segment::Text("# ".to_owned()),
+                Segment::Fg(Color::Green),
+                Segment::Text("v1.0".to_owned()),
+                Segment::Reset,
+                Segment::Text("\n".to_owned()),
+            ]
+        );
+
+        // Replaying to another recorder yields the same segments
+        let (mut replayed, copy) = Recorder::new();
+        replay(&recorded, &mut replayed).unwrap();
+        assert_eq!(*copy.borrow(), recorded);
+    }
+
+    #[test]
+    fn paged_writer() {
+        let mut writer = PagedWriter::new(80, 10, None);
+        assert!(writer.fg(Color::Green).is_ok());
+        write!(writer, "Hello").unwrap();
+        assert!(writer.reset().is_ok());
+        assert_eq!(writer.buffer, b"\x1b[32mHello\x1b[0m");
+        assert!(writer.finish().is_ok());
+
+        // The exit status of the pager does not matter
+        let mut writer = PagedWriter::new(80, 1, None);
+        writer.command = "cat >/dev/null; exit 1".to_owned();
+        write!(writer, "a\nb\n").unwrap();
+        assert!(writer.finish().is_ok());
+    }
+
+    #[test]
+    fn pager_command() {
+        let vars = |git_pager: Option<&'static str>| {
+            move |key: &str| match key {
+                "GIT_PAGER" => git_pager.map(str::to_owned),
+                "PAGER" => Some("more".to_owned()),
+                _ => None,
+            }
+        };
+        assert_eq!(
+            PagedWriter::get_pager_command(Some("less -S"), vars(None)),
+            "less -S"
+        );
+        assert_eq!(PagedWriter::get_pager_command(None, vars(None)), "more");
+        assert_eq!(
+            PagedWriter::get_pager_command(Some("less -S"), vars(Some("most"))),
+            "most"
+        );
+        assert_eq!(PagedWriter::get_pager_command(None, |_| None), "less");
+    }
+
+    #[test]
+    fn color_support() {
+        let vars = |no_color: &'static str, force: &'static str| {
+            move |key: &str| match key {
+                "NO_COLOR" => Some(no_color.into()),
+                "CLICOLOR_FORCE" => Some(force.into()),
+                _ => None,
+            }
+        };
+        assert_eq!(forced_color(vars("", "1")), Some(true));
+        assert_eq!(forced_color(vars("1", "1")), Some(false));
+        assert_eq!(forced_color(vars("", "0")), None);
+        assert_eq!(forced_color(|_| None), None);
+    }
+
+    #[test]
+    fn stdout_writer() {
+        let mut writer = stdout();
+        assert!(writer.fg(Color::Yellow).is_ok());
+        assert!(writer.reset().is_ok());
+    }
+}
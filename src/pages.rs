@@ -0,0 +1,163 @@
+//! Pages per release for documentation sites like mdBook or static site
+//! generators like Zola and Hugo, where every release is written to its own
+//! Markdown file.
+
+use crate::{config::Config, parser::ParsedTag};
+use failure::{bail, Error};
+use toml::{map::Map, Value};
+
+/// Returns the file name of the page of a release, where all characters
+/// except alphanumerics, dots and underscores are replaced by dashes, e.g.
+/// "v1.0.0-v1.0.md" for the merged tags "v1.0.0 / v1.0".
+pub fn file_name(release: &str) -> String {
+    let mut name = String::new();
+    for c in release.chars() {
+        if c.is_alphanumeric() || c == '.' || c == '_' {
+            name.push(c);
+        } else if !name.is_empty() && !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+    format!("{}.md", name.trim_end_matches('-'))
+}
+
+/// Returns the fragment of the `SUMMARY.md` of an mdBook, which links the
+/// pages of all releases as nested chapters within the directory.
+pub fn mdbook_summary(
+    directory: &str,
+    releases: &[(String, String)],
+) -> String {
+    releases
+        .iter()
+        .map(|(release, file_name)| {
+            format!("- [{}]({}/{})\n", release, directory, file_name)
+        })
+        .collect()
+}
+
+/// Returns the page of a release for the content directory of a static site
+/// generator, which is either "zola" or "hugo". The TOML front matter contains
+/// the release as title and version, its date and the translated categories
+/// of its commits as taxonomy terms, where the date is in the configured
+/// timezone. The rendered release header of the notes is omitted, since the
+/// sites render the title themselves.
+///
+/// # Errors
+/// If the static site generator or the timezone is unknown.
+pub fn site_page(
+    generator: &str,
+    tag: &ParsedTag,
+    notes: &str,
+    config: &Config,
+) -> Result<String, Error> {
+    let categories = config
+        .categories
+        .iter()
+        .filter(|category| {
+            tag.commits
+                .iter()
+                .any(|commit| &commit.summary.category == *category)
+        })
+        .map(|category| Value::String(config.translate(category).to_owned()))
+        .collect::<Vec<_>>();
+    let table = |key: &str, value: Value| {
+        let mut table = Map::new();
+        table.insert(key.to_owned(), value);
+        Value::Table(table)
+    };
+    let version = Value::String(tag.name.clone());
+    let mut front_matter = Map::new();
+    front_matter.insert("title".to_owned(), version.clone());
+    let date = tag.zoned_date(config)?.to_rfc3339();
+    front_matter.insert("date".to_owned(), Value::String(date));
+    match generator {
+        "zola" => {
+            front_matter.insert("extra".to_owned(), table("version", version));
+            front_matter.insert(
+                "taxonomies".to_owned(),
+                table("categories", Value::Array(categories)),
+            );
+        }
+        "hugo" => {
+            front_matter.insert("params".to_owned(), table("version", version));
+            front_matter
+                .insert("categories".to_owned(), Value::Array(categories));
+        }
+        _ => bail!("Unknown static site generator '{}'", generator),
+    }
+
+    let notes = notes.trim_start();
+    let body = match notes.split_once('\n') {
+        Some((header, body)) if header.starts_with('#') => body.trim_start(),
+        None if notes.starts_with('#') => "",
+        _ => notes,
+    };
+    Ok(format!(
+        "+++\n{}+++\n\n{}",
+        toml::to_string(&Value::Table(front_matter))?,
+        body
+    ))
+}
+
+/// Returns the index of the release notes section of a static site generator
+/// like `site_page`, which lists the newest releases first.
+pub fn site_index(generator: &str) -> String {
+    match generator {
+        "zola" => "+++\ntitle = \"Release notes\"\nsort_by = \"date\"\n+++\n",
+        _ => "+++\ntitle = \"Release notes\"\n+++\n",
+    }
+    .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn page_file_names() {
+        assert_eq!(file_name("v1.0.0"), "v1.0.0.md");
+        assert_eq!(file_name("v1.0.0 / v1.0"), "v1.0.0-v1.0.md");
+        assert_eq!(file_name("release/2 "), "release-2.md");
+        assert_eq!(
+            mdbook_summary(
+                "changelog",
+                &[
+                    ("v2".to_owned(), "v2.md".to_owned()),
+                    ("v1".to_owned(), "v1.md".to_owned())
+                ]
+            ),
+            "- [v2](changelog/v2.md)\n- [v1](changelog/v1.md)\n"
+        );
+    }
+
+    #[test]
+    fn site_pages() {
+        let config = Config::new();
+        let mut tag = ParsedTag::from_messages(
+            "v2",
+            &["Fixed a", "Added b", "Fixed c"],
+            &config,
+        );
+        tag.date = Utc.timestamp(1000, 0);
+        let notes = "\n# v2 (1970-01-01):\n- [Added] b\n";
+        assert_eq!(
+            site_page("zola", &tag, notes, &config).unwrap(),
+            "+++\ndate = \"1970-01-01T00:16:40+00:00\"\ntitle = \"v2\"\n\n\
+             [extra]\nversion = \"v2\"\n\n\
+             [taxonomies]\ncategories = [\"Added\", \"Fixed\"]\n+++\n\n\
+             - [Added] b\n"
+        );
+        let page = site_page("hugo", &tag, "- [Added] b", &config);
+        assert_eq!(
+            page.unwrap(),
+            "+++\ncategories = [\"Added\", \"Fixed\"]\n\
+             date = \"1970-01-01T00:16:40+00:00\"\ntitle = \"v2\"\n\n\
+             [params]\nversion = \"v2\"\n+++\n\n- [Added] b"
+        );
+        assert!(site_page("jekyll", &tag, notes, &config).is_err());
+        let mut config = config;
+        config.timezone = "unknown".to_owned();
+        assert!(site_page("zola", &tag, notes, &config).is_err());
+    }
+}
@@ -1,17 +1,45 @@
-use crate::config::Config;
-use chrono::{offset::Utc, Date, Datelike};
-use failure::{bail, format_err, Error};
-use git2::Oid;
+#[cfg(any(feature = "git", test))]
+use crate::output;
+use crate::{
+    config::Config,
+    error::ErrorKind,
+    links::{self, Provider},
+    oid::Oid,
+    output::{Color, ColorWriter, SilentWriter},
+    width,
+};
+use chrono::{
+    offset::{FixedOffset, Local, Utc},
+    DateTime,
+};
+use failure::{bail, format_err, Error, ResultExt};
 use lazy_static::lazy_static;
 use nom::{
-    alpha, call_m, char, digit, do_parse, many0, map, map_res, method, opt,
-    re_bytes_find, rest, separated_pair, space, tag,
+    alpha, call, char, digit, do_parse, error_position, many0, opt, rest,
+    separated_pair, space, tag, types::CompleteStr, IResult,
 };
 use regex::{Regex, RegexBuilder};
-use std::{collections::BTreeMap, fs::File, io::prelude::*, iter, str};
-use term;
+use serde::{de, Deserialize as _, Deserializer, Serializer};
+use serde_derive::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    fmt::Write as FmtWrite,
+    fs::File,
+    io::prelude::*,
+    iter,
+    process::{Command, Stdio},
+    str,
+};
 use toml::{self, Value};
 
+/// The commit message trailer overriding the changelog entry.
+static CHANGELOG_TRAILER: &str = "Changelog";
+
+/// The separator between the tag names of merged release sections.
+pub static MERGED_TAGS_SEPARATOR: &str = " / ";
+
 pub static TOML_DEFAULT_KEY: &str = "default";
 pub static TOML_FOOTERS_KEY: &str = "footers";
 pub static TOML_NAME_KEY: &str = "name";
@@ -22,6 +50,21 @@ pub static TOML_ONCE_KEY: &str = "once";
 pub static TOML_HEADER_KEY: &str = "header";
 pub static TOML_FOOTER_KEY: &str = "footer";
 
+/// The default gitmojis as emoji, shortcode and their category.
+static GITMOJIS: &[(&str, &str, &str)] = &[
+    ("✨", ":sparkles:", "Added"),
+    ("🎉", ":tada:", "Added"),
+    ("🐛", ":bug:", "Fixed"),
+    ("🚑", ":ambulance:", "Fixed"),
+    ("🔒", ":lock:", "Fixed"),
+    ("⚡", ":zap:", "Improved"),
+    ("🎨", ":art:", "Improved"),
+    ("♻", ":recycle:", "Changed"),
+    ("⬆", ":arrow_up:", "Changed"),
+    ("🔥", ":fire:", "Removed"),
+    ("⚰", ":coffin:", "Removed"),
+];
+
 #[derive(PartialEq)]
 pub enum Printed {
     Nothing,
@@ -55,7 +98,7 @@ pub trait Print {
 
     fn print_default_term(
         &self,
-        mut t: &mut Box<term::StdoutTerminal>,
+        mut t: &mut Box<dyn ColorWriter>,
         config: &Config,
         tag: Option<&str>,
     ) -> Result<(), Error> {
@@ -64,11 +107,11 @@ pub trait Print {
             config,
             tag,
             &|t| {
-                t.fg(term::color::BRIGHT_BLUE)?;
+                t.fg(Color::BrightBlue)?;
                 Ok(())
             },
             &|t| {
-                t.fg(term::color::WHITE)?;
+                t.fg(Color::White)?;
                 Ok(())
             },
             &|t| {
@@ -98,7 +141,7 @@ pub trait Print {
 
     fn print_to_term_and_write_to_vector(
         &self,
-        mut term: &mut Box<term::StdoutTerminal>,
+        mut term: &mut Box<dyn ColorWriter>,
         mut vec: &mut Vec<u8>,
         config: &Config,
         tag: Option<&str>,
@@ -116,6 +159,7 @@ pub trait Tags {
 
     /// Sort and uniq the tags as well.
     /// Transfers ownership of the vector back if done.
+    #[cfg(feature = "git")]
     fn get_tags_unique(&self, mut vec: Vec<String>) -> Vec<String> {
         vec = self.get_tags(vec);
         vec.sort();
@@ -124,15 +168,187 @@ pub trait Tags {
     }
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+/// A release within the parsed log, which is either a git tag or the
+/// unreleased part of the history.
+#[derive(
+    Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize,
+)]
 pub struct ParsedTag {
+    /// The name of the tag, or "Unreleased"
     pub name: String,
-    pub date: Date<Utc>,
+
+    /// The first line of the tag annotation, e.g. "The Speedy Release"
+    pub title: Option<String>,
+
+    /// The commit date of the tagged commit
+    pub date: DateTime<Utc>,
+
+    /// The commit the tag points to, `None` for unreleased commits
+    #[serde(
+        default,
+        serialize_with = "serialize_oid",
+        deserialize_with = "deserialize_oid"
+    )]
+    pub oid: Option<Oid>,
+
+    /// All commits belonging to the release, including the ones which could
+    /// not be parsed
+    #[serde(
+        serialize_with = "serialize_oids",
+        deserialize_with = "deserialize_oids"
+    )]
+    pub commit_ids: Vec<Oid>,
+
+    /// The successfully parsed commits of the release
     pub commits: Vec<ParsedCommit>,
-    pub message_ids: Vec<usize>,
+
+    /// The indices of the commit messages of the release within the parsing
+    /// queue of `GitJournal::parse_log`, which are only used while parsing
+    #[serde(skip)]
+    pub(crate) message_ids: Vec<usize>,
+}
+
+/// A commit matching a search within the parsed releases.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchResult {
+    /// The name of the release containing the commit, or "Unreleased"
+    pub release: String,
+
+    /// The category of the commit summary, e.g. "Fixed"
+    pub category: String,
+
+    /// The matching commit
+    pub commit: ParsedCommit,
+}
+
+/// Serializes an optional git object id as hex string.
+fn serialize_oid<S: Serializer>(
+    oid: &Option<Oid>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match oid {
+        Some(oid) => serializer.serialize_some(&oid.to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Serializes git object ids as hex strings.
+fn serialize_oids<S: Serializer>(
+    oids: &[Oid],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(oids.iter().map(|oid| oid.to_string()))
+}
+
+/// Deserializes an optional git object id from a hex string.
+fn deserialize_oid<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Oid>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(oid) => Oid::from_str(&oid).map(Some).map_err(de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+/// Deserializes git object ids from hex strings.
+fn deserialize_oids<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Oid>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|oid| Oid::from_str(oid).map_err(de::Error::custom))
+        .collect()
+}
+
+/// Returns the text in a form which parses to the same text again, since the
+/// parsing removes a single trailing dot of the element.
+fn escape_text(text: &str, dot_removed: bool) -> Cow<'_, str> {
+    if dot_removed && text.ends_with('.') {
+        Cow::Owned(format!("{}.", text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Formats the tags as suffix of an element.
+fn format_tags(tags: &[String]) -> String {
+    if tags.is_empty() {
+        String::new()
+    } else {
+        format!(" :{}:", tags.join(", "))
+    }
+}
+
+/// Wraps the category in the configured delimiters.
+fn format_category(category: &str, config: &Config) -> String {
+    let delimiters = &config.category_delimiters;
+    format!("{}{}{}", delimiters[0], category, delimiters[1])
 }
 
 impl ParsedTag {
+    /// Returns a release of the current date with the parsed commit messages,
+    /// which is used as fixture by the tests.
+    #[cfg(test)]
+    pub(crate) fn from_messages(
+        name: &str,
+        messages: &[&str],
+        config: &Config,
+    ) -> Self {
+        let parser = Parser {
+            config: config.clone(),
+            result: vec![],
+        };
+        ParsedTag {
+            name: name.to_owned(),
+            title: None,
+            date: Utc::now(),
+            oid: None,
+            commit_ids: vec![],
+            commits: messages
+                .iter()
+                .map(|message| {
+                    parser.parse_commit_message(message, None).unwrap()
+                })
+                .collect(),
+            message_ids: vec![],
+        }
+    }
+
+    /// Returns the date of the tag in the configured timezone, where the local
+    /// timezone is ignored in `reproducible` mode.
+    pub fn zoned_date(
+        &self,
+        config: &Config,
+    ) -> Result<DateTime<FixedOffset>, Error> {
+        match config.timezone.as_str() {
+            "local" if !config.reproducible => {
+                let date = self.date.with_timezone(&Local);
+                Ok(date.with_timezone(date.offset()))
+            }
+            "local" | "utc" => {
+                Ok(self.date.with_timezone(&FixedOffset::east(0)))
+            }
+            timezone => Err(ErrorKind::Config("timezone".to_owned())
+                .wrap(format_err!("Unknown timezone '{}'", timezone))),
+        }
+    }
+
+    /// Formats the date of the tag regarding the configured format and
+    /// timezone, see `zoned_date`.
+    pub fn format_date(&self, config: &Config) -> Result<String, Error> {
+        let date = self.zoned_date(config)?;
+        let mut formatted = String::new();
+        write!(formatted, "{}", date.format(&config.date_format)).map_err(
+            |_| {
+                ErrorKind::Config("date_format".to_owned()).wrap(format_err!(
+                    "Invalid date format '{}'",
+                    config.date_format
+                ))
+            },
+        )?;
+        Ok(formatted)
+    }
+
     fn print<T: Write, F, G, H>(
         &self,
         t: &mut T,
@@ -149,17 +365,16 @@ impl ParsedTag {
         if config.colored_output {
             c1(t)?;
         }
-        write!(t, "\n# {} ", self.name)?;
+        write!(t, "\n# {} ", config.translate(&self.name))?;
+        if config.show_tag_titles {
+            if let Some(ref title) = self.title {
+                write!(t, "— {} ", title)?;
+            }
+        }
         if config.colored_output {
             c2(t)?;
         }
-        write!(
-            t,
-            "({}-{:02}-{:02}):",
-            self.date.year(),
-            self.date.month(),
-            self.date.day()
-        )?;
+        write!(t, "({}):", self.format_date(config)?)?;
         if config.colored_output {
             c3(t)?;
         }
@@ -177,18 +392,18 @@ impl ParsedTag {
 
     fn print_default_term(
         &self,
-        mut t: &mut Box<term::StdoutTerminal>,
+        mut t: &mut Box<dyn ColorWriter>,
         config: &Config,
     ) -> Result<(), Error> {
         self.print(
             &mut t,
             config,
             &|t| {
-                t.fg(term::color::GREEN)?;
+                t.fg(Color::Green)?;
                 Ok(())
             },
             &|t| {
-                t.fg(term::color::YELLOW)?;
+                t.fg(Color::Yellow)?;
                 Ok(())
             },
             &|t| {
@@ -201,7 +416,7 @@ impl ParsedTag {
 
     fn print_to_term_and_write_to_vector(
         &self,
-        mut term: &mut Box<term::StdoutTerminal>,
+        mut term: &mut Box<dyn ColorWriter>,
         mut vec: &mut Vec<u8>,
         compact: &bool,
         config: &Config,
@@ -238,9 +453,18 @@ impl ParsedTag {
                     }
                 }
 
-                // Print the tags
+                // Print the tags and their pinned commits
                 self.print_default_term(&mut term, config)?;
                 self.print_default(&mut vec, config)?;
+                let (pinned, _) = self.get_pinned_commits(*compact, config);
+                self.print_highlights(
+                    term,
+                    vec,
+                    &pinned,
+                    compact,
+                    config,
+                    &mut BTreeMap::new(),
+                )?;
 
                 // Print commits
                 if let Some(main_table) = toml.as_table() {
@@ -276,19 +500,65 @@ impl ParsedTag {
                 self.print_default_term(&mut term, config)?;
                 self.print_default(&mut vec, config)?;
 
-                for commit in &self.commits {
-                    if *compact {
-                        commit.summary.print_to_term_and_write_to_vector(
-                            &mut term, &mut vec, config, None,
+                // The pinned commits count towards the maximum of entries of
+                // their category within their group
+                let (pinned, commits) =
+                    self.get_pinned_commits(*compact, config);
+                self.print_highlights(
+                    term,
+                    vec,
+                    &pinned,
+                    compact,
+                    config,
+                    &mut BTreeMap::new(),
+                )?;
+                let (groups, pinned_groups) = match config.group_tag_prefix {
+                    Some(ref prefix) => (
+                        Self::get_groups(&commits, prefix),
+                        Self::get_groups(&pinned, prefix),
+                    ),
+                    None => (vec![(None, commits)], vec![(None, pinned)]),
+                };
+                for (group, commits) in groups {
+                    let mut counts = BTreeMap::new();
+                    for (_, pinned) in
+                        pinned_groups.iter().filter(|(g, _)| *g == group)
+                    {
+                        for commit in pinned {
+                            *counts
+                                .entry(commit.summary.category.clone())
+                                .or_insert(0) += 1;
+                        }
+                    }
+                    if config.group_tag_prefix.is_some() {
+                        let name = match group {
+                            Some(ref group) => Self::get_group_name(group),
+                            None => "Other".to_owned(),
+                        };
+                        Self::print_section_header(
+                            term, vec, "##", &name, config,
                         )?;
-                    } else {
-                        commit.print_to_term_and_write_to_vector(
-                            &mut term, &mut vec, config, None,
+                    }
+                    self.print_commit_list(
+                        term,
+                        vec,
+                        &commits,
+                        compact,
+                        config,
+                        &mut counts,
+                    )?;
+                    for (category, count) in counts {
+                        self.print_more_entries(
+                            term,
+                            vec,
+                            count,
+                            Some(&category),
+                            config,
                         )?;
                     }
+                    writeln!(term)?;
+                    writeln!(vec)?;
                 }
-                writeln!(term, "")?;
-                writeln!(vec, "")?;
                 if !*compact && config.enable_footers {
                     self.print_footers(&mut term, &mut vec, None, config)?;
                 }
@@ -298,9 +568,108 @@ impl ParsedTag {
         Ok(())
     }
 
+    /// Prints the pinned commits as "Highlights" section, where the entries
+    /// are counted per category like within `print_commit_list`.
+    fn print_highlights(
+        &self,
+        term: &mut Box<dyn ColorWriter>,
+        vec: &mut Vec<u8>,
+        pinned: &[ParsedCommit],
+        compact: &bool,
+        config: &Config,
+        counts: &mut BTreeMap<String, usize>,
+    ) -> Result<(), Error> {
+        if pinned.is_empty() {
+            return Ok(());
+        }
+        Self::print_section_header(term, vec, "##", "Highlights", config)?;
+        self.print_commit_list(term, vec, pinned, compact, config, counts)?;
+        writeln!(term)?;
+        writeln!(vec)?;
+        Ok(())
+    }
+
+    /// Prints the commits, where the entries are counted per category and
+    /// skipped if they exceed the maximum of entries per category.
+    fn print_commit_list(
+        &self,
+        mut term: &mut Box<dyn ColorWriter>,
+        mut vec: &mut Vec<u8>,
+        commits: &[ParsedCommit],
+        compact: &bool,
+        config: &Config,
+        counts: &mut BTreeMap<String, usize>,
+    ) -> Result<(), Error> {
+        for commit in commits {
+            let count =
+                counts.entry(commit.summary.category.clone()).or_insert(0);
+            *count += 1;
+            if Self::exceeds_max_entries(*count, config) {
+                continue;
+            }
+            if *compact {
+                commit
+                    .summary
+                    .get_short(config)
+                    .print_to_term_and_write_to_vector(
+                        &mut term, &mut vec, config, None,
+                    )?;
+            } else {
+                commit.print_to_term_and_write_to_vector(
+                    &mut term, &mut vec, config, None,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Groups the commits by their first tag starting with the prefix, e.g.
+    /// "epic/auth" for the prefix "epic/". The groups are sorted by name,
+    /// followed by the commits without any group as `None`.
+    fn get_groups(
+        commits: &[ParsedCommit],
+        prefix: &str,
+    ) -> Vec<(Option<String>, Vec<ParsedCommit>)> {
+        let mut groups: BTreeMap<String, Vec<ParsedCommit>> = BTreeMap::new();
+        let mut other = vec![];
+        for commit in commits {
+            let group = commit
+                .get_tags(vec![])
+                .iter()
+                .filter_map(|tag| tag.strip_prefix(prefix))
+                .find(|group| !group.is_empty())
+                .map(str::to_owned);
+            match group {
+                Some(group) => {
+                    groups.entry(group).or_default().push(commit.clone())
+                }
+                None => other.push(commit.clone()),
+            }
+        }
+        let mut result = groups
+            .into_iter()
+            .map(|(group, commits)| (Some(group), commits))
+            .collect::<Vec<_>>();
+        if !other.is_empty() {
+            result.push((None, other));
+        }
+        result
+    }
+
+    /// Returns the display name of a group, where the first letter is
+    /// capitalized and dashes or underscores are replaced by spaces.
+    fn get_group_name(group: &str) -> String {
+        let name = group.replace(&['-', '_'][..], " ");
+        let mut chars = name.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => name,
+        }
+    }
+
     fn print_commits_in_table(
         &self,
-        mut term: &mut Box<term::StdoutTerminal>,
+        mut term: &mut Box<dyn ColorWriter>,
         mut vec: &mut Vec<u8>,
         table: &toml::value::Table,
         level: &mut usize,
@@ -331,54 +700,62 @@ impl ParsedTag {
             None => tag,
         };
 
-        if (*compact
-            && ((self
-                .commits
-                .iter()
-                .filter(|c| c.summary.contains_tag(Some(tag)))
-                .count()
-                > 0
-                && !config.excluded_commit_tags.contains(&tag.to_owned()))
-                || (tag == TOML_DEFAULT_KEY
-                    && self
-                        .commits
-                        .iter()
-                        .filter(|c| c.summary.contains_untagged_elements())
-                        .count()
-                        > 0)))
-            || (!*compact
-                && ((self
-                    .commits
-                    .iter()
-                    .filter(|c| c.contains_tag(Some(tag)))
-                    .count()
-                    > 0
-                    && !config.excluded_commit_tags.contains(&tag.to_owned()))
+        // Pinned commits are printed within the highlights, but count towards
+        // the maximum of entries of the section
+        let (pinned, commits) = self.get_pinned_commits(*compact, config);
+        let excluded = config.excluded_commit_tags.contains(&tag.to_owned());
+        let has_entries = commits.iter().any(|c| {
+            if *compact {
+                (c.summary.contains_tag(Some(tag)) && !excluded)
                     || (tag == TOML_DEFAULT_KEY
-                        && self
-                            .commits
-                            .iter()
-                            .filter(|c| c.contains_untagged_elements())
-                            .count()
-                            > 0)))
-        {
-            if config.colored_output {
-                term.fg(term::color::BRIGHT_RED)?;
+                        && c.summary.contains_untagged_elements())
+            } else {
+                (c.contains_tag(Some(tag)) && !excluded)
+                    || (tag == TOML_DEFAULT_KEY
+                        && c.contains_untagged_elements())
             }
-            write!(term, "\n{} {}", header_lvl, name)?;
-            write!(vec, "\n{} {}", header_lvl, name)?;
-
-            term.reset()?;
+        });
+        let printed = |commit: &ParsedCommit| {
+            (*compact && commit.summary.should_be_printed(Some(tag)))
+                || (!*compact && commit.should_be_printed(Some(tag)))
+        };
+        if !has_entries {
+            // Print the placeholder for empty sections if configured
+            if let Some(ref placeholder) = config.empty_section_placeholder {
+                if !config.excluded_commit_tags.contains(&tag.to_owned()) {
+                    Self::print_section_header(
+                        term,
+                        vec,
+                        &header_lvl,
+                        name,
+                        config,
+                    )?;
+                    writeln!(term, "\n{}", placeholder)?;
+                    writeln!(vec, "\n{}", placeholder)?;
+                }
+            }
+        } else {
+            Self::print_section_header(term, vec, &header_lvl, name, config)?;
 
             // Print commits for this tag
-            for commit in &self.commits {
+            let mut count = pinned.iter().filter(|c| printed(c)).count();
+            for commit in &commits {
+                if printed(commit) {
+                    count += 1;
+                    if Self::exceeds_max_entries(count, config) {
+                        continue;
+                    }
+                }
                 if *compact {
-                    commit.summary.print_to_term_and_write_to_vector(
-                        &mut term,
-                        &mut vec,
-                        config,
-                        Some(tag),
-                    )?;
+                    commit
+                        .summary
+                        .get_short(config)
+                        .print_to_term_and_write_to_vector(
+                            &mut term,
+                            &mut vec,
+                            config,
+                            Some(tag),
+                        )?;
                 } else {
                     commit.print_to_term_and_write_to_vector(
                         &mut term,
@@ -388,9 +765,10 @@ impl ParsedTag {
                     )?;
                 }
             }
+            self.print_more_entries(term, vec, count, None, config)?;
 
-            writeln!(term, "")?;
-            writeln!(vec, "")?;
+            writeln!(term)?;
+            writeln!(vec)?;
 
             // Print footers if specified in the template
             if let Some(footers) = table.get(TOML_FOOTERS_KEY) {
@@ -405,9 +783,129 @@ impl ParsedTag {
         Ok(())
     }
 
+    fn print_section_header(
+        term: &mut Box<dyn ColorWriter>,
+        vec: &mut Vec<u8>,
+        header_lvl: &str,
+        name: &str,
+        config: &Config,
+    ) -> Result<(), Error> {
+        if config.colored_output {
+            term.fg(Color::BrightRed)?;
+        }
+        let name = config.translate(name);
+        write!(term, "\n{} {}", header_lvl, name)?;
+        write!(vec, "\n{} {}", header_lvl, name)?;
+        term.reset()?;
+        Ok(())
+    }
+
+    /// Returns the commits to be printed, where commits with identical
+    /// summaries are folded into a single entry if configured. Folded entries
+    /// contain either their count or the list of commit hashes.
+    fn get_printed_commits(&self, config: &Config) -> Vec<ParsedCommit> {
+        if !config.fold_duplicates {
+            return self.commits.clone();
+        }
+
+        let mut folded: Vec<(ParsedCommit, Vec<Option<Oid>>)> = vec![];
+        let mut indices: HashMap<(&String, &String), usize> = HashMap::new();
+        for commit in &self.commits {
+            let key = (&commit.summary.category, &commit.summary.text);
+            match indices.get(&key) {
+                Some(index) => folded[*index].1.push(commit.oid),
+                None => {
+                    indices.insert(key, folded.len());
+                    folded.push((commit.clone(), vec![commit.oid]));
+                }
+            }
+        }
+
+        folded
+            .into_iter()
+            .map(|(mut commit, oids)| {
+                if oids.len() > 1 {
+                    if config.show_commit_hash {
+                        let hashes = oids
+                            .iter()
+                            .filter_map(|oid| *oid)
+                            .map(|oid| format!("{:.7}", oid))
+                            .collect::<Vec<_>>();
+                        commit.summary.text +=
+                            &format!(" ({})", hashes.join(", "));
+                        commit.summary.oid = None;
+                    } else {
+                        commit.summary.text += &format!(" (×{})", oids.len());
+                    }
+                }
+                commit
+            })
+            .collect()
+    }
+
+    /// Returns the printed commits split into the ones pinned by the
+    /// `highlight_tag` and the rest, where the tag of a list item pins its
+    /// commit as well if not `compact`.
+    fn get_pinned_commits(
+        &self,
+        compact: bool,
+        config: &Config,
+    ) -> (Vec<ParsedCommit>, Vec<ParsedCommit>) {
+        let commits = self.get_printed_commits(config);
+        if config.highlight_tag.is_empty() {
+            return (vec![], commits);
+        }
+        let tag = Some(config.highlight_tag.as_str());
+        commits.into_iter().partition(|commit| {
+            commit.summary.contains_tag(tag)
+                || (!compact && commit.contains_tag(tag))
+        })
+    }
+
+    /// Checks if the entry at the given position exceeds the configured
+    /// maximum of entries per category.
+    fn exceeds_max_entries(position: usize, config: &Config) -> bool {
+        match config.max_entries_per_category {
+            Some(max) => position > max,
+            None => false,
+        }
+    }
+
+    /// Prints the line for the entries which exceeded the maximum of entries
+    /// of a category, linking to the full log if configured.
+    fn print_more_entries(
+        &self,
+        term: &mut Box<dyn ColorWriter>,
+        vec: &mut Vec<u8>,
+        count: usize,
+        category: Option<&str>,
+        config: &Config,
+    ) -> Result<(), Error> {
+        let max = match config.max_entries_per_category {
+            Some(max) if count > max => max,
+            _ => return Ok(()),
+        };
+        let mut line = "\n- ".to_owned();
+        if let Some(category) = category {
+            line += &format!(
+                "{}{}{} ",
+                config.category_delimiters[0],
+                config.translate(category),
+                config.category_delimiters[1]
+            );
+        }
+        line += &format!("… and {} more", count - max);
+        if let Some(ref url) = config.full_log_url {
+            line += &format!(" ([full log]({}))", url);
+        }
+        write!(term, "{}", line)?;
+        write!(vec, "{}", line)?;
+        Ok(())
+    }
+
     fn print_footers(
         &self,
-        term: &mut Box<term::StdoutTerminal>,
+        term: &mut Box<dyn ColorWriter>,
         vec: &mut Vec<u8>,
         footer_keys: Option<&[Value]>,
         config: &Config,
@@ -459,7 +957,7 @@ impl ParsedTag {
         // Print the mapped footers
         for (key, values) in &footer_tree {
             if config.colored_output {
-                term.fg(term::color::BRIGHT_RED)?;
+                term.fg(Color::BrightRed)?;
             }
             writeln!(term, "\n{}:", key)?;
             writeln!(vec, "\n{}:", key)?;
@@ -492,12 +990,206 @@ impl Tags for ParsedTag {
     }
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+/// A single parsed commit message.
+#[derive(
+    Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize,
+)]
 pub struct ParsedCommit {
+    /// The id of the commit, if available
+    #[serde(
+        default,
+        serialize_with = "serialize_oid",
+        deserialize_with = "deserialize_oid"
+    )]
     pub oid: Option<Oid>,
+
+    /// The summary line of the commit message
     pub summary: SummaryElement,
+
+    /// The paragraphs and lists of the commit message body
     pub body: Vec<BodyElement>,
+
+    /// The footer key value pairs of the commit message
     pub footer: Vec<FooterElement>,
+
+    /// The name of the commit author, if available
+    pub author: Option<String>,
+
+    /// The other branches containing the same change as different commit,
+    /// if it has been cherry-picked between the parsed branches
+    pub cherry_picks: Vec<String>,
+}
+
+impl ParsedCommit {
+    /// Returns the texts of the summary and the body.
+    pub fn texts(&self) -> Vec<&str> {
+        let mut texts = vec![self.summary.text.as_str()];
+        for element in &self.body {
+            match element {
+                BodyElement::List(list) => {
+                    texts.extend(list.iter().map(|item| item.text.as_str()))
+                }
+                BodyElement::Paragraph(paragraph) => {
+                    texts.push(&paragraph.text)
+                }
+            }
+        }
+        texts
+    }
+
+    /// Returns the categories of the summary and the list items of the body.
+    pub fn categories(&self) -> Vec<&str> {
+        let mut categories = vec![self.summary.category.as_str()];
+        for element in &self.body {
+            if let BodyElement::List(list) = element {
+                categories
+                    .extend(list.iter().map(|item| item.category.as_str()))
+            }
+        }
+        categories
+    }
+
+    /// Replaces the texts of the summary and the body via the function, e.g.
+    /// to redact them after parsing.
+    pub fn map_texts<F: Fn(&str) -> String>(&mut self, f: F) {
+        self.summary.text = f(&self.summary.text);
+        for element in &mut self.body {
+            match element {
+                BodyElement::List(list) => {
+                    for item in list {
+                        item.text = f(&item.text);
+                    }
+                }
+                BodyElement::Paragraph(paragraph) => {
+                    paragraph.text = f(&paragraph.text);
+                }
+            }
+        }
+    }
+
+    /// Returns whether the commit should be excluded from the changelog via
+    /// a `Changelog: none` trailer.
+    pub fn is_excluded(&self) -> bool {
+        self.footer.iter().any(|footer| {
+            footer.key.eq_ignore_ascii_case(CHANGELOG_TRAILER)
+                && footer.value.trim().eq_ignore_ascii_case("none")
+        })
+    }
+
+    /// Returns whether the keyword is part of the summary, body, footers or
+    /// tags of the commit, ignoring the case.
+    pub fn matches(&self, keyword: &str) -> bool {
+        let keyword = keyword.to_lowercase();
+        let mut texts = vec![&self.summary.prefix, &self.summary.text];
+        texts.extend(&self.summary.tags);
+        for body_element in &self.body {
+            match *body_element {
+                BodyElement::List(ref list) => {
+                    for item in list {
+                        texts.push(&item.text);
+                        texts.extend(&item.tags);
+                    }
+                }
+                BodyElement::Paragraph(ref paragraph) => {
+                    texts.push(&paragraph.text);
+                    texts.extend(&paragraph.tags);
+                }
+            }
+        }
+        texts.extend(self.footer.iter().map(|footer| &footer.value));
+        texts
+            .iter()
+            .any(|text| text.to_lowercase().contains(&keyword))
+    }
+
+    /// Renders the commit back to a commit message, which parses to an
+    /// equivalent commit again. The categories are wrapped in the configured
+    /// delimiters and the tags are moved to the end of each element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitjournal::{parse_commit_message, Config};
+    ///
+    /// let config = Config::new();
+    /// let commit =
+    ///     parse_commit_message("Added some feature. :ui:", &config).unwrap();
+    /// let message = commit.to_message(&config);
+    /// assert_eq!(message, "[Added] some feature :ui:");
+    /// let reparsed = parse_commit_message(&message, &config).unwrap();
+    /// assert!(commit.is_equivalent(&reparsed));
+    /// ```
+    pub fn to_message(&self, config: &Config) -> String {
+        let mut parts = vec![self.summary.to_line(config)];
+        for body_element in &self.body {
+            match *body_element {
+                BodyElement::List(ref list) => {
+                    let items = list.iter().map(|item| item.to_line(config));
+                    parts.push(items.collect::<Vec<_>>().join("\n"));
+                }
+                BodyElement::Paragraph(ref paragraph) => parts.push(
+                    escape_text(&paragraph.text, true).into_owned()
+                        + &format_tags(&paragraph.tags),
+                ),
+            }
+        }
+        if !self.footer.is_empty() {
+            let footer = self
+                .footer
+                .iter()
+                .map(|footer| format!("{}: {}", footer.key, footer.value));
+            parts.push(footer.collect::<Vec<_>>().join("\n"));
+        }
+        parts.join("\n\n")
+    }
+
+    /// Returns whether both commits consist of the same message elements,
+    /// ignoring the commit ids and everything not stored within the message
+    /// like the author or fetched issues.
+    pub fn is_equivalent(&self, other: &ParsedCommit) -> bool {
+        fn message_only(commit: &ParsedCommit) -> ParsedCommit {
+            ParsedCommit {
+                oid: None,
+                summary: SummaryElement {
+                    oid: None,
+                    issue: None,
+                    backported: false,
+                    ..commit.summary.clone()
+                },
+                body: commit
+                    .body
+                    .iter()
+                    .map(|body_element| match *body_element {
+                        BodyElement::List(ref list) => BodyElement::List(
+                            list.iter()
+                                .map(|item| ListElement {
+                                    oid: None,
+                                    ..item.clone()
+                                })
+                                .collect(),
+                        ),
+                        BodyElement::Paragraph(ref paragraph) => {
+                            BodyElement::Paragraph(ParagraphElement {
+                                oid: None,
+                                ..paragraph.clone()
+                            })
+                        }
+                    })
+                    .collect(),
+                footer: commit
+                    .footer
+                    .iter()
+                    .map(|footer| FooterElement {
+                        oid: None,
+                        ..footer.clone()
+                    })
+                    .collect(),
+                author: None,
+                cherry_picks: vec![],
+            }
+        }
+        message_only(self) == message_only(other)
+    }
 }
 
 impl Print for ParsedCommit {
@@ -551,13 +1243,92 @@ impl Tags for ParsedCommit {
     }
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+/// The summary line of a commit message.
+#[derive(
+    Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize,
+)]
 pub struct SummaryElement {
+    /// The id of the commit, if available
+    #[serde(
+        default,
+        serialize_with = "serialize_oid",
+        deserialize_with = "deserialize_oid"
+    )]
     pub oid: Option<Oid>,
+
+    /// The optional prefix, e.g. "JIRA-1234"
     pub prefix: String,
+
+    /// The category, e.g. "Added"
     pub category: String,
+
+    /// The text without category and tags
     pub text: String,
+
+    /// The tags found within the summary
     pub tags: Vec<String>,
+
+    /// The number of the pull request, taken from a "(#1234)" suffix
+    pub pull_request: Option<u64>,
+
+    /// The number of the GitLab merge request, taken from a "See merge
+    /// request group/project!1234" reference if `merge_requests` are enabled
+    pub merge_request: Option<u64>,
+
+    /// The issue referenced by the prefix, if it could be fetched
+    pub issue: Option<Issue>,
+
+    /// Whether the change has been cherry-picked onto another commit
+    pub backported: bool,
+}
+
+impl SummaryElement {
+    /// Returns the summary for the short output, where the text is truncated
+    /// or wrapped to the configured `short_summary_width`
+    fn get_short(&self, config: &Config) -> SummaryElement {
+        let mut summary = self.clone();
+        let max_width = match config.short_summary_width {
+            Some(max_width) => max_width,
+            None => return summary,
+        };
+        if !config.short_summary_wrap {
+            summary.text = width::truncate(&self.text, max_width);
+            return summary;
+        }
+
+        // Align the wrapped lines with the beginning of the text
+        let mut indent = 2 + width::display_width(&format!(
+            "{}{}{} ",
+            config.category_delimiters[0],
+            config.translate(&self.category),
+            config.category_delimiters[1]
+        ));
+        if config.show_prefix && !self.prefix.is_empty() {
+            indent += width::display_width(&self.prefix) + 1;
+        }
+        if let Some(emoji) = config.category_emojis.get(&self.category) {
+            indent += width::display_width(emoji) + 1;
+        }
+        let separator = format!("\n{}", " ".repeat(indent));
+        summary.text = width::wrap(&self.text, max_width).join(&separator);
+        summary
+    }
+
+    /// Renders the summary back to the summary line of a commit message.
+    fn to_line(&self, config: &Config) -> String {
+        let mut line = String::new();
+        if !self.prefix.is_empty() {
+            line.push_str(&self.prefix);
+            line.push(' ');
+        }
+        line.push_str(&format_category(&self.category, config));
+        line.push(' ');
+        line.push_str(&escape_text(&self.text, self.pull_request.is_none()));
+        if let Some(number) = self.pull_request {
+            write!(line, " (#{})", number).ok();
+        }
+        line + &format_tags(&self.tags)
+    }
 }
 
 impl Print for SummaryElement {
@@ -591,21 +1362,70 @@ impl Print for SummaryElement {
             if config.show_prefix && !self.prefix.is_empty() {
                 write!(t, "{} ", self.prefix)?;
             }
+            if let Some(emoji) = config.category_emojis.get(&self.category) {
+                write!(t, "{} ", emoji)?;
+            }
             if config.colored_output {
                 c1(t)?;
             }
             write!(t, "{}", config.category_delimiters[0])?;
-            write!(t, "{}", self.category)?;
+            write!(t, "{}", config.translate(&self.category))?;
             write!(t, "{} ", config.category_delimiters[1])?;
             if config.colored_output {
                 c2(t)?;
             }
-            write!(t, "{}", self.text)?;
+            write!(t, "{}", links::link_work_items(&self.text, config))?;
+            if let Some(ref issue) = self.issue {
+                write!(t, " ({}: {})", issue.status, issue.title)?;
+            }
+            if config.show_backports && self.backported {
+                write!(t, " ({})", config.translate("backported"))?;
+            }
+            let links = Provider::from_config(config).unwrap_or(None);
+            if config.show_pull_requests {
+                // GitLab references merge requests like "!1234", where a
+                // suffix like "(#1234)" references an issue
+                let references =
+                    [('#', self.pull_request), ('!', self.merge_request)];
+                for (sign, number) in references.iter() {
+                    let number = match number {
+                        Some(number) => number,
+                        None => continue,
+                    };
+                    let url = match links {
+                        Some((Provider::GitLab, url)) if *sign == '#' => {
+                            Some(format!("{}/-/issues/", url))
+                        }
+                        _ => config.pull_request_url.clone().or_else(|| {
+                            links.map(|(provider, url)| {
+                                provider.pull_request_url(url)
+                            })
+                        }),
+                    };
+                    match url {
+                        Some(ref url) => write!(
+                            t,
+                            " ([{}{}]({}{}))",
+                            sign, number, url, number
+                        )?,
+                        None => write!(t, " ({}{})", sign, number)?,
+                    }
+                }
+            }
 
-            // Print the oid for the summary element (always)
+            // Print the oid for the summary element (always), linked to the
+            // commit within the repository if configured
             if config.show_commit_hash {
                 if let Some(oid) = self.oid {
-                    write!(t, " ({:.7})", oid)?;
+                    match links {
+                        Some((provider, url)) => write!(
+                            t,
+                            " ([{:.7}]({}))",
+                            oid,
+                            provider.commit_url(url, &oid.to_string())
+                        )?,
+                        None => write!(t, " ({:.7})", oid)?,
+                    }
                 }
             }
             if config.colored_output {
@@ -627,24 +1447,72 @@ impl Print for SummaryElement {
     }
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+/// An element of the commit message body.
+#[derive(
+    Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(tag = "type", content = "content")]
 pub enum BodyElement {
+    /// A list of categorized items
     List(Vec<ListElement>),
+
+    /// A plain text paragraph
     Paragraph(ParagraphElement),
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+/// A single item of a list within the commit message body.
+#[derive(
+    Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize,
+)]
 pub struct ListElement {
+    /// The id of the commit, if available
+    #[serde(
+        default,
+        serialize_with = "serialize_oid",
+        deserialize_with = "deserialize_oid"
+    )]
     pub oid: Option<Oid>,
+
+    /// The category of the item
     pub category: String,
+
+    /// The text without category and tags
     pub text: String,
+
+    /// The tags found within the item
     pub tags: Vec<String>,
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
-pub struct ParagraphElement {
+impl ListElement {
+    /// Renders the item back to a line of a list within a commit message.
+    fn to_line(&self, config: &Config) -> String {
+        let mut line = "- ".to_owned();
+        if !self.category.is_empty() {
+            line.push_str(&format_category(&self.category, config));
+            line.push(' ');
+        }
+        line.push_str(&escape_text(&self.text, true));
+        line + &format_tags(&self.tags)
+    }
+}
+
+/// A paragraph within the commit message body.
+#[derive(
+    Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct ParagraphElement {
+    /// The id of the commit, if available
+    #[serde(
+        default,
+        serialize_with = "serialize_oid",
+        deserialize_with = "deserialize_oid"
+    )]
     pub oid: Option<Oid>,
+
+    /// The text without tags
     pub text: String,
+
+    /// The tags found within the paragraph
     pub tags: Vec<String>,
 }
 
@@ -753,17 +1621,21 @@ impl Print for ListElement {
                 }
             })?;
             if !self.category.is_empty() {
+                if let Some(emoji) = config.category_emojis.get(&self.category)
+                {
+                    write!(t, "{} ", emoji)?;
+                }
                 if config.colored_output {
                     c1(t)?;
                 }
                 write!(t, "{}", config.category_delimiters[0])?;
-                write!(t, "{}", self.category)?;
+                write!(t, "{}", config.translate(&self.category))?;
                 write!(t, "{} ", config.category_delimiters[1])?;
                 if config.colored_output {
                     c2(t)?;
                 }
             }
-            write!(t, "{}", self.text)?;
+            write!(t, "{}", links::link_work_items(&self.text, config))?;
             // Print only in templating mode, otherwise hide unnecessary
             // information
             if config.show_commit_hash && tag.is_some() {
@@ -825,7 +1697,7 @@ impl Print for ParagraphElement {
         }
 
         if self.should_be_printed(tag) {
-            for (index, line) in self.text
+            for (index, line) in links::link_work_items(&self.text, config)
                     .lines()
                     .map(|x| {
                              let indent = if tag.is_none() { 4 } else { 2 };
@@ -863,10 +1735,36 @@ impl Print for ParagraphElement {
     }
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+/// An issue of an issue tracker like JIRA, which is referenced by the prefix
+/// of a commit message.
+#[derive(
+    Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct Issue {
+    /// The title of the issue
+    pub title: String,
+
+    /// The status of the issue, e.g. "Done"
+    pub status: String,
+}
+
+/// A key value pair of the commit message footer.
+#[derive(
+    Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize,
+)]
 pub struct FooterElement {
+    /// The id of the commit, if available
+    #[serde(
+        default,
+        serialize_with = "serialize_oid",
+        deserialize_with = "deserialize_oid"
+    )]
     pub oid: Option<Oid>,
+
+    /// The footer key, e.g. "Reviewed-by"
     pub key: String,
+
+    /// The footer value
     pub value: String,
 }
 
@@ -882,10 +1780,105 @@ lazy_static! {
         .unwrap();
     static ref RE_PARAGRAPH: Regex =
         RegexBuilder::new(r"^\w").multi_line(true).build().unwrap();
+    static ref RE_FOOTER_KEY: Regex = Regex::new(r"^[\w-]+:$").unwrap();
+    static ref RE_PREFIX: Regex = Regex::new(r"^([A-Za-z]+-\d+)\s").unwrap();
+    static ref RE_PULL_REQUEST: Regex = Regex::new(r"\s*\(#(\d+)\)$").unwrap();
+    pub(crate) static ref RE_MERGE_REQUEST: Regex =
+        RegexBuilder::new(r"^See merge request \S+!(\d+)$")
+            .multi_line(true)
+            .build()
+            .unwrap();
     static ref RE_COMMENT: Regex =
         RegexBuilder::new(r"^#.*").multi_line(true).build().unwrap();
 }
 
+/// Finds the first category within the input and consumes everything up to
+/// its end.
+fn find_category<'a>(
+    input: CompleteStr<'a>,
+    regex: &Regex,
+) -> IResult<CompleteStr<'a>, &'a str> {
+    match regex.find(input.0) {
+        Some(m) => Ok((CompleteStr(&input.0[m.end()..]), m.as_str())),
+        None => Err(nom::Err::Error(error_position!(
+            input,
+            nom::ErrorKind::RegexpFind
+        ))),
+    }
+}
+
+/// Parses a category, which can be wrapped in the configured delimiters.
+fn parse_category<'a>(
+    input: CompleteStr<'a>,
+    delimiters: &[String],
+    regex: &Regex,
+) -> IResult<CompleteStr<'a>, &'a str> {
+    do_parse!(
+        input,
+        opt!(tag!(delimiters[0].as_str()))
+            >> p_category: call!(find_category, regex)
+            >> opt!(tag!(delimiters[1].as_str()))
+            >> (p_category)
+    )
+}
+
+/// Parses a list item like `- [Fixed] some text :tag:` into its
+/// optional category and the remaining text.
+fn parse_list_item<'a>(
+    input: CompleteStr<'a>,
+    delimiters: &[String],
+    regex: &Regex,
+) -> IResult<CompleteStr<'a>, (Option<&'a str>, &'a str)> {
+    do_parse!(
+        input,
+        many0!(space)
+            >> tag!("-")
+            >> opt!(space)
+            >> p_category: opt!(call!(parse_category, delimiters, regex))
+            >> opt!(space)
+            >> p_rest: rest
+            >> ((p_category, p_rest.0))
+    )
+}
+
+/// Parses a summary like `JIRA-1234 [Added] some text :tag:` into its
+/// optional prefix, the category and the remaining text.
+#[allow(clippy::type_complexity)]
+fn parse_summary<'a>(
+    input: CompleteStr<'a>,
+    delimiters: &[String],
+    regex: &Regex,
+) -> IResult<CompleteStr<'a>, (Option<(&'a str, &'a str)>, &'a str, &'a str)> {
+    do_parse!(
+        input,
+        p_prefix: opt!(separated_pair!(alpha, char!('-'), digit))
+            >> opt!(space)
+            >> p_category: call!(parse_category, delimiters, regex)
+            >> space
+            >> p_rest: rest
+            >> ((p_prefix.map(|p| (p.0 .0, p.1 .0)), p_category, p_rest.0))
+    )
+}
+
+/// Returns the prefix like "JIRA-1234" of the summary and the trailing
+/// footer block like `Signed-off-by: Name <email>` of a commit message, which
+/// do not need a valid summary.
+#[cfg(feature = "git")]
+pub(crate) fn prefix_and_footer(message: &str) -> (Option<&str>, Option<&str>) {
+    let prefix = RE_PREFIX
+        .captures(message.trim_start())
+        .and_then(|cap| cap.get(1))
+        .map(|prefix| prefix.as_str());
+    let footer = message
+        .split("\n\n")
+        .skip(1)
+        .map(|part| part.trim_matches('\n'))
+        .filter(|part| !part.trim().is_empty())
+        .last()
+        .filter(|part| part.lines().all(|line| RE_FOOTER.is_match(line)));
+    (prefix, footer)
+}
+
 #[derive(Clone)]
 pub struct Parser {
     pub config: Config,
@@ -893,84 +1886,87 @@ pub struct Parser {
 }
 
 impl Parser {
-    method!(parse_category<Self, &[u8], &str>, self,
-        do_parse!(
-            opt!(tag!(self.config.category_delimiters[0].as_str())) >>
-            p_category: map_res!(
-                re_bytes_find!(&self.config.categories.join("|")),
-                str::from_utf8
-            ) >>
-            opt!(tag!(self.config.category_delimiters[1].as_str())) >>
-
-            (p_category)
-    ));
-
-    method!(parse_list_item<Self, &[u8], ListElement>, mut self,
-        do_parse!(
-            many0!(space) >>
-            tag!("-") >>
-            opt!(space) >>
-            p_category: opt!(call_m!(self.parse_category)) >>
-            opt!(space) >>
-            p_tags_rest: map!(rest, Self::parse_and_consume_tags) >>
-
-            (ListElement {
-                oid: None,
-                category: p_category.unwrap_or("").to_owned(),
-                tags: p_tags_rest.0.clone(),
-                text: p_tags_rest.1.clone(),
-            })
-        )
-    );
-
-    method!(parse_summary<Self, &[u8], SummaryElement>, mut self,
-        do_parse!(
-            p_prefix: opt!(separated_pair!(alpha, char!('-'), digit)) >>
-            opt!(space) >>
-            p_category: call_m!(self.parse_category) >>
-            space >>
-            p_tags_rest: map!(rest, Self::parse_and_consume_tags) >>
-
-            (SummaryElement {
-                oid: None,
-                prefix: p_prefix.map_or("".to_owned(), |p| {
-                    format!("{}-{}", str::from_utf8(p.0).unwrap_or(""), str::from_utf8(p.1).unwrap_or(""))
-                }),
-                category: p_category.to_owned(),
-                tags: p_tags_rest.0.clone(),
-                text: p_tags_rest.1.clone(),
-            })
-        )
-    );
+    /// Returns the regular expression matching the configured categories,
+    /// which is cached per thread since it is the same for every commit.
+    fn category_regex(&self) -> Result<Regex, Error> {
+        thread_local! {
+            static CACHE: RefCell<Option<(String, Regex)>> =
+                const { RefCell::new(None) };
+        }
+        let pattern = self.config.categories.join("|");
+        CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if let Some((ref cached, ref regex)) = *cache {
+                if *cached == pattern {
+                    return Ok(regex.clone());
+                }
+            }
+            let regex = Regex::new(&pattern).map_err(|e| {
+                ErrorKind::Config("categories".to_owned()).wrap(e)
+            })?;
+            *cache = Some((pattern, regex.clone()));
+            Ok(regex)
+        })
+    }
 
-    fn parse_and_consume_tags(input: &[u8]) -> (Vec<String>, String) {
-        let string = str::from_utf8(input).unwrap_or("");
+    /// Parses the tags and the remaining text of the input.
+    fn parse_and_consume_tags(input: &str) -> (Vec<String>, String) {
         let mut tags = vec![];
-        for cap in RE_TAGS.captures_iter(string) {
+        for cap in RE_TAGS.captures_iter(input) {
             if let Some(tag) = cap.get(1) {
+                // Ignore tags containing dots.
                 tags.extend(
                     tag.as_str()
                         .split(',')
-                        .filter_map(|x| {
-                            // Ignore tags containing dots.
-                            if !x.contains('.') {
-                                Some(x.trim().to_owned())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<String>>(),
+                        .filter(|x| !x.contains('.'))
+                        .map(|x| x.trim().to_owned()),
                 );
             }
         }
-        let mut text = RE_TAGS.replace_all(string, "").into_owned();
-        if let Some('.') = text.chars().rev().nth(0) {
+        let mut text = RE_TAGS.replace_all(input, "").into_owned();
+        if text.ends_with('.') {
             text.pop();
         }
         (tags, text)
     }
 
-    /// Parses a single commit message and returns a changelog ready form
+    /// Replaces a leading gitmoji of the summary with its category, where the
+    /// configured `category_emojis` take precedence over the default ones
+    fn replace_gitmoji(&self, summary_line: &str) -> String {
+        let configured = self
+            .config
+            .category_emojis
+            .iter()
+            .map(|(category, emoji)| (emoji.as_str(), category.as_str()));
+        let defaults = GITMOJIS.iter().flat_map(|&(emoji, code, category)| {
+            vec![(emoji, category), (code, category)]
+        });
+        for (emoji, category) in configured.chain(defaults) {
+            if emoji.is_empty()
+                || !self.config.categories.iter().any(|c| c == category)
+            {
+                continue;
+            }
+            if let Some(text) = summary_line.strip_prefix(emoji) {
+                return format!(
+                    "{}{}{} {}",
+                    self.config.category_delimiters[0],
+                    category,
+                    self.config.category_delimiters[1],
+                    text.trim_start_matches(|c: char| {
+                        c == '\u{fe0f}' || c.is_whitespace()
+                    })
+                );
+            }
+        }
+        summary_line.to_owned()
+    }
+
+    /// Parses a single commit message and returns a changelog ready form.
+    /// The parsers operate on slices of the message, but the parsing is not
+    /// zero-copy: the elements of the returned commit own their texts, since
+    /// they outlive the message and are cached, sent and serialized on their
+    /// own. Joined list items and replaced gitmojis are allocated as well.
     pub fn parse_commit_message(
         &self,
         message: &str,
@@ -988,12 +1984,42 @@ impl Parser {
                 )
             })?
             .trim();
-        let mut parsed_summary =
-            match self.clone().parse_summary(summary_line.as_bytes()) {
-                (_, Ok(parsed)) => parsed.1,
+        let summary_line = if self.config.parse_gitmoji {
+            Cow::Owned(self.replace_gitmoji(summary_line))
+        } else {
+            Cow::Borrowed(summary_line)
+        };
+        let delimiters = &self.config.category_delimiters;
+        let regex = self.category_regex()?;
+        let (prefix, category, rest) =
+            match parse_summary(CompleteStr(&summary_line), delimiters, &regex)
+            {
+                Ok((_, parsed)) => parsed,
                 _ => bail!("Summary parsing failed: '{}'", summary_line),
             };
-        parsed_summary.oid = oid;
+        let (tags, text) = Self::parse_and_consume_tags(rest);
+        let mut parsed_summary = SummaryElement {
+            oid,
+            prefix: prefix.map_or_else(String::new, |(project, number)| {
+                format!("{}-{}", project, number)
+            }),
+            category: category.to_owned(),
+            text: text.trim_end().to_owned(),
+            tags,
+            pull_request: None,
+            merge_request: None,
+            issue: None,
+            backported: false,
+        };
+
+        // Move a pull request suffix like "(#1234)" into its own field
+        let pull_request = RE_PULL_REQUEST
+            .captures(&parsed_summary.text)
+            .and_then(|cap| Some((cap.get(0)?.start(), cap[1].parse().ok()?)));
+        if let Some((start, number)) = pull_request {
+            parsed_summary.pull_request = Some(number);
+            parsed_summary.text.truncate(start);
+        }
 
         // Parse the body and the footer, the summary is already consumed
         let mut parsed_footer = vec![];
@@ -1006,6 +2032,18 @@ impl Parser {
                 continue;
             }
 
+            // Move a GitLab merge request reference into its own field
+            if self.config.merge_requests != "off" {
+                let number = RE_MERGE_REQUEST
+                    .captures(part.trim())
+                    .filter(|cap| cap[0].len() == part.trim().len())
+                    .and_then(|cap| cap[1].parse().ok());
+                if let Some(number) = number {
+                    parsed_summary.merge_request.get_or_insert(number);
+                    continue;
+                }
+            }
+
             // Parse the footer
             if RE_FOOTER.is_match(part) {
                 for cap in RE_FOOTER.captures_iter(part) {
@@ -1025,12 +2063,19 @@ impl Parser {
             // Parse all list items
             } else if RE_LIST.is_match(part) {
                 let mut list = vec![];
-                for list_item in part.lines() {
-                    if let (_, Ok(mut result)) =
-                        self.clone().parse_list_item(list_item.as_bytes())
-                    {
-                        result.1.oid = oid;
-                        list.push(result.1);
+                for list_item in Self::join_list_lines(part) {
+                    if let Ok((_, (category, rest))) = parse_list_item(
+                        CompleteStr(&list_item),
+                        delimiters,
+                        &regex,
+                    ) {
+                        let (tags, text) = Self::parse_and_consume_tags(rest);
+                        list.push(ListElement {
+                            oid,
+                            category: category.unwrap_or("").to_owned(),
+                            text,
+                            tags,
+                        });
                     };
                 }
                 parsed_body.push(BodyElement::List(list));
@@ -1038,7 +2083,7 @@ impl Parser {
             // Nothing of tbe above items matched, check for a Paragraph element
             } else if RE_PARAGRAPH.is_match(part) {
                 let (parsed_tags, parsed_text) =
-                    Self::parse_and_consume_tags(part.as_bytes());
+                    Self::parse_and_consume_tags(part);
                 parsed_body.push(BodyElement::Paragraph(ParagraphElement {
                     oid,
                     text: parsed_text.trim().to_owned(),
@@ -1051,41 +2096,484 @@ impl Parser {
             }
         }
 
+        // A `Changelog` trailer replaces the text of the entry, where the
+        // exclusion via `Changelog: none` is kept for the caller
+        let trailer = parsed_footer.iter().position(|footer| {
+            footer.key.eq_ignore_ascii_case(CHANGELOG_TRAILER)
+                && !footer.value.trim().eq_ignore_ascii_case("none")
+        });
+        if let Some(index) = trailer {
+            parsed_summary.text =
+                parsed_footer.remove(index).value.trim().to_owned();
+        }
+
         Ok(ParsedCommit {
             oid,
             summary: parsed_summary,
             body: parsed_body,
             footer: parsed_footer,
+            author: None,
+            cherry_picks: vec![],
         })
     }
 
+    /// Formats a valid commit message into its canonical form, where the
+    /// categories are wrapped in their delimiters, the whitespace is
+    /// normalized, the summary and categorized list items start lowercase
+    /// and the paragraphs start uppercase and are wrapped at the configured
+    /// `message_width`. Invalid messages are returned unchanged, as well as
+    /// messages which would not parse to an equivalent commit after the
+    /// formatting.
+    pub fn format_commit_message(&self, message: &str) -> String {
+        let original = match self.parse_commit_message(message, None) {
+            Ok(commit) => commit,
+            Err(_) => return message.to_owned(),
+        };
+        let formatted = match self.format_commit_parts(message) {
+            Ok(Some(formatted)) => formatted,
+            _ => return message.to_owned(),
+        };
+        match self.parse_commit_message(&formatted, None) {
+            Ok(ref commit)
+                if self
+                    .normalize(commit)
+                    .is_equivalent(&self.normalize(&original)) =>
+            {
+                formatted
+            }
+            _ => message.to_owned(),
+        }
+    }
+
+    /// Formats every part of the commit message, or returns `None` if a
+    /// category is not at the beginning of its line, since the text before
+    /// it would be lost.
+    fn format_commit_parts(
+        &self,
+        message: &str,
+    ) -> Result<Option<String>, Error> {
+        let delimiters = &self.config.category_delimiters;
+        let regex = self.category_regex()?;
+        let mut commit_parts = message.split("\n\n");
+
+        // Format the summary line
+        let summary_line = commit_parts.next().unwrap_or("").trim();
+        let summary_line = if self.config.parse_gitmoji {
+            Cow::Owned(self.replace_gitmoji(summary_line))
+        } else {
+            Cow::Borrowed(summary_line)
+        };
+        let mut summary =
+            self.parse_commit_message(&summary_line, None)?.summary;
+        let without_prefix = summary_line
+            .trim_start_matches(summary.prefix.as_str())
+            .trim_start();
+        if !Self::starts_with_category(without_prefix, &summary.category) {
+            return Ok(None);
+        }
+        summary.text = self.case_first(&Self::collapse(&summary.text));
+        let mut formatted = vec![summary.to_line(&self.config)];
+
+        // Wrap the paragraphs at most and the list items only at the
+        // verified line length
+        let (width, list_width) = match self.config.max_body_line_length {
+            0 => (self.config.message_width, usize::MAX),
+            max => (self.config.message_width.min(max), max),
+        };
+
+        for part in commit_parts {
+            let part = part.trim_matches('\n');
+            if part.trim().is_empty() {
+                continue;
+            }
+
+            // Keep the comments as they are
+            if RE_COMMENT.is_match(part) {
+                formatted.push(part.to_owned());
+
+            // Normalize the whitespace of the footer values
+            } else if RE_FOOTER.is_match(part) {
+                let lines =
+                    part.lines().map(|line| match RE_FOOTER.captures(line) {
+                        Some(cap) => format!("{}: {}", &cap[1], cap[2].trim()),
+                        None => line.trim_end().to_owned(),
+                    });
+                formatted.push(lines.collect::<Vec<_>>().join("\n"));
+
+            // Format and wrap every list item but keep its indentation
+            } else if RE_LIST.is_match(part) {
+                let mut lines = vec![];
+                for line in Self::join_list_lines(part) {
+                    let (category, rest) = match parse_list_item(
+                        CompleteStr(&line),
+                        delimiters,
+                        &regex,
+                    ) {
+                        Ok((_, parsed)) => parsed,
+                        Err(_) => {
+                            lines.push(line.trim_end().to_owned());
+                            continue;
+                        }
+                    };
+                    let item = line.trim_start()[1..].trim_start();
+                    if let Some(category) = category {
+                        if !Self::starts_with_category(item, category) {
+                            return Ok(None);
+                        }
+                    }
+                    let (tags, text) = Self::parse_and_consume_tags(rest);
+                    let text = Self::collapse(&text);
+                    let item = ListElement {
+                        oid: None,
+                        category: category.unwrap_or("").to_owned(),
+                        text: match category {
+                            Some(_) => self.case_first(&text),
+                            None => text,
+                        },
+                        tags,
+                    };
+                    let indent = &line[..line.len() - line.trim_start().len()];
+                    let wrapped = Self::wrap_paragraph(
+                        &item.to_line(&self.config)[2..],
+                        list_width.saturating_sub(indent.len() + 2),
+                    );
+                    lines.push(format!(
+                        "{}- {}",
+                        indent,
+                        wrapped.replace('\n', &format!("\n{}  ", indent))
+                    ));
+                }
+                formatted.push(lines.join("\n"));
+
+            // Wrap the paragraphs
+            } else {
+                formatted.push(Self::wrap_paragraph(
+                    &Self::uppercase_first(part.trim()),
+                    width,
+                ));
+            }
+        }
+        Ok(Some(formatted.join("\n\n")))
+    }
+
+    /// Returns the lines of a list, where the indented continuation lines of
+    /// wrapped items are joined to the line of their item.
+    fn join_list_lines(part: &str) -> Vec<String> {
+        let mut lines: Vec<String> = vec![];
+        for line in part.lines() {
+            let text = line.trim();
+            match lines.last_mut() {
+                Some(last)
+                    if line.starts_with(char::is_whitespace)
+                        && !text.is_empty()
+                        && !text.starts_with('-') =>
+                {
+                    last.push(' ');
+                    last.push_str(text);
+                }
+                _ => lines.push(line.to_owned()),
+            }
+        }
+        lines
+    }
+
+    /// Returns whether the text starts with the category, which can be
+    /// wrapped in the configured delimiters.
+    fn starts_with_category(text: &str, category: &str) -> bool {
+        text.trim_start_matches(|c: char| !c.is_alphanumeric())
+            .starts_with(category)
+    }
+
+    /// Returns the commit as it would be parsed after the formatting.
+    fn normalize(&self, commit: &ParsedCommit) -> ParsedCommit {
+        let mut commit = commit.clone();
+        commit.summary.text =
+            self.case_first(&Self::collapse(&commit.summary.text));
+        for body_element in &mut commit.body {
+            match *body_element {
+                BodyElement::List(ref mut list) => {
+                    for item in list {
+                        item.text = Self::collapse(&item.text);
+                        if !item.category.is_empty() {
+                            item.text = self.case_first(&item.text);
+                        }
+                    }
+                }
+                BodyElement::Paragraph(ref mut paragraph) => {
+                    paragraph.text =
+                        Self::uppercase_first(&Self::collapse(&paragraph.text))
+                }
+            }
+        }
+        for footer in &mut commit.footer {
+            footer.value = footer.value.trim().to_owned();
+        }
+        commit
+    }
+
+    /// Replaces every whitespace sequence with a single space.
+    fn collapse(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Changes the case of the first word of a categorized text, which is
+    /// uppercased if the "capitalized" style check is enabled and lowercased
+    /// otherwise.
+    fn case_first(&self, text: &str) -> String {
+        if self.config.style_checks.iter().any(|c| c == "capitalized") {
+            Self::uppercase_first(text)
+        } else {
+            Self::lowercase_first(text)
+        }
+    }
+
+    /// Lowercases a capitalized first word like "Some", but keeps
+    /// abbreviations like "API" or mixed words like "GitHub".
+    fn lowercase_first(text: &str) -> String {
+        let word = text.split_whitespace().next().unwrap_or("");
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first)
+                if first.is_uppercase()
+                    && chars.clone().next().is_some()
+                    && chars.all(char::is_lowercase) =>
+            {
+                first.to_lowercase().collect::<String>()
+                    + &text[first.len_utf8()..]
+            }
+            _ => text.to_owned(),
+        }
+    }
+
+    /// Uppercases the first letter of a lowercase first word.
+    fn uppercase_first(text: &str) -> String {
+        let word = text.split_whitespace().next().unwrap_or("");
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first)
+                if first.is_lowercase() && chars.all(char::is_lowercase) =>
+            {
+                first.to_uppercase().collect::<String>()
+                    + &text[first.len_utf8()..]
+            }
+            _ => text.to_owned(),
+        }
+    }
+
+    /// Wraps the paragraph at the given width, where the tags are never split
+    /// and no line may start like a list item, comment or footer.
+    fn wrap_paragraph(text: &str, max_width: usize) -> String {
+        let mut words: Vec<String> = vec![];
+        let mut within_tag = false;
+        for word in text.split_whitespace() {
+            match words.last_mut() {
+                Some(last) if within_tag => {
+                    last.push(' ');
+                    last.push_str(word);
+                    within_tag = !word.contains(':');
+                }
+                _ => {
+                    within_tag =
+                        word.starts_with(':') && !word[1..].contains(':');
+                    words.push(word.to_owned());
+                }
+            }
+        }
+
+        let mut lines: Vec<String> = vec![];
+        for word in words {
+            let breakable =
+                !word.starts_with(['-', '#']) && !RE_FOOTER_KEY.is_match(&word);
+            match lines.last_mut() {
+                Some(line)
+                    if !breakable
+                        || width::display_width(line)
+                            + 1
+                            + width::display_width(&word)
+                            <= max_width =>
+                {
+                    line.push(' ');
+                    line.push_str(&word);
+                }
+                _ => lines.push(word),
+            }
+        }
+        lines.join("\n")
+    }
+
     /// Prints the commits without any template
+    #[cfg(feature = "git")]
     pub fn print(
         &self,
         compact: &bool,
         template: Option<&str>,
+        config: &Config,
     ) -> Result<Vec<u8>, Error> {
-        let mut term = term::stdout()
-            .ok_or_else(|| format_err!("Could not print to terminal"))?;
+        let mut term =
+            output::terminal(config.use_pager, config.pager.as_deref());
+        let vec = self.print_to_term(&mut term, compact, template, config)?;
+        writeln!(term)?;
+        term.finish()?;
+        Ok(vec)
+    }
+
+    /// Returns a parser containing only the given release, which will be
+    /// printed without the configured changelog header and footer
+    pub fn select_release(
+        &self,
+        release: &str,
+        config: &Config,
+    ) -> Result<Parser, Error> {
+        let parsed_tag = match self.result.iter().find(|parsed_tag| {
+            parsed_tag
+                .name
+                .split(MERGED_TAGS_SEPARATOR)
+                .any(|name| name == release)
+        }) {
+            Some(parsed_tag) => parsed_tag.clone(),
+            None => bail!("Release '{}' not found in the parsed log.", release),
+        };
+        let mut config = config.clone();
+        config.changelog_header = None;
+        config.changelog_footer = None;
+        Ok(Parser {
+            config,
+            result: vec![parsed_tag],
+        })
+    }
+
+    /// Renders the commits like `print`, but without any terminal output
+    pub fn render(
+        &self,
+        compact: &bool,
+        template: Option<&str>,
+        config: &Config,
+    ) -> Result<Vec<u8>, Error> {
+        let mut term: Box<dyn ColorWriter> = Box::new(SilentWriter);
+        self.print_to_term(&mut term, compact, template, config)
+    }
+
+    /// Renders the commits like `render`, where the terminal output is
+    /// recorded including its colors, see `output::replay`.
+    #[cfg(feature = "git")]
+    pub fn record(
+        &self,
+        compact: &bool,
+        template: Option<&str>,
+        config: &Config,
+    ) -> Result<(Vec<u8>, Vec<output::Segment>), Error> {
+        let (recorder, segments) = output::Recorder::new();
+        let mut term: Box<dyn ColorWriter> = Box::new(recorder);
+        let vec = self.print_to_term(&mut term, compact, template, config)?;
+        let segments = segments.borrow().clone();
+        Ok((vec, segments))
+    }
+
+    fn print_to_term(
+        &self,
+        term: &mut Box<dyn ColorWriter>,
+        compact: &bool,
+        template: Option<&str>,
+        config: &Config,
+    ) -> Result<Vec<u8>, Error> {
+        // Pipe the entries through the external translation command first,
+        // where a `{language}` placeholder is only expanded for the
+        // `translation_profiles`
+        if let Some(ref command) = config.translate_cmd {
+            if !command.contains("{language}") {
+                let mut config = config.clone();
+                config.translate_cmd = None;
+                return self
+                    .translate(command)?
+                    .print_to_term(term, compact, template, &config);
+            }
+        }
+
         let mut vec = vec![];
 
+        // Print the changelog header if configured
+        if let Some(ref header) = config.changelog_header {
+            writeln!(term, "{}", header)?;
+            writeln!(vec, "{}", header)?;
+        }
+
         // Print every tag
         for (index, tag) in self.result.iter().enumerate() {
             tag.print_to_term_and_write_to_vector(
-                &mut term,
+                term,
                 &mut vec,
                 compact,
-                &self.config,
+                config,
                 template,
                 (index, self.result.len()),
             )?;
         }
 
-        writeln!(term, "")?;
+        // Print the changelog footer if configured
+        if let Some(ref footer) = config.changelog_footer {
+            writeln!(term, "\n{}", footer)?;
+            writeln!(vec, "\n{}", footer)?;
+        }
         Ok(vec)
     }
 
+    /// Returns a parser whose entry texts are piped through the shell
+    /// command, where every distinct text is only translated once.
+    fn translate(&self, command: &str) -> Result<Parser, Error> {
+        let mut translations = BTreeMap::new();
+        for text in self
+            .result
+            .iter()
+            .flat_map(|tag| &tag.commits)
+            .flat_map(|commit| commit.texts())
+        {
+            if !translations.contains_key(text) {
+                let translated = Self::run_translate_cmd(command, text)?;
+                translations.insert(text.to_owned(), translated);
+            }
+        }
+        let mut parser = self.clone();
+        for commit in parser.result.iter_mut().flat_map(|t| &mut t.commits) {
+            commit.map_texts(|text| {
+                translations
+                    .get(text)
+                    .cloned()
+                    .unwrap_or_else(|| text.to_owned())
+            });
+        }
+        Ok(parser)
+    }
+
+    /// Pipes the text through the shell command and returns its output
+    /// without the trailing newline.
+    fn run_translate_cmd(command: &str, text: &str) -> Result<String, Error> {
+        let error = || ErrorKind::Config("translate_cmd".to_owned());
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context(error())?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes()).context(error())?;
+        }
+        let output = child.wait_with_output().context(error())?;
+        if !output.status.success() {
+            return Err(error().wrap(format_err!(
+                "The translation command '{}' failed: {}",
+                command,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8(output.stdout)?
+            .trim_end_matches('\n')
+            .to_owned())
+    }
+
     /// Returns all tags recursively from a toml table
+    #[cfg(feature = "git")]
     pub fn get_tags_from_toml(
         &self,
         table: &toml::value::Table,
@@ -1114,7 +2602,8 @@ impl Parser {
 mod tests {
     use super::*;
     use crate::config::{self, Config};
-    use term;
+    use chrono::TimeZone;
+    use proptest::{prop_assert, prop_assert_eq, proptest};
 
     fn get_parser() -> Parser {
         Parser {
@@ -1154,7 +2643,7 @@ mod tests {
             assert_eq!(commit.summary.category, "Changed");
             assert_eq!(commit.summary.text, "my commit summary");
             assert_eq!(commit.summary.tags.len(), 0);
-            let mut t = term::stdout().unwrap();
+            let mut t = output::stdout();
             assert!(commit
                 .print_to_term_and_write_to_vector(
                     &mut t,
@@ -1189,7 +2678,7 @@ mod tests {
             assert_eq!(commit.summary.category, "Changed");
             assert_eq!(commit.summary.text, "my commit summary");
             assert_eq!(commit.summary.tags.len(), 0);
-            let mut t = term::stdout().unwrap();
+            let mut t = output::stdout();
             assert!(commit
                 .print_to_term_and_write_to_vector(
                     &mut t,
@@ -1227,7 +2716,7 @@ mod tests {
                 commit.summary.tags,
                 vec!["tag1".to_owned(), "tag2".to_owned(), "tag3".to_owned()]
             );
-            let mut t = term::stdout().unwrap();
+            let mut t = output::stdout();
             assert!(commit
                 .print_to_term_and_write_to_vector(
                     &mut t,
@@ -1265,7 +2754,7 @@ mod tests {
                 commit.summary.tags,
                 vec!["1234".to_owned(), "some tag".to_owned()]
             );
-            let mut t = term::stdout().unwrap();
+            let mut t = output::stdout();
             assert!(commit
                 .print_to_term_and_write_to_vector(
                     &mut t,
@@ -1285,6 +2774,670 @@ mod tests {
         }
     }
 
+    #[test]
+    fn format_tag_date() {
+        let mut config = Config::new();
+        let mut tag = ParsedTag::from_messages("v1", &[], &config);
+        tag.date = Utc.ymd(2016, 9, 12).and_hms(23, 30, 0);
+        assert_eq!(tag.format_date(&config).unwrap(), "2016-09-12");
+        config.date_format = "%d.%m.%Y %H:%M".to_owned();
+        assert_eq!(tag.format_date(&config).unwrap(), "12.09.2016 23:30");
+        config.timezone = "local".to_owned();
+        assert!(tag.format_date(&config).is_ok());
+        config.timezone = "Local".to_owned();
+        assert!(tag.format_date(&config).is_err());
+        config.timezone = "local".to_owned();
+        config.date_format = "%Q".to_owned();
+        assert!(tag.format_date(&config).is_err());
+    }
+
+    #[test]
+    fn render_max_entries_per_category() {
+        let mut config = config::Config::new();
+        config.colored_output = false;
+        config.max_entries_per_category = Some(1);
+        config.full_log_url = Some("https://example.com/log".to_owned());
+        let mut tag = ParsedTag::from_messages(
+            "v1",
+            &["Added a", "Added b", "Added c", "Fixed d"],
+            &config,
+        );
+        tag.date = Utc.ymd(2016, 9, 12).and_hms(23, 30, 0);
+        let parser = Parser {
+            config: config.clone(),
+            result: vec![tag],
+        };
+        let output = parser.render(&true, None, &config).unwrap();
+        let output = str::from_utf8(&output).unwrap();
+        assert!(output.contains("- [Added] a\n- [Fixed] d"));
+        assert!(!output.contains("- [Added] b"));
+        assert!(output.contains(
+            "- [Added] … and 2 more ([full log](https://example.com/log))"
+        ));
+    }
+
+    #[test]
+    fn render_folded_duplicates() {
+        let mut config = config::Config::new();
+        config.colored_output = false;
+        config.fold_duplicates = true;
+        let oid = Oid::from_str("1234567890").unwrap();
+        let mut tag = ParsedTag::from_messages(
+            "v1",
+            &["Changed dep", "Added a", "Changed dep", "Changed dep"],
+            &config,
+        );
+        tag.date = Utc.ymd(2016, 9, 12).and_hms(23, 30, 0);
+        for commit in &mut tag.commits {
+            commit.oid = Some(oid);
+            commit.summary.oid = Some(oid);
+        }
+        let parser = Parser {
+            config: config.clone(),
+            result: vec![tag],
+        };
+        let output = parser.render(&true, None, &config).unwrap();
+        let output = str::from_utf8(&output).unwrap();
+        assert!(output.contains("- [Changed] dep (×3)\n- [Added] a\n"));
+
+        config.show_commit_hash = true;
+        let output = parser.render(&true, None, &config).unwrap();
+        let output = str::from_utf8(&output).unwrap();
+        assert!(
+            output.contains("- [Changed] dep (1234567, 1234567, 1234567)\n")
+        );
+    }
+
+    #[test]
+    fn parse_and_render_gitmoji() {
+        let mut config = config::Config::new();
+        config.colored_output = false;
+        config.parse_gitmoji = true;
+        config
+            .category_emojis
+            .insert("Added".to_owned(), "🚀".to_owned());
+        let parser = Parser {
+            config: config.clone(),
+            result: vec![],
+        };
+        let parse = |message| parser.parse_commit_message(message, None);
+        let commit = parse("🚀 a rocket").unwrap();
+        assert_eq!(commit.summary.category, "Added");
+        assert_eq!(commit.summary.text, "a rocket");
+        let commit = parse("⚡️ the speed").unwrap();
+        assert_eq!(commit.summary.category, "Improved");
+        assert_eq!(commit.summary.text, "the speed");
+        let commit = parse(":bug: a bug :tag:").unwrap();
+        assert_eq!(commit.summary.category, "Fixed");
+        assert_eq!(commit.summary.tags, vec!["tag".to_owned()]);
+        assert!(parse("🦀 no gitmoji").is_err());
+
+        let mut tag =
+            ParsedTag::from_messages("v1", &["✨ a sparkle"], &config);
+        tag.date = Utc.ymd(2016, 9, 12).and_hms(23, 30, 0);
+        let parser = Parser {
+            config: config.clone(),
+            result: vec![tag],
+        };
+        let output = parser.render(&true, None, &config).unwrap();
+        let output = str::from_utf8(&output).unwrap();
+        assert!(output.contains("- 🚀 [Added] a sparkle\n"));
+    }
+
+    #[test]
+    fn render_short_summary_width() {
+        let mut config = config::Config::new();
+        config.colored_output = false;
+        config.short_summary_width = Some(10);
+        let mut tag = ParsedTag::from_messages(
+            "v1",
+            &["Added some very long text", "Fixed 日本語テキストです"],
+            &config,
+        );
+        tag.date = Utc.ymd(2016, 9, 12).and_hms(23, 30, 0);
+        let parser = Parser {
+            config: config.clone(),
+            result: vec![tag],
+        };
+        let output = parser.render(&true, None, &config).unwrap();
+        let output = str::from_utf8(&output).unwrap();
+        assert!(output.contains("- [Added] some very…\n- [Fixed] 日本語テ…\n"));
+
+        config.short_summary_wrap = true;
+        let output = parser.render(&true, None, &config).unwrap();
+        let output = str::from_utf8(&output).unwrap();
+        assert!(output.contains(
+            "- [Added] some very\n          long text\n\
+             - [Fixed] 日本語テキ\n          ストです\n"
+        ));
+        let output = parser.render(&false, None, &config).unwrap();
+        let output = str::from_utf8(&output).unwrap();
+        assert!(output.contains("- [Added] some very long text\n"));
+    }
+
+    #[test]
+    fn parse_and_render_pull_request() {
+        let mut config = config::Config::new();
+        config.colored_output = false;
+        let parser = Parser {
+            config: config.clone(),
+            result: vec![],
+        };
+        let commit = parser
+            .parse_commit_message("Added some feature (#1234)", None)
+            .unwrap();
+        assert_eq!(commit.summary.text, "some feature");
+        assert_eq!(commit.summary.pull_request, Some(1234));
+        let commit = parser
+            .parse_commit_message("Fixed issue (#12) again", None)
+            .unwrap();
+        assert_eq!(commit.summary.text, "issue (#12) again");
+        assert_eq!(commit.summary.pull_request, None);
+
+        let mut tag = ParsedTag::from_messages(
+            "v1",
+            &["Added some feature (#1234)"],
+            &config,
+        );
+        tag.date = Utc.ymd(2016, 9, 12).and_hms(23, 30, 0);
+        let parser = Parser {
+            config: config.clone(),
+            result: vec![tag],
+        };
+        let output = parser.render(&true, None, &config).unwrap();
+        assert!(str::from_utf8(&output)
+            .unwrap()
+            .contains("- [Added] some feature (#1234)\n"));
+
+        config.pull_request_url =
+            Some("https://github.com/user/project/pull/".to_owned());
+        let output = parser.render(&true, None, &config).unwrap();
+        assert!(str::from_utf8(&output).unwrap().contains(
+            "- [Added] some feature \
+             ([#1234](https://github.com/user/project/pull/1234))\n"
+        ));
+
+        config.pull_request_url = None;
+        config.repository_url = Some("https://codeberg.org/u/p".to_owned());
+        let output = parser.render(&true, None, &config).unwrap();
+        assert!(str::from_utf8(&output).unwrap().contains(
+            "- [Added] some feature ([#1234](https://codeberg.org/u/p/pulls/1234))\n"
+        ));
+
+        // GitLab suffixes reference issues instead of merge requests
+        config.repository_url = Some("https://gitlab.com/g/p".to_owned());
+        let output = parser.render(&true, None, &config).unwrap();
+        assert!(str::from_utf8(&output).unwrap().contains(
+            "- [Added] some feature ([#1234](https://gitlab.com/g/p/-/issues/1234))\n"
+        ));
+
+        config.show_pull_requests = false;
+        let output = parser.render(&true, None, &config).unwrap();
+        assert!(str::from_utf8(&output)
+            .unwrap()
+            .contains("- [Added] some feature\n"));
+    }
+
+    #[test]
+    fn render_translated_entries() {
+        let mut config = config::Config::new();
+        config.colored_output = false;
+        let mut tag = ParsedTag::from_messages(
+            "v1",
+            &["Added some feature\n\n- Fixed a bug"],
+            &config,
+        );
+        tag.date = Utc.ymd(2016, 9, 12).and_hms(23, 30, 0);
+        let parser = Parser {
+            config: config.clone(),
+            result: vec![tag],
+        };
+        config.translate_cmd = Some("tr a-z A-Z".to_owned());
+        let output = parser.render(&false, None, &config).unwrap();
+        let output = str::from_utf8(&output).unwrap();
+        assert!(output.contains("- [Added] SOME FEATURE\n    - [Fixed] A BUG"));
+
+        config.translate_cmd = Some("tr a-z A-Z # {language}".to_owned());
+        let output = parser.render(&false, None, &config).unwrap();
+        assert!(str::from_utf8(&output).unwrap().contains("some feature"));
+
+        config.translate_cmd = Some("exit 1".to_owned());
+        assert!(parser.render(&false, None, &config).is_err());
+    }
+
+    #[test]
+    fn split_prefix_and_footer() {
+        assert_eq!(
+            prefix_and_footer(
+                "JIRA-12 wip\n\nSome text\n\nSigned-off-by: Jo <jo@a.b>\n\
+                 Change-Id: I1234\n"
+            ),
+            (
+                Some("JIRA-12"),
+                Some("Signed-off-by: Jo <jo@a.b>\nChange-Id: I1234")
+            )
+        );
+        assert_eq!(prefix_and_footer("wip\n\nSome text\n"), (None, None));
+        assert_eq!(prefix_and_footer("Closes: #1"), (None, None));
+    }
+
+    #[test]
+    fn parse_changelog_trailer() {
+        let parser = Parser {
+            config: config::Config::new(),
+            result: vec![],
+        };
+        let commit = parser
+            .parse_commit_message(
+                "Fixed the thing\n\nSome body\n\n\
+                 Changelog: The login works again",
+                None,
+            )
+            .unwrap();
+        assert_eq!(commit.summary.category, "Fixed");
+        assert_eq!(commit.summary.text, "The login works again");
+        assert!(commit.footer.is_empty());
+        assert!(!commit.is_excluded());
+
+        let commit = parser
+            .parse_commit_message(
+                "Changed internal stuff\n\nchangelog: None",
+                None,
+            )
+            .unwrap();
+        assert!(commit.is_excluded());
+    }
+
+    #[test]
+    fn render_group_tag_prefix() {
+        let mut config = config::Config::new();
+        config.colored_output = false;
+        config.group_tag_prefix = Some("epic/".to_owned());
+        let mut tag = ParsedTag::from_messages(
+            "v1",
+            &[
+                "Added login :epic/auth:",
+                "Fixed invoices :epic/billing:",
+                "Fixed typo",
+                "Changed tokens :epic/auth:",
+            ],
+            &config,
+        );
+        tag.date = Utc.ymd(2016, 9, 12).and_hms(23, 30, 0);
+        let parser = Parser {
+            config: config.clone(),
+            result: vec![tag],
+        };
+        let output = parser.render(&true, None, &config).unwrap();
+        let output = str::from_utf8(&output).unwrap();
+        assert!(output.contains(
+            ":\n## Auth\n- [Added] login\n- [Changed] tokens\n\n\
+             ## Billing\n- [Fixed] invoices\n\n## Other\n- [Fixed] typo\n"
+        ));
+    }
+
+    #[test]
+    fn render_highlight_tag() {
+        let mut config = config::Config::new();
+        config.colored_output = false;
+        let mut tag = ParsedTag::from_messages(
+            "v1",
+            &[
+                "Fixed typo",
+                "Added login :highlight:",
+                "Improved speed",
+                "Fixed crash :highlight:",
+            ],
+            &config,
+        );
+        tag.date = Utc.ymd(2016, 9, 12).and_hms(23, 30, 0);
+        let mut parser = Parser {
+            config: config.clone(),
+            result: vec![tag],
+        };
+        let output = parser.render(&true, None, &config).unwrap();
+        assert!(str::from_utf8(&output).unwrap().ends_with(
+            ":\n## Highlights\n- [Added] login\n- [Fixed] crash\n\n\
+             - [Fixed] typo\n- [Improved] speed\n"
+        ));
+
+        // Pinned entries count towards the maximum of their category
+        config.max_entries_per_category = Some(1);
+        let output = parser.render(&true, None, &config).unwrap();
+        assert!(str::from_utf8(&output).unwrap().ends_with(
+            ":\n## Highlights\n- [Added] login\n- [Fixed] crash\n\n\
+             - [Improved] speed\n- [Fixed] … and 1 more\n"
+        ));
+
+        // Templates print the pinned entries before their sections
+        let mut tag = ParsedTag::from_messages(
+            "v1",
+            &["Added login :tag1: :highlight:", "Fixed crash :tag1:"],
+            &config,
+        );
+        tag.date = Utc.ymd(2016, 9, 12).and_hms(23, 30, 0);
+        parser.result = vec![tag];
+        let output = parser
+            .render(&true, Some("./tests/template.toml"), &config)
+            .unwrap();
+        assert!(str::from_utf8(&output).unwrap().contains(
+            ":\n## Highlights\n- [Added] login\n\n## Section 1\n- … and 1 \
+             more\n"
+        ));
+
+        // List items pin their commit unless compact, where the header is
+        // translated like the categories
+        let mut tag = ParsedTag::from_messages(
+            "v1",
+            &["Added login\n\n- [Fixed] crash :highlight:", "Fixed typo"],
+            &config,
+        );
+        tag.date = Utc.ymd(2016, 9, 12).and_hms(23, 30, 0);
+        parser.result = vec![tag];
+        config.max_entries_per_category = None;
+        config
+            .translations
+            .insert("Highlights".to_owned(), "Höhepunkte".to_owned());
+        let output = parser.render(&false, None, &config).unwrap();
+        assert!(str::from_utf8(&output).unwrap().ends_with(
+            ":\n## Höhepunkte\n- [Added] login\n    - [Fixed] crash\n\n\
+             - [Fixed] typo\n"
+        ));
+        let output = parser.render(&true, None, &config).unwrap();
+        assert!(!str::from_utf8(&output).unwrap().contains("Höhepunkte"));
+
+        config.highlight_tag.clear();
+        parser.config = config.clone();
+        let output = parser.render(&true, None, &config).unwrap();
+        assert!(!str::from_utf8(&output).unwrap().contains("Highlights"));
+    }
+
+    #[test]
+    fn print_translated_category() {
+        let mut config = Config::new();
+        config
+            .translations
+            .insert("Added".to_owned(), "Hinzugefügt".to_owned());
+        let commit = get_parser()
+            .parse_commit_message("Added my commit summary", None)
+            .unwrap();
+        let mut vec = vec![];
+        assert!(commit.print_default(&mut vec, &config, None).is_ok());
+        assert_eq!(
+            str::from_utf8(&vec).unwrap(),
+            "\n- [Hinzugefügt] my commit summary"
+        );
+    }
+
+    #[test]
+    fn render_changelog_header_and_footer() {
+        let mut config = Config::new();
+        config.changelog_header = Some("# Changelog".to_owned());
+        config.changelog_footer = Some("MIT licensed".to_owned());
+        let output = get_parser().render(&false, None, &config).unwrap();
+        assert_eq!(
+            str::from_utf8(&output).unwrap(),
+            "# Changelog\n\nMIT licensed\n"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn format_is_idempotent(message in "\\PC*(\n\n\\PC*){0,3}") {
+            let parser = get_parser();
+            let formatted = parser.format_commit_message(&message);
+            prop_assert_eq!(
+                parser.parse_commit_message(&message, None).is_ok(),
+                parser.parse_commit_message(&formatted, None).is_ok()
+            );
+            prop_assert_eq!(parser.format_commit_message(&formatted), formatted);
+        }
+
+        #[test]
+        fn parse_never_panics(message in "\\PC*(\n\n\\PC*){0,3}") {
+            get_parser().parse_commit_message(&message, None).ok();
+        }
+
+        #[test]
+        fn parse_never_panics_on_syntax(
+            message in "[\\[\\]:#\\- \n\tA-Za-z0-9(){}.,]{0,64}"
+        ) {
+            let mut parser = get_parser();
+            parser.parse_commit_message(&message, None).ok();
+            parser.config.parse_gitmoji = true;
+            parser.parse_commit_message(&message, None).ok();
+        }
+
+        #[test]
+        fn parse_valid_summaries(
+            category in "(Added|Changed|Fixed|Improved|Removed)",
+            text in "[a-z][a-z ]{0,30}[a-z]",
+        ) {
+            let commit = get_parser()
+                .parse_commit_message(&format!("{} {}", category, text), None)
+                .unwrap();
+            prop_assert_eq!(commit.summary.category, category);
+            prop_assert_eq!(commit.summary.text, text);
+        }
+
+        #[test]
+        fn render_round_trip(
+            message in "([A-Z]{2,4}-[0-9]{1,4} )?\\[?(Added|Changed|Fixed)\\]? \
+                        [a-z][a-z. ]{0,20}( :[a-z]{1,5}(, [a-z]{1,5})*:)?\
+                        ( \\(#[0-9]{1,4}\\))?\
+                        (\n\n(- (\\[?(Added|Fixed)\\]? )?[a-z][a-z. ]{0,20}\
+                        ( :[a-z]{1,5}:)?(\n- [a-z][a-z. ]{0,20})*\
+                        |[A-Za-z][a-z., ]{0,30}( :[a-z]{1,5}:)?\
+                        |[A-Z][a-z]{1,8}(-[a-z]{1,5})?: [a-z ]{1,20})){0,4}"
+        ) {
+            let parser = get_parser();
+            let commit = parser.parse_commit_message(&message, None).unwrap();
+            let rendered = commit.to_message(&parser.config);
+            let reparsed =
+                parser.parse_commit_message(&rendered, None).unwrap();
+            prop_assert!(commit.is_equivalent(&reparsed), "{}", rendered);
+            prop_assert_eq!(reparsed.to_message(&parser.config), rendered);
+        }
+    }
+
+    #[test]
+    fn render_commit_message() {
+        let mut parser = get_parser();
+        let message = "JIRA-1234 Added :ui: some feature. :a, b: (#12)\n\n\
+                       Some paragraph..\n\n\
+                       - Fixed item :other:\n\
+                       - another item\n\n\
+                       Reviewed-by: Me\n\
+                       Changelog: Some other feature";
+        let oid = Oid::from_str(&"1".repeat(40)).ok();
+        let commit = parser.parse_commit_message(message, oid).unwrap();
+        let rendered = commit.to_message(&parser.config);
+        assert_eq!(
+            rendered,
+            "JIRA-1234 [Added] Some other feature (#12) :a, b:\n\n\
+             Some paragraph..\n\n\
+             - [Fixed] item :other:\n\
+             - another item\n\n\
+             Reviewed-by: Me"
+        );
+        let reparsed = parser.parse_commit_message(&rendered, None).unwrap();
+        assert!(commit.is_equivalent(&reparsed));
+        assert_ne!(commit, reparsed);
+
+        let mut other = reparsed.clone();
+        other.body.pop();
+        assert!(!commit.is_equivalent(&other));
+
+        parser.config.category_delimiters =
+            vec!["<".to_owned(), ">".to_owned()];
+        let rendered = commit.to_message(&parser.config);
+        assert!(rendered.starts_with("JIRA-1234 <Added> Some other feature"));
+        let reparsed = parser.parse_commit_message(&rendered, None).unwrap();
+        assert!(commit.is_equivalent(&reparsed));
+    }
+
+    #[test]
+    fn format_wrapped_list_items() {
+        let mut parser = get_parser();
+        parser.config.max_body_line_length = 24;
+        let message = "Added some feature\n\n\
+                       - Fixed the bug with a very long description :a:\n\
+                       \x20 - Nested item which is too long as well\n\
+                       - Short item";
+        let formatted = parser.format_commit_message(message);
+        assert_eq!(
+            formatted,
+            "[Added] some feature\n\n\
+             - [Fixed] the bug with a\n\
+             \x20 very long description\n\
+             \x20 :a:\n\
+             \x20 - Nested item which is\n\
+             \x20   too long as well\n\
+             - Short item"
+        );
+        assert_eq!(parser.format_commit_message(&formatted), formatted);
+        let commit = parser.parse_commit_message(&formatted, None).unwrap();
+        let original = parser.parse_commit_message(message, None).unwrap();
+        assert_eq!(commit.body, original.body);
+        assert_eq!(
+            commit.texts()[1..],
+            [
+                "the bug with a very long description",
+                "Nested item which is too long as well",
+                "Short item"
+            ]
+        );
+    }
+
+    #[test]
+    fn format_commit_message() {
+        let mut parser = get_parser();
+        parser.config.message_width = 20;
+        let message = "JIRA-1234   Added  The  feature. :a:\n\n\n\
+                       this is a   paragraph with some words :tag1, tag2: \
+                       and - a ####### commit-msg: in it\n\n\
+                       - Fixed   Some bug\n\
+                       \x20 - [Changed] API  usage\n\
+                       - List item\n\n\
+                       # A comment\n\n\
+                       Reviewed-by:   Me  ";
+        let formatted = parser.format_commit_message(message);
+        assert_eq!(
+            formatted,
+            "JIRA-1234 [Added] the feature :a:\n\n\
+             This is a paragraph\n\
+             with some words\n\
+             :tag1, tag2: and - a ####### commit-msg:\n\
+             in it\n\n\
+             - [Fixed] some bug\n\
+             \x20 - [Changed] API usage\n\
+             - List item\n\n\
+             # A comment\n\n\
+             Reviewed-by: Me"
+        );
+        assert_eq!(parser.format_commit_message(&formatted), formatted);
+        let commit = parser.parse_commit_message(&formatted, None).unwrap();
+        assert_eq!(commit.body.len(), 2);
+        assert_eq!(commit.footer.len(), 1);
+
+        assert_eq!(parser.format_commit_message("Added API"), "[Added] API");
+
+        let mut capitalized = parser.clone();
+        capitalized.config.style_checks = vec!["capitalized".to_owned()];
+        assert_eq!(
+            capitalized.format_commit_message("Added some\n\n- Fixed a bug"),
+            "[Added] Some\n\n- [Fixed] A bug"
+        );
+
+        // Invalid messages and categories which are not at the beginning are
+        // kept
+        for message in &[
+            "Some feature",
+            "Some Added feature",
+            "Added feature\n\n- a Fixed bug",
+        ] {
+            assert_eq!(parser.format_commit_message(message), *message);
+        }
+    }
+
+    #[test]
+    fn parse_lone_brackets() {
+        let mut parser = get_parser();
+        for message in &[
+            "[",
+            "]",
+            "[]",
+            "[Added",
+            "Added [",
+            "[Added]",
+            "- [",
+            "Added a\n\n- [",
+            "Added a\n\n[",
+            "JIRA-[",
+            "✨[",
+        ] {
+            parser.parse_commit_message(message, None).ok();
+            parser.config.parse_gitmoji = true;
+            parser.parse_commit_message(message, None).ok();
+            parser.config.parse_gitmoji = false;
+        }
+        assert!(parser.parse_commit_message("[Added] a [", None).is_ok());
+    }
+
+    #[test]
+    fn parse_with_changing_categories() {
+        let mut parser = get_parser();
+        assert!(parser.parse_commit_message("Security fix", None).is_err());
+        parser.config.categories = vec!["Security".to_owned()];
+        let commit = parser
+            .parse_commit_message("Security fix\n\n- Security list", None)
+            .unwrap();
+        assert_eq!(commit.summary.category, "Security");
+        assert_eq!(commit.summary.text, "fix");
+        parser.config.categories = vec!["(".to_owned()];
+        assert!(parser.parse_commit_message("Added a", None).is_err());
+        assert!(get_parser().parse_commit_message("Added a", None).is_ok());
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let parser = get_parser();
+        let mut commit = parser
+            .parse_commit_message(
+                "JIRA-1234 Added feature :tag:\n\n\
+                 Some paragraph\n\n\
+                 - Fixed item :other:\n\n\
+                 Reviewed-by: Me",
+                Some(Oid::from_str(&"1".repeat(40)).unwrap()),
+            )
+            .unwrap();
+        commit.summary.issue = Some(Issue {
+            title: "Some issue".to_owned(),
+            status: "Done".to_owned(),
+        });
+        let tag = ParsedTag {
+            name: "v1".to_owned(),
+            title: Some("The release".to_owned()),
+            date: Utc.ymd(2016, 9, 12).and_hms(23, 30, 0),
+            oid: commit.oid,
+            commit_ids: vec![commit.oid.unwrap()],
+            commits: vec![commit],
+            message_ids: vec![],
+        };
+
+        let json = serde_json::to_string(&tag).unwrap();
+        assert_eq!(serde_json::from_str::<ParsedTag>(&json).unwrap(), tag);
+
+        // TOML omits the missing values
+        let toml = toml::to_string(&Value::try_from(&tag).unwrap()).unwrap();
+        assert_eq!(toml::from_str::<ParsedTag>(&toml).unwrap(), tag);
+
+        assert!(serde_json::from_str::<ParsedTag>(
+            &json.replace(&"1".repeat(40), "wrong")
+        )
+        .is_err());
+    }
+
     #[test]
     fn parse_commit_failure_1() {
         parse_and_print_error("None");
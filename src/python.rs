@@ -0,0 +1,119 @@
+//! The Python bindings for verifying and parsing commit messages and
+//! generating the changelog, which are only available if the `python`
+//! feature is enabled. The extension module is the shared library of the
+//! `ffi` crate, which is built via `cargo build --release -p git-journal-ffi
+//! --features python` and imported as `gitjournal` after renaming it to
+//! `gitjournal.so`:
+//!
+//! ```python
+//! import gitjournal
+//!
+//! commit = gitjournal.parse("Added some feature :ui:")
+//! assert commit["summary"]["category"] == "Added"
+//! assert gitjournal.verify("Some invalid message") is not None
+//! releases = gitjournal.generate(".", format="json")["releases"]
+//! ```
+
+use crate::{
+    error::Report, parse_commit_message, Config, GitJournal, ParseOptions,
+};
+use failure::Error;
+use pyo3::{
+    create_exception, exceptions::PyException, prelude::*, types::PyString,
+};
+
+create_exception!(
+    gitjournal,
+    GitJournalError,
+    PyException,
+    "An error reported by the library, e.g. an invalid commit message."
+);
+
+/// Converts the error into a `GitJournalError` including all its causes.
+fn to_py_err(error: Error) -> PyErr {
+    GitJournalError::new_err(format!("{:#}", Report::from(error)))
+}
+
+/// Converts the JSON into the corresponding Python objects.
+fn loads(py: Python<'_>, json: &str) -> PyResult<PyObject> {
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+/// Parses a commit message and returns the parsed commit as dictionary.
+///
+/// The configuration is read from the repository or file `config_path`, or
+/// the default configuration is used. Raises a `GitJournalError` if the
+/// message is not valid.
+#[pyfunction]
+#[pyo3(signature = (message, config_path = None))]
+fn parse(
+    py: Python<'_>,
+    message: &str,
+    config_path: Option<&str>,
+) -> PyResult<PyObject> {
+    let json = (|| -> Result<String, Error> {
+        let mut config = Config::new();
+        if let Some(config_path) = config_path {
+            config.load(config_path)?;
+        }
+        let commit = parse_commit_message(message, &config)?;
+        Ok(serde_json::to_string(&commit)?)
+    })()
+    .map_err(to_py_err)?;
+    loads(py, &json)
+}
+
+/// Verifies a commit message and returns the reason why it is not valid, or
+/// `None` if it is valid.
+///
+/// The configuration and rules of the repository at `path` are used like by
+/// `git journal verify`. Raises a `GitJournalError` if the repository could
+/// not be opened.
+#[pyfunction]
+#[pyo3(signature = (message, path = "."))]
+fn verify(message: &str, path: &str) -> PyResult<Option<String>> {
+    let journal = GitJournal::new(path).map_err(to_py_err)?;
+    Ok(journal
+        .verify_message(message)
+        .err()
+        .map(|e| format!("{:#}", Report::from(e))))
+}
+
+/// Generates the changelog of the repository at `path`.
+///
+/// The revision range defaults to "HEAD" including all tags. For the format
+/// "md" the Markdown is returned as string, for "json" the parsed document
+/// and for the other export formats like "yaml" their text.
+#[pyfunction]
+#[pyo3(signature = (path = ".", revision_range = None, format = "md"))]
+fn generate(
+    py: Python<'_>,
+    path: &str,
+    revision_range: Option<&str>,
+    format: &str,
+) -> PyResult<PyObject> {
+    let output = (|| -> Result<String, Error> {
+        let mut journal = GitJournal::new(path)?;
+        let revision_range = revision_range.unwrap_or("HEAD");
+        journal.parse_log(&ParseOptions::new(revision_range).all(true))?;
+        match format {
+            "md" | "markdown" => journal.parsed().render(false, None),
+            format => journal.export(format),
+        }
+    })()
+    .map_err(to_py_err)?;
+    match format {
+        "json" => loads(py, &output),
+        _ => Ok(PyString::new(py, &output).into_any().unbind()),
+    }
+}
+
+/// The `gitjournal` extension module.
+#[pymodule]
+fn gitjournal(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("GitJournalError", m.py().get_type::<GitJournalError>())?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(verify, m)?)?;
+    m.add_function(wrap_pyfunction!(generate, m)?)?;
+    Ok(())
+}
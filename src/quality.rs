@@ -0,0 +1,233 @@
+//! Scores of the commit hygiene of every release, which are based on the
+//! share of valid commit messages, of commits with a body or with a reference
+//! and on heuristics of the summary quality.
+
+use crate::{
+    config::Config,
+    parser::{BodyElement, ParsedCommit, ParsedTag},
+    rules::RE_ISSUE,
+    width,
+};
+use serde_derive::Serialize;
+use std::fmt::Write;
+
+/// Generic summaries which do not describe the change at all.
+static GENERIC_SUMMARIES: &[&str] = &[
+    "changes", "cleanup", "fix", "fixes", "misc", "stuff", "update", "updates",
+];
+
+/// The quality metrics of a single release, where all shares are between
+/// 0 and 1.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ReleaseQuality {
+    /// The name of the release, e.g. "v1.0.0" or "Unreleased"
+    pub release: String,
+
+    /// The number of commits of the release, including the invalid ones
+    pub commits: usize,
+
+    /// The share of commits with a valid commit message
+    pub valid: f64,
+
+    /// The share of valid commits with a body
+    pub bodies: f64,
+
+    /// The share of valid commits referencing an issue or pull request
+    pub references: f64,
+
+    /// The average quality of the summaries, see `summary_quality`
+    pub summaries: f64,
+
+    /// The overall score between 0 and 100, the average of all shares
+    pub score: f64,
+
+    /// The change of the score compared to the previous release, `None` for
+    /// the oldest release
+    pub trend: Option<f64>,
+}
+
+impl ReleaseQuality {
+    /// Scores the release, where the commits without id like manual entries
+    /// are ignored.
+    pub fn new(tag: &ParsedTag, config: &Config) -> Self {
+        let commits = tag
+            .commits
+            .iter()
+            .filter(|commit| {
+                commit.oid.is_some_and(|oid| tag.commit_ids.contains(&oid))
+            })
+            .collect::<Vec<_>>();
+        let share = |count: usize, total: usize| {
+            if total == 0 {
+                1.0
+            } else {
+                count as f64 / total as f64
+            }
+        };
+        let count = |f: fn(&ParsedCommit) -> bool| {
+            share(commits.iter().filter(|c| f(c)).count(), commits.len())
+        };
+        let valid = share(commits.len(), tag.commit_ids.len());
+        let bodies = count(|commit| !commit.body.is_empty());
+        let references = count(has_reference);
+        let summaries = if commits.is_empty() {
+            1.0
+        } else {
+            commits
+                .iter()
+                .map(|commit| summary_quality(commit, config))
+                .sum::<f64>()
+                / commits.len() as f64
+        };
+        ReleaseQuality {
+            release: tag.name.clone(),
+            commits: tag.commit_ids.len(),
+            valid,
+            bodies,
+            references,
+            summaries,
+            score: (valid + bodies + references + summaries) * 25.0,
+            trend: None,
+        }
+    }
+}
+
+/// Returns the quality of all releases, where the releases are expected to be
+/// sorted from the newest to the oldest one for the trends.
+pub fn score(releases: &[ParsedTag], config: &Config) -> Vec<ReleaseQuality> {
+    let mut qualities = releases
+        .iter()
+        .filter(|tag| !tag.commit_ids.is_empty())
+        .map(|tag| ReleaseQuality::new(tag, config))
+        .collect::<Vec<_>>();
+    for index in 1..qualities.len() {
+        qualities[index - 1].trend =
+            Some(qualities[index - 1].score - qualities[index].score);
+    }
+    qualities
+}
+
+/// Returns whether the commit references an issue or pull request via its
+/// prefix, a summary suffix like "(#12)", its texts or its footers.
+fn has_reference(commit: &ParsedCommit) -> bool {
+    !commit.summary.prefix.is_empty()
+        || commit.summary.pull_request.is_some()
+        || commit.summary.merge_request.is_some()
+        || commit.texts().iter().any(|text| RE_ISSUE.is_match(text))
+        || commit
+            .footer
+            .iter()
+            .any(|footer| RE_ISSUE.is_match(&footer.value))
+}
+
+/// Returns the share of the passed heuristics of the summary: a length
+/// between 10 characters and the `message_width`, more than two words, no
+/// work in progress marker, no generic text like "fix" and a body for
+/// summaries containing "and", which often indicates multiple changes.
+fn summary_quality(commit: &ParsedCommit, config: &Config) -> f64 {
+    let text = commit.summary.text.trim();
+    let length = width::display_width(text);
+    let words = text.split_whitespace().count();
+    let generic = GENERIC_SUMMARIES.contains(&text.to_lowercase().as_str());
+    let wip = text.split_whitespace().any(|word| {
+        word.trim_matches(&['[', ']', ':'][..])
+            .eq_ignore_ascii_case("wip")
+    });
+    let multiple = text.split_whitespace().any(|word| word == "and")
+        && !commit
+            .body
+            .iter()
+            .any(|element| matches!(element, BodyElement::List(_)));
+    let checks = [
+        (10..=config.message_width).contains(&length),
+        words > 2,
+        !wip,
+        !generic,
+        !multiple,
+    ];
+    checks.iter().filter(|check| **check).count() as f64 / checks.len() as f64
+}
+
+/// Renders the qualities as table with percentages and the trend of the
+/// score, e.g. "+5.0".
+pub fn table(qualities: &[ReleaseQuality]) -> String {
+    let width = qualities
+        .iter()
+        .map(|quality| width::display_width(&quality.release))
+        .chain(Some(7))
+        .max()
+        .unwrap_or_default();
+    let mut table = format!(
+        "{:<width$}  Commits  Valid  Bodies  References  Summaries  Score  \
+         Trend\n",
+        "Release",
+        width = width
+    );
+    let percent = |share: f64| format!("{:.0}%", share * 100.0);
+    for quality in qualities {
+        let trend = match quality.trend {
+            Some(trend) => format!("{:+.1}", trend),
+            None => "-".to_owned(),
+        };
+        let _ = writeln!(
+            table,
+            "{}{}  {:>7}  {:>5}  {:>6}  {:>10}  {:>9}  {:>5.1}  {:>5}",
+            quality.release,
+            " ".repeat(width - width::display_width(&quality.release)),
+            quality.commits,
+            percent(quality.valid),
+            percent(quality.bodies),
+            percent(quality.references),
+            percent(quality.summaries),
+            quality.score,
+            trend
+        );
+    }
+    table
+}
+
+#[cfg(all(test, feature = "git"))]
+mod tests {
+    use super::*;
+    use crate::oid::Oid;
+
+    #[test]
+    fn release_quality() {
+        let config = Config::new();
+        let oids = (1..=3)
+            .map(|i| Oid::from_str(&format!("{:040}", i)).unwrap())
+            .collect::<Vec<_>>();
+        let tag = |name: &str, messages: &[&str]| {
+            let mut tag = ParsedTag::from_messages(name, messages, &config);
+            tag.commit_ids = oids.clone();
+            for (commit, oid) in tag.commits.iter_mut().zip(&oids) {
+                commit.oid = Some(*oid);
+            }
+            tag
+        };
+        let releases = vec![
+            tag(
+                "v2",
+                &[
+                    "Added the login page\n\nSee #12.",
+                    "JIRA-1 Fixed the broken logout",
+                    "Fixed the cache and the logs WIP",
+                ],
+            ),
+            tag("v1", &["Fixed fix."]),
+        ];
+        let qualities = score(&releases, &config);
+        assert_eq!(qualities.len(), 2);
+        assert_eq!(qualities[0].valid, 1.0);
+        assert_eq!(qualities[0].bodies, 1.0 / 3.0);
+        assert_eq!(qualities[0].references, 2.0 / 3.0);
+        assert_eq!(qualities[1].valid, 1.0 / 3.0);
+        assert_eq!(qualities[1].summaries, 0.4);
+        assert!(qualities[0].trend.unwrap() > 0.0);
+        assert_eq!(qualities[1].trend, None);
+
+        let table = table(&qualities);
+        assert!(table.starts_with("Release  Commits  Valid"));
+        assert!(table.contains("\nv1             3    33%      0%"));
+    }
+}
@@ -0,0 +1,561 @@
+//! Custom checks of the commit messages, which can be implemented outside of
+//! the crate and registered at a `GitJournal` to be run by the verification.
+
+use crate::{
+    config::Config,
+    error::ErrorKind,
+    parser::{BodyElement, ParsedCommit},
+    width,
+};
+use failure::{bail, format_err, Error};
+use lazy_static::lazy_static;
+use log::{info, warn};
+use regex::Regex;
+
+lazy_static! {
+    pub(crate) static ref RE_ISSUE: Regex =
+        Regex::new(r"#\d+\b|\b[A-Z][A-Z0-9]+-\d+\b").unwrap();
+    static ref RE_SIGNOFF: Regex =
+        Regex::new(r"^([^<>]+) <([^<>@\s]+@[^<>\s]+)>$").unwrap();
+}
+
+/// The summary prefixes of transient commits created by `git commit --fixup`
+/// and `--squash`.
+static FIXUP_PREFIXES: &[&str] = &["fixup!", "squash!", "amend!"];
+
+/// Returns whether the verification of a transient commit is skipped, which
+/// will be squashed by `git rebase --autosquash`.
+///
+/// # Errors
+/// When the `fixup_commits` are rejected or the mode is unknown.
+pub(crate) fn skip_fixup(
+    message: &str,
+    config: &Config,
+) -> Result<bool, Error> {
+    let summary = message.lines().next().unwrap_or_default();
+    if !FIXUP_PREFIXES.iter().any(|p| summary.starts_with(p)) {
+        return Ok(false);
+    }
+    match config.fixup_commits.as_str() {
+        "allow" => {
+            info!("Skipping the verification of '{}'.", summary);
+            Ok(true)
+        }
+        "reject" => {
+            bail!("Fixup and squash commits are rejected: '{}'", summary)
+        }
+        mode => Err(ErrorKind::Config("fixup_commits".to_owned())
+            .wrap(format_err!("Unknown fixup commits mode '{}'", mode))),
+    }
+}
+
+/// Checks the built-in rules followed by the `custom` ones and reports all
+/// violations, where the sign-off has to match the `author` like "Name
+/// <email>".
+///
+/// # Errors
+/// When at least one of the rules is violated.
+pub(crate) fn check(
+    commit: &ParsedCommit,
+    message: &str,
+    config: &Config,
+    author: Option<String>,
+    custom: &[Box<dyn Rule>],
+) -> Result<(), Error> {
+    let builtin_rules: Vec<Box<dyn Rule>> = vec![
+        Box::new(StyleCheck),
+        Box::new(BodyLineLength),
+        Box::new(IssueReference),
+        Box::new(SignOff { author }),
+        #[cfg(feature = "spellcheck")]
+        Box::new(crate::spellcheck::SpellCheck),
+    ];
+    let violated = builtin_rules
+        .iter()
+        .chain(custom)
+        .filter(|rule| match rule.check(commit, message, config) {
+            Ok(()) => false,
+            Err(e) => {
+                warn!("Rule '{}' violated: {}", rule.name(), e);
+                true
+            }
+        })
+        .map(|rule| rule.name())
+        .collect::<Vec<_>>();
+    if !violated.is_empty() {
+        bail!(
+            "The commit message violates the rules: {}",
+            violated.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// A check of a commit message, which runs after the message has been parsed
+/// successfully by `GitJournal::verify`. All registered rules are checked and
+/// every violation is reported, before the verification fails.
+///
+/// # Examples
+///
+/// ```
+/// use failure::{bail, Error};
+/// use gitjournal::{Config, GitJournal, ParsedCommit, Rule};
+///
+/// struct NoWip;
+///
+/// impl Rule for NoWip {
+///     fn name(&self) -> &str {
+///         "no-wip"
+///     }
+///
+///     fn check(
+///         &self,
+///         commit: &ParsedCommit,
+///         _message: &str,
+///         _config: &Config,
+///     ) -> Result<(), Error> {
+///         if commit.summary.text.contains("WIP") {
+///             bail!("Work in progress must not be committed")
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let mut journal = GitJournal::new(".").unwrap();
+/// journal.register_rule(Box::new(NoWip));
+/// assert!(journal.verify_message("Added WIP feature").is_err());
+/// ```
+pub trait Rule: Send + Sync {
+    /// The name of the rule, which is reported on violations.
+    fn name(&self) -> &str;
+
+    /// Checks the parsed commit together with its raw message.
+    ///
+    /// # Errors
+    /// If the commit message violates the rule, with the reason as message.
+    fn check(
+        &self,
+        commit: &ParsedCommit,
+        message: &str,
+        config: &Config,
+    ) -> Result<(), Error>;
+}
+
+/// The built-in rule for the wording of the summaries, where every check of
+/// the `style_checks` can be enabled separately:
+///
+/// - "capitalized": The text after the category starts with a capital letter
+/// - "imperative": The text does not start with a verb like "adds", "added"
+///   or "adding" instead of "add"
+/// - "no_period": The summary does not end with a period
+/// - "no_wip": The summary does not contain "WIP"
+pub(crate) struct StyleCheck;
+
+impl StyleCheck {
+    /// Returns whether the word looks like a verb which is not in its
+    /// imperative form.
+    fn is_not_imperative(word: &str) -> bool {
+        static EXCEPTIONS: &[&str] = &[
+            "bed", "bring", "embed", "feed", "need", "proceed", "red", "seed",
+            "shed", "shred", "speed", "spring", "string", "thing", "wing",
+        ];
+        static ENDINGS: &[&str] = &[
+            "adds",
+            "changes",
+            "creates",
+            "deletes",
+            "fixes",
+            "implements",
+            "improves",
+            "makes",
+            "moves",
+            "removes",
+            "renames",
+            "updates",
+            "uses",
+        ];
+        let word = word.to_lowercase();
+        !EXCEPTIONS.contains(&word.as_str())
+            && (word.ends_with("ed")
+                || word.ends_with("ing")
+                || ENDINGS.contains(&word.as_str()))
+    }
+}
+
+impl Rule for StyleCheck {
+    fn name(&self) -> &str {
+        "style"
+    }
+
+    fn check(
+        &self,
+        commit: &ParsedCommit,
+        message: &str,
+        config: &Config,
+    ) -> Result<(), Error> {
+        let summary = message.lines().next().unwrap_or_default().trim();
+        let text = commit.summary.text.trim();
+        let first_word = text
+            .split_whitespace()
+            .find(|word| !word.starts_with(':'))
+            .unwrap_or_default();
+        let mut violations = vec![];
+        for check in &config.style_checks {
+            let violation = match check.as_str() {
+                "capitalized" => {
+                    if first_word.starts_with(char::is_lowercase) {
+                        Some("does not start with a capital letter")
+                    } else {
+                        None
+                    }
+                }
+                "imperative" => {
+                    if Self::is_not_imperative(first_word) {
+                        Some("does not use the imperative mood")
+                    } else {
+                        None
+                    }
+                }
+                "no_period" => {
+                    if summary.ends_with('.') {
+                        Some("ends with a period")
+                    } else {
+                        None
+                    }
+                }
+                "no_wip" => {
+                    if summary
+                        .split(|c: char| !c.is_alphanumeric())
+                        .any(|word| word.eq_ignore_ascii_case("wip"))
+                    {
+                        Some("marks a work in progress")
+                    } else {
+                        None
+                    }
+                }
+                _ => {
+                    return Err(ErrorKind::Config("style_checks".to_owned())
+                        .wrap(format_err!("Unknown style check '{}'", check)))
+                }
+            };
+            violations.extend(violation);
+        }
+        if !violations.is_empty() {
+            bail!("The summary {}", violations.join(", "));
+        }
+        Ok(())
+    }
+}
+
+/// The built-in rule for the width of the body lines, which must not exceed
+/// the `max_body_line_length`. Comments, footers and lines without any
+/// whitespace like long URLs are skipped, since they can not be wrapped.
+pub(crate) struct BodyLineLength;
+
+impl Rule for BodyLineLength {
+    fn name(&self) -> &str {
+        "body_line_length"
+    }
+
+    fn check(
+        &self,
+        commit: &ParsedCommit,
+        message: &str,
+        config: &Config,
+    ) -> Result<(), Error> {
+        let max_length = config.max_body_line_length;
+        if max_length == 0 {
+            return Ok(());
+        }
+        let long_lines = message
+            .lines()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, line)| {
+                let line = line.trim();
+                !line.starts_with('#')
+                    && !commit.footer.iter().any(|footer| {
+                        line.starts_with(&format!("{}:", footer.key))
+                            && line.ends_with(footer.value.trim())
+                    })
+                    && line.contains(char::is_whitespace)
+                    && width::display_width(line) > max_length
+            })
+            .map(|(index, _)| (index + 1).to_string())
+            .collect::<Vec<_>>();
+        if !long_lines.is_empty() {
+            bail!(
+                "The body lines {} exceed {} characters",
+                long_lines.join(", "),
+                max_length
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The built-in rule for the `issue_requirements` of the categories, where
+/// the summary or a list item of such a category requires a prefix or an
+/// issue reference like "#123" or "ABC-123" anywhere in the message.
+pub(crate) struct IssueReference;
+
+impl Rule for IssueReference {
+    fn name(&self) -> &str {
+        "issue_reference"
+    }
+
+    fn check(
+        &self,
+        commit: &ParsedCommit,
+        message: &str,
+        config: &Config,
+    ) -> Result<(), Error> {
+        let list_categories =
+            commit.body.iter().flat_map(|element| match *element {
+                BodyElement::List(ref list) => {
+                    list.iter().map(|item| &item.category).collect()
+                }
+                BodyElement::Paragraph(_) => vec![],
+            });
+        let mut required = vec![];
+        let mut error = false;
+        for category in Some(&commit.summary.category)
+            .into_iter()
+            .chain(list_categories)
+        {
+            match config.issue_requirements.get(category).map(|l| l.as_str()) {
+                None => continue,
+                Some("error") => error = true,
+                Some("warn") => {}
+                Some(level) => {
+                    return Err(ErrorKind::Config(
+                        "issue_requirements".to_owned(),
+                    )
+                    .wrap(format_err!(
+                        "Unknown requirement level '{}'",
+                        level
+                    )))
+                }
+            }
+            if !required.contains(category) {
+                required.push(category.to_owned());
+            }
+        }
+
+        let referenced = !commit.summary.prefix.is_empty()
+            || message
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .any(|line| RE_ISSUE.is_match(line));
+        if required.is_empty() || referenced {
+            return Ok(());
+        }
+        let message = format!(
+            "The category '{}' requires an issue reference like '#123'",
+            required.join("', '")
+        );
+        if error {
+            bail!(message);
+        }
+        warn!("{}", message);
+        Ok(())
+    }
+}
+
+/// The built-in rule for the Developer Certificate of Origin, where the
+/// `signoff` requires a valid `Signed-off-by: Name <email>` trailer, which
+/// has to match the author for "author".
+pub(crate) struct SignOff {
+    /// The author of the commit like "Name <email>", if known
+    pub author: Option<String>,
+}
+
+impl Rule for SignOff {
+    fn name(&self) -> &str {
+        "signoff"
+    }
+
+    fn check(
+        &self,
+        commit: &ParsedCommit,
+        _message: &str,
+        config: &Config,
+    ) -> Result<(), Error> {
+        let author = match config.signoff.as_str() {
+            "off" => return Ok(()),
+            "required" => None,
+            "author" => Some(self.author.as_ref().ok_or_else(|| {
+                format_err!("The author of the commit is not configured")
+            })?),
+            level => {
+                return Err(ErrorKind::Config("signoff".to_owned())
+                    .wrap(format_err!("Unknown sign-off level '{}'", level)))
+            }
+        };
+        let signoffs = commit
+            .footer
+            .iter()
+            .filter(|footer| footer.key.eq_ignore_ascii_case("Signed-off-by"))
+            .filter_map(|footer| RE_SIGNOFF.captures(footer.value.trim()))
+            .collect::<Vec<_>>();
+        match author {
+            None if signoffs.is_empty() => bail!(
+                "The commit message has no valid 'Signed-off-by: Name \
+                 <email>' trailer"
+            ),
+            Some(author) => {
+                let expected = RE_SIGNOFF.captures(author);
+                if !signoffs.iter().any(|cap| {
+                    expected.as_ref().is_some_and(|expected| {
+                        cap[1] == expected[1]
+                            && cap[2].eq_ignore_ascii_case(&expected[2])
+                    })
+                }) {
+                    bail!(
+                        "The commit message has no 'Signed-off-by: {}' \
+                         trailer",
+                        author
+                    )
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_commit_message;
+
+    #[test]
+    fn style_checks() {
+        let mut config = Config::new();
+        let check = |message: &str, config: &Config| {
+            let commit = parse_commit_message(message, config).unwrap();
+            StyleCheck
+                .check(&commit, message, config)
+                .map_err(|e| e.to_string())
+        };
+        assert!(check("Added adding feature. WIP", &config).is_ok());
+
+        config.style_checks = vec![
+            "capitalized".to_owned(),
+            "imperative".to_owned(),
+            "no_period".to_owned(),
+            "no_wip".to_owned(),
+        ];
+        assert!(check("Added :ui: Add the feature", &config).is_ok());
+        assert!(check("Fixed Speed up the wiping", &config).is_ok());
+        assert_eq!(
+            check("Added adding feature. [WIP].", &config).unwrap_err(),
+            "The summary does not start with a capital letter, does not use \
+             the imperative mood, ends with a period, marks a work in progress"
+        );
+        assert!(check("Changed Updates the docs", &config).is_err());
+
+        config.style_checks = vec!["unknown".to_owned()];
+        assert!(check("Added Feature", &config).is_err());
+    }
+
+    #[test]
+    fn body_line_length() {
+        let mut config = Config::new();
+        let check = |message: &str, config: &Config| {
+            let commit = parse_commit_message(message, config).unwrap();
+            BodyLineLength
+                .check(&commit, message, config)
+                .map_err(|e| e.to_string())
+        };
+        let long = "word ".repeat(20);
+        let message = format!(
+            "Added {0}\n\n{0}\n\n- Fixed a bug\n\n\
+             https://example.com/{1}\n\n# {0}\n\n- Fixed {0}",
+            long,
+            "x".repeat(80)
+        );
+        assert_eq!(
+            check(&message, &config).unwrap_err(),
+            "The body lines 3, 11 exceed 72 characters"
+        );
+        assert!(check(&format!("Added {}", long), &config).is_ok());
+        let footer = format!("Added a\n\nSigned-off-by: {}<a@b.c>", long);
+        assert!(check(&footer, &config).is_ok());
+
+        config.max_body_line_length = 0;
+        assert!(check(&message, &config).is_ok());
+    }
+
+    #[test]
+    fn issue_reference() {
+        let mut config = Config::new();
+        let check = |message: &str, config: &Config| {
+            let commit = parse_commit_message(message, config).unwrap();
+            IssueReference
+                .check(&commit, message, config)
+                .map_err(|e| e.to_string())
+        };
+        assert!(check("Fixed some bug", &config).is_ok());
+
+        config
+            .issue_requirements
+            .insert("Fixed".to_owned(), "error".to_owned());
+        config
+            .issue_requirements
+            .insert("Removed".to_owned(), "warn".to_owned());
+        assert_eq!(
+            check("Added feature\n\n- Fixed bug\n\n# Closes #12", &config)
+                .unwrap_err(),
+            "The category 'Fixed' requires an issue reference like '#123'"
+        );
+        assert!(check("Fixed some bug (#12)", &config).is_ok());
+        assert!(check("Fixed bug\n\nCloses: ABC-12", &config).is_ok());
+        assert!(check("JIRA-1234 Fixed some bug", &config).is_ok());
+        assert!(check("Removed some feature", &config).is_ok());
+        assert!(check("Added some feature", &config).is_ok());
+
+        config
+            .issue_requirements
+            .insert("Added".to_owned(), "always".to_owned());
+        assert!(check("Added some feature", &config).is_err());
+    }
+
+    #[test]
+    fn signoff() {
+        let mut config = Config::new();
+        let check = |message: &str, config: &Config| {
+            let commit = parse_commit_message(message, config).unwrap();
+            let rule = SignOff {
+                author: Some("Jane Doe <jane@example.com>".to_owned()),
+            };
+            rule.check(&commit, message, config)
+                .map_err(|e| e.to_string())
+        };
+        let other = "Added feature\n\nSigned-off-by: John <john@example.com>";
+        assert!(check("Added feature", &config).is_ok());
+
+        config.signoff = "required".to_owned();
+        assert!(check(other, &config).is_ok());
+        assert_eq!(
+            check("Added feature\n\nSigned-off-by: Jane", &config).unwrap_err(),
+            "The commit message has no valid 'Signed-off-by: Name <email>' \
+             trailer"
+        );
+
+        config.signoff = "author".to_owned();
+        assert_eq!(
+            check(other, &config).unwrap_err(),
+            "The commit message has no 'Signed-off-by: Jane Doe \
+             <jane@example.com>' trailer"
+        );
+        assert!(check(
+            &format!("{}\nSigned-off-by: Jane Doe <Jane@Example.com>", other),
+            &config
+        )
+        .is_ok());
+
+        config.signoff = "always".to_owned();
+        assert!(check("Added feature", &config).is_err());
+    }
+}
@@ -0,0 +1,358 @@
+//! A small HTTP service generating the release notes of a repository on
+//! request, which is only available if the `server` feature is enabled.
+//!
+//! The changelog is available via `GET /changelog`, where the query
+//! parameters `from` and `to` select the range of tags and `format` the
+//! output, which is either "md" (default) or one of the export formats like
+//! "json". Every request parses the repository again, so that new commits and
+//! tags are reflected immediately.
+
+use crate::{error::Report, GitJournal, ParseOptions, JSON_SCHEMA};
+use failure::Error;
+use log::{info, warn};
+use std::{
+    collections::HashMap,
+    io::{prelude::*, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// The maximum size of the request line and headers in bytes.
+static MAX_REQUEST_SIZE: u64 = 8192;
+
+/// The maximum number of connections answered at the same time.
+static MAX_CONNECTIONS: usize = 16;
+
+/// The time after which reading the request or writing the response of a
+/// connection is aborted.
+static TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A connection being answered, which counts towards the `MAX_CONNECTIONS`
+/// until it is dropped.
+struct Connection(Arc<AtomicUsize>);
+
+impl Connection {
+    /// Opens a connection, if less than `MAX_CONNECTIONS` are active.
+    fn open(active: &Arc<AtomicUsize>) -> Option<Self> {
+        if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            active.fetch_sub(1, Ordering::SeqCst);
+            return None;
+        }
+        Some(Connection(Arc::clone(active)))
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A response of the service.
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn new(status: u16, content_type: &'static str, body: String) -> Self {
+        Response {
+            status,
+            content_type,
+            body,
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Response::new(status, "text/plain", format!("{}\n", message))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        }
+    }
+
+    fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}; charset=utf-8\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.reason(),
+            self.content_type,
+            self.body.len(),
+            self.body
+        )?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Decodes a percent encoded query component, where `+` is a space.
+fn decode(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' if index + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[index + 1..index + 3]);
+                match hex.ok().and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        index += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Splits a request target like `/changelog?from=v1` into its path and the
+/// decoded query parameters.
+fn parse_target(target: &str) -> (&str, HashMap<String, String>) {
+    let (path, query) = match target.find('?') {
+        Some(index) => (&target[..index], &target[index + 1..]),
+        None => (target, ""),
+    };
+    let parameters = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.find('=') {
+            Some(index) => (decode(&pair[..index]), decode(&pair[index + 1..])),
+            None => (decode(pair), String::new()),
+        })
+        .collect();
+    (path, parameters)
+}
+
+/// Returns the content type of an output format.
+fn content_type(format: &str) -> &'static str {
+    match format {
+        "csv" => "text/csv",
+        "json" => "application/json",
+        "toml" => "application/toml",
+        "tsv" => "text/tab-separated-values",
+        "yaml" => "application/yaml",
+        _ => "text/markdown",
+    }
+}
+
+/// Generates the changelog of the repository for the query parameters.
+fn changelog(
+    path: &str,
+    parameters: &HashMap<String, String>,
+) -> Result<Response, Error> {
+    let mut journal = GitJournal::new(path)?;
+    let format = parameters.get("format").map_or("md", |f| f.as_str());
+    let to = parameters.get("to").map(|to| to.as_str());
+    match parameters.get("from") {
+        Some(from) => {
+            let options = ParseOptions::default().all(true);
+            journal.parse_log_between_tags(from, to, &options)?;
+        }
+        None => {
+            journal.parse_log(
+                &ParseOptions::new(to.unwrap_or("HEAD")).all(true),
+            )?;
+        }
+    }
+    let body = match format {
+        "md" | "markdown" => journal.parsed().render(false, None)?,
+        _ => journal.export(format)?,
+    };
+    Ok(Response::new(200, content_type(format), body))
+}
+
+/// Answers a single request given by its method and target.
+fn respond(path: &str, method: &str, target: &str) -> Response {
+    if method != "GET" {
+        return Response::error(405, "Only GET requests are supported");
+    }
+    let (route, parameters) = parse_target(target);
+    match route {
+        "/changelog" => changelog(path, &parameters).unwrap_or_else(|e| {
+            Response::error(400, &format!("{:#}", Report::from(e)))
+        }),
+        "/schema" => {
+            Response::new(200, "application/json", JSON_SCHEMA.to_owned())
+        }
+        _ => Response::error(404, "Unknown endpoint"),
+    }
+}
+
+/// Reads the request line and the headers of the connection and writes the
+/// response to it, where both are aborted after the `timeout`.
+fn handle(
+    path: &str,
+    stream: TcpStream,
+    timeout: Duration,
+) -> Result<(), Error> {
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_REQUEST_SIZE));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let response = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => {
+            info!("{} {}", method, target);
+            respond(path, method, target)
+        }
+        _ => Response::error(400, "Invalid request"),
+    };
+    response.write_to(&mut &stream)
+}
+
+/// Serves the release notes of the repository at the given path via HTTP on
+/// the address, e.g. "127.0.0.1:8080", until the process is stopped. Every
+/// connection is handled within its own thread, where at most 16 connections
+/// are answered at the same time and further ones are rejected. Connections
+/// which do not send the request or read the response within 10 seconds are
+/// closed.
+///
+/// # Errors
+/// When the address could not be bound.
+pub fn serve(path: &str, address: &str) -> Result<(), Error> {
+    let listener = TcpListener::bind(address)?;
+    info!("Serving the release notes on 'http://{}'.", address);
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!("Could not accept the connection: {}", e);
+                continue;
+            }
+        };
+        let connection = match Connection::open(&active) {
+            Some(connection) => connection,
+            None => {
+                warn!("Rejecting the connection, too many requests.");
+                stream.set_write_timeout(Some(TIMEOUT)).ok();
+                let response = Response::error(503, "Too many requests");
+                response.write_to(&mut &stream).ok();
+                continue;
+            }
+        };
+        let path = path.to_owned();
+        thread::spawn(move || {
+            let _connection = connection;
+            if let Err(e) = handle(&path, stream, TIMEOUT) {
+                warn!("Could not answer the request: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Shutdown;
+
+    #[test]
+    fn query_parameters() {
+        let (route, parameters) =
+            parse_target("/changelog?from=v1.0.0&to=v%32&format=json&x+y&");
+        assert_eq!(route, "/changelog");
+        assert_eq!(parameters["from"], "v1.0.0");
+        assert_eq!(parameters["to"], "v2");
+        assert_eq!(parameters["format"], "json");
+        assert_eq!(parameters["x y"], "");
+        assert_eq!(decode("100%"), "100%");
+        assert_eq!(decode("%zz%41"), "%zzA");
+        assert_eq!(decode("%ä%C3%A4"), "%ää");
+    }
+
+    #[test]
+    fn responses() {
+        assert_eq!(respond(".", "POST", "/changelog").status, 405);
+        assert_eq!(respond(".", "GET", "/other").status, 404);
+        assert_eq!(respond(".", "GET", "/schema").body, JSON_SCHEMA);
+        let response = respond(".", "GET", "/changelog?from=missing");
+        assert_eq!(response.status, 400);
+        assert!(response.body.contains("missing"));
+        let response = respond(".", "GET", "/changelog?format=unknown");
+        assert_eq!(response.status, 400);
+
+        let response = respond(".", "GET", "/changelog?format=json");
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, "application/json");
+        assert!(response.body.contains("\"format_version\""));
+        let response = respond(".", "GET", "/changelog");
+        assert_eq!(response.content_type, "text/markdown");
+    }
+
+    #[test]
+    fn serve_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            handle(".", stream, TIMEOUT).unwrap();
+        });
+        let mut client = TcpStream::connect(address).unwrap();
+        client
+            .write_all(b"GET /schema HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        client.shutdown(Shutdown::Write).unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        server.join().unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: application/json"));
+        assert!(response.ends_with(JSON_SCHEMA));
+    }
+
+    #[test]
+    fn request_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            handle(".", stream, Duration::from_millis(50))
+        });
+        let _client = TcpStream::connect(address).unwrap();
+        assert!(server.join().unwrap().is_err());
+    }
+
+    #[test]
+    fn limit_connections() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let mut connections = (0..MAX_CONNECTIONS)
+            .map(|_| Connection::open(&active).unwrap())
+            .collect::<Vec<_>>();
+        assert!(Connection::open(&active).is_none());
+        assert_eq!(active.load(Ordering::SeqCst), MAX_CONNECTIONS);
+        connections.pop();
+        assert!(Connection::open(&active).is_some());
+        drop(connections);
+        assert_eq!(active.load(Ordering::SeqCst), 0);
+    }
+}
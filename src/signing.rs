@@ -0,0 +1,64 @@
+//! GPG signatures of the generated changelogs, which allow the consumers to
+//! verify that the release notes have not been changed after the generation.
+
+use failure::{bail, format_err, Error};
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+/// Returns the ASCII armored detached signature of the content, created by
+/// the GPG `program` with the optional key.
+pub fn sign(
+    program: &str,
+    key: Option<&str>,
+    content: &[u8],
+) -> Result<String, Error> {
+    let mut command = Command::new(program);
+    command.args(["--batch", "--armor", "--detach-sign"]);
+    if let Some(key) = key {
+        command.args(["--local-user", key]);
+    }
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format_err!("Could not run '{}': {}", program, e))?;
+    if let Some(ref mut stdin) = child.stdin {
+        stdin.write_all(content)?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "Could not sign the output: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Returns the signature as trailer block, which is an HTML comment to keep
+/// it hidden within rendered Markdown. The signature covers everything
+/// before the trailer block.
+pub fn trailer(signature: &str) -> String {
+    format!("\n<!--\n{}-->\n", signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_trailer() {
+        let signature = "-----BEGIN PGP SIGNATURE-----\n\n\
+                         abc\n-----END PGP SIGNATURE-----\n";
+        assert_eq!(
+            trailer(signature),
+            "\n<!--\n-----BEGIN PGP SIGNATURE-----\n\nabc\n\
+             -----END PGP SIGNATURE-----\n-->\n"
+        );
+        assert!(sign("/dev/null/gpg", None, b"content").is_err());
+        assert!(sign("false", None, b"content").is_err());
+    }
+}
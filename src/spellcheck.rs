@@ -0,0 +1,120 @@
+//! A dictionary based spell check of the commit messages during the
+//! verification, which is only available if the `spellcheck` feature is
+//! enabled.
+
+use crate::{config::Config, parser::ParsedCommit, rules::Rule};
+use failure::{bail, Error};
+use log::warn;
+use std::{collections::HashSet, fs};
+
+/// The built-in rule checking the words of the summary and the body against
+/// the `spellcheck_dictionary` and the project `spellcheck_words`.
+pub(crate) struct SpellCheck;
+
+impl SpellCheck {
+    /// Returns the misspelled words of the text, in the order of their
+    /// occurrence. Words containing digits, written in capitals like "API"
+    /// or within backticks are skipped.
+    fn misspelled<'a>(text: &'a str, words: &HashSet<String>) -> Vec<&'a str> {
+        text.split('`')
+            .step_by(2)
+            .flat_map(|text| text.split_whitespace())
+            .filter(|word| !word.contains("://"))
+            .map(|word| {
+                word.trim_matches(|c: char| !c.is_alphanumeric())
+                    .trim_end_matches("'s")
+            })
+            .filter(|word| {
+                !word.is_empty()
+                    && word.chars().all(|c| c.is_alphabetic() || c == '\'')
+                    && word.chars().skip(1).any(|c| c.is_lowercase())
+                    && !words.contains(&word.to_lowercase())
+            })
+            .collect()
+    }
+}
+
+impl Rule for SpellCheck {
+    fn name(&self) -> &str {
+        "spellcheck"
+    }
+
+    fn check(
+        &self,
+        commit: &ParsedCommit,
+        _message: &str,
+        config: &Config,
+    ) -> Result<(), Error> {
+        if config.spellcheck == "off" {
+            return Ok(());
+        }
+        let dictionary = match fs::read_to_string(&config.spellcheck_dictionary)
+        {
+            Ok(dictionary) => dictionary,
+            Err(e) => {
+                warn!(
+                    "Skipping the spell check, could not read the dictionary \
+                     '{}': {}",
+                    config.spellcheck_dictionary, e
+                );
+                return Ok(());
+            }
+        };
+        let words = dictionary
+            .lines()
+            .chain(config.spellcheck_words.iter().map(|w| w.as_str()))
+            .map(|word| word.trim().to_lowercase())
+            .collect::<HashSet<_>>();
+
+        let misspelled = commit
+            .texts()
+            .into_iter()
+            .flat_map(|text| Self::misspelled(text, &words))
+            .collect::<Vec<_>>();
+        if misspelled.is_empty() {
+            return Ok(());
+        }
+        let message = format!("Unknown words: {}", misspelled.join(", "));
+        if config.spellcheck == "error" {
+            bail!(message);
+        }
+        warn!("{}", message);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_commit_message;
+    use std::env;
+
+    #[test]
+    fn spellcheck() {
+        let path = env::temp_dir().join("gitjournal_spellcheck_words");
+        fs::write(&path, "added\nthe\nfeature\nfixed\nbug\nin\n").unwrap();
+        let mut config = Config::new();
+        config.spellcheck_dictionary = path.display().to_string();
+        config.spellcheck_words = vec!["GitJournal".to_owned()];
+        config.spellcheck = "error".to_owned();
+
+        let check = |message: &str, config: &Config| {
+            let commit = parse_commit_message(message, config).unwrap();
+            SpellCheck.check(&commit, message, config)
+        };
+        assert!(check("Added the feature :ui:", &config).is_ok());
+        assert!(check("Added the gitjournal's `fetur` API v2", &config).is_ok());
+        let error = check("Added the featur\n\n- Fixed bugg", &config);
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "Unknown words: featur, bugg"
+        );
+
+        config.spellcheck = "warn".to_owned();
+        assert!(check("Added the featur", &config).is_ok());
+        config.spellcheck = "error".to_owned();
+        config.spellcheck_dictionary = "/dev/null/missing".to_owned();
+        assert!(check("Added the featur", &config).is_ok());
+        fs::remove_file(path).unwrap();
+    }
+}
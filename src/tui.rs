@@ -0,0 +1,355 @@
+//! An interactive terminal browser for the parsed log, which is only available
+//! if the `tui` feature is enabled.
+
+use crate::{
+    encoding::base64,
+    journal::ParsedJournal,
+    parser::{Print, MERGED_TAGS_SEPARATOR},
+    width,
+};
+use failure::Error;
+use std::{
+    collections::HashSet,
+    io::{self, prelude::*},
+};
+use termion::{
+    clear, cursor, event::Key, input::TermRead, raw::IntoRawMode,
+    screen::AlternateScreen, style,
+};
+
+/// The help line shown at the bottom of the browser.
+static HELP: &str = "j/k: move  enter: expand/collapse  /: filter  \
+                     c: copy release  q: quit";
+
+/// A single line of the browser.
+#[derive(Clone, Debug, PartialEq)]
+enum Row {
+    /// A release by its index
+    Release(usize),
+
+    /// A category within a release together with the amount of commits
+    Category(usize, String, usize),
+
+    /// A commit by the index of its release and its index inside of it
+    Commit(usize, usize),
+}
+
+/// The state of the browser, independent of any terminal.
+struct Browser<'a> {
+    journal: &'a ParsedJournal,
+    expanded: HashSet<(usize, String)>,
+    selected: usize,
+    offset: usize,
+    filter: String,
+    filtering: bool,
+    message: Option<String>,
+}
+
+impl<'a> Browser<'a> {
+    fn new(journal: &'a ParsedJournal) -> Self {
+        Browser {
+            journal,
+            expanded: HashSet::new(),
+            selected: 0,
+            offset: 0,
+            filter: String::new(),
+            filtering: false,
+            message: None,
+        }
+    }
+
+    /// Returns whether the commit matches the current filter, which can be a
+    /// tag or a part of the author name.
+    fn is_visible(&self, release: usize, commit: usize) -> bool {
+        if self.filter.is_empty() {
+            return true;
+        }
+        let commit = &self.journal.releases()[release].commits[commit];
+        let author_matches = match commit.author {
+            Some(ref author) => {
+                author.to_lowercase().contains(&self.filter.to_lowercase())
+            }
+            None => false,
+        };
+        author_matches || commit.contains_tag(Some(&self.filter))
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = vec![];
+        for (index, release) in self.journal.releases().iter().enumerate() {
+            rows.push(Row::Release(index));
+
+            // Group the visible commits by their category, keeping the order
+            // of their first occurrence
+            let mut categories: Vec<(String, Vec<usize>)> = vec![];
+            for (commit_index, commit) in release.commits.iter().enumerate() {
+                if !self.is_visible(index, commit_index) {
+                    continue;
+                }
+                let category = &commit.summary.category;
+                match categories.iter_mut().find(|c| &c.0 == category) {
+                    Some(entry) => entry.1.push(commit_index),
+                    None => {
+                        categories.push((category.clone(), vec![commit_index]))
+                    }
+                }
+            }
+            for (category, commits) in categories {
+                let expanded =
+                    self.expanded.contains(&(index, category.clone()));
+                rows.push(Row::Category(index, category, commits.len()));
+                if expanded {
+                    rows.extend(
+                        commits.into_iter().map(|c| Row::Commit(index, c)),
+                    );
+                }
+            }
+        }
+        rows
+    }
+
+    fn release_of(row: &Row) -> usize {
+        match *row {
+            Row::Release(index)
+            | Row::Category(index, _, _)
+            | Row::Commit(index, _) => index,
+        }
+    }
+
+    fn format_row(&self, row: &Row) -> String {
+        let releases = self.journal.releases();
+        match *row {
+            Row::Release(index) => format!(
+                "{} ({})",
+                releases[index].name,
+                releases[index].commits.len()
+            ),
+            Row::Category(index, ref category, count) => {
+                let marker =
+                    if self.expanded.contains(&(index, category.clone())) {
+                        "-"
+                    } else {
+                        "+"
+                    };
+                format!("  {} {} ({})", marker, category, count)
+            }
+            Row::Commit(release, commit) => {
+                let commit = &releases[release].commits[commit];
+                match commit.author {
+                    Some(ref author) => {
+                        format!("      {} ({})", commit.summary.text, author)
+                    }
+                    None => format!("      {}", commit.summary.text),
+                }
+            }
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let count = self.rows().len() as isize;
+        let selected = (self.selected as isize + delta).max(0).min(count - 1);
+        self.selected = selected.max(0) as usize;
+    }
+
+    fn toggle(&mut self) {
+        if let Some(Row::Category(index, category, _)) =
+            self.rows().get(self.selected).cloned()
+        {
+            let key = (index, category);
+            if !self.expanded.remove(&key) {
+                self.expanded.insert(key);
+            }
+        }
+    }
+
+    /// Returns the rendered section of the selected release.
+    fn selected_release(&self) -> Result<String, Error> {
+        let rows = self.rows();
+        let index = match rows.get(self.selected) {
+            Some(row) => Self::release_of(row),
+            None => return Ok(String::new()),
+        };
+        let name = &self.journal.releases()[index].name;
+        let name = name.split(MERGED_TAGS_SEPARATOR).next().unwrap_or(name);
+        self.journal.render_release(name, false, None)
+    }
+
+    /// Handles a single key press and returns whether the browser should
+    /// continue.
+    fn handle_key<W: Write>(
+        &mut self,
+        key: Key,
+        out: &mut W,
+    ) -> Result<bool, Error> {
+        self.message = None;
+        if self.filtering {
+            match key {
+                Key::Char('\n') | Key::Esc => self.filtering = false,
+                Key::Backspace => {
+                    self.filter.pop();
+                }
+                Key::Char(c) => self.filter.push(c),
+                _ => {}
+            }
+            self.selected = 0;
+            return Ok(true);
+        }
+        match key {
+            Key::Char('q') | Key::Esc | Key::Ctrl('c') => return Ok(false),
+            Key::Char('j') | Key::Down => self.move_selection(1),
+            Key::Char('k') | Key::Up => self.move_selection(-1),
+            Key::PageDown => self.move_selection(10),
+            Key::PageUp => self.move_selection(-10),
+            Key::Char('\n') | Key::Char(' ') => self.toggle(),
+            Key::Char('/') => {
+                self.filter.clear();
+                self.filtering = true;
+            }
+            Key::Char('c') => {
+                // Copy via the OSC 52 escape sequence of the terminal
+                let section = self.selected_release()?;
+                write!(out, "\x1b]52;c;{}\x07", base64(section.as_bytes()))?;
+                self.message = Some("Copied the release section.".to_owned());
+            }
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    fn draw<W: Write>(
+        &mut self,
+        out: &mut W,
+        (width, height): (usize, usize),
+    ) -> Result<(), Error> {
+        let rows = self.rows();
+        let height = height.max(2) - 1;
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + height {
+            self.offset = self.selected + 1 - height;
+        }
+
+        write!(out, "{}{}", clear::All, cursor::Goto(1, 1))?;
+        for (index, row) in
+            rows.iter().enumerate().skip(self.offset).take(height)
+        {
+            if index == self.selected {
+                write!(
+                    out,
+                    "{}{}{}",
+                    style::Invert,
+                    width::truncate(&self.format_row(row), width),
+                    style::Reset
+                )?;
+            } else {
+                write!(
+                    out,
+                    "{}",
+                    width::truncate(&self.format_row(row), width)
+                )?;
+            }
+            write!(out, "\r\n")?;
+        }
+
+        write!(out, "{}", cursor::Goto(1, height as u16 + 1))?;
+        let status = if self.filtering {
+            format!("/{}", self.filter)
+        } else if let Some(ref message) = self.message {
+            message.clone()
+        } else if !self.filter.is_empty() {
+            format!("Filter: {}  {}", self.filter, HELP)
+        } else {
+            HELP.to_owned()
+        };
+        write!(out, "{}", width::truncate(&status, width))?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// Shows the parsed releases in an interactive terminal browser until the
+/// user quits.
+///
+/// # Errors
+/// When the terminal could not be set up or the rendering of a release
+/// failed.
+pub fn browse(journal: &ParsedJournal) -> Result<(), Error> {
+    let mut out = AlternateScreen::from(io::stdout().into_raw_mode()?);
+    write!(out, "{}", cursor::Hide)?;
+    let mut browser = Browser::new(journal);
+    let mut keys = io::stdin().keys();
+    let result = loop {
+        let (width, height) = termion::terminal_size()?;
+        let size = (width as usize, height as usize);
+        if let Err(e) = browser.draw(&mut out, size) {
+            break Err(e);
+        }
+        match keys.next() {
+            Some(Ok(key)) => match browser.handle_key(key, &mut out) {
+                Ok(true) => {}
+                Ok(false) => break Ok(()),
+                Err(e) => break Err(e),
+            },
+            Some(Err(e)) => break Err(e.into()),
+            None => break Ok(()),
+        }
+    };
+    write!(out, "{}", cursor::Show)?;
+    out.flush()?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::Config,
+        parser::{ParsedTag, Parser},
+    };
+
+    fn get_journal() -> ParsedJournal {
+        let config = Config::new();
+        let mut tag = ParsedTag::from_messages(
+            "v1",
+            &["Added file1 :feature:", "Fixed file1", "Added file2"],
+            &config,
+        );
+        for (commit, author) in
+            tag.commits.iter_mut().zip(&["Alice", "Bob", "Bob"])
+        {
+            commit.author = Some((*author).to_owned());
+        }
+        let parser = Parser {
+            config,
+            result: vec![tag],
+        };
+        ParsedJournal::new(parser, None)
+    }
+
+    #[test]
+    fn browser_rows() {
+        let journal = get_journal();
+        let mut browser = Browser::new(&journal);
+        let mut out = vec![];
+        assert_eq!(browser.rows().len(), 3);
+        assert_eq!(browser.rows()[1], Row::Category(0, "Added".to_owned(), 2));
+
+        assert!(browser.handle_key(Key::Down, &mut out).unwrap());
+        assert!(browser.handle_key(Key::Char('\n'), &mut out).unwrap());
+        assert_eq!(browser.rows().len(), 5);
+        assert_eq!(browser.rows()[2], Row::Commit(0, 0));
+        assert!(browser.draw(&mut out, (80, 10)).is_ok());
+
+        for c in "/bob\n".chars() {
+            assert!(browser.handle_key(Key::Char(c), &mut out).unwrap());
+        }
+        assert_eq!(browser.rows().len(), 4);
+        for c in "/feature\n".chars() {
+            assert!(browser.handle_key(Key::Char(c), &mut out).unwrap());
+        }
+        assert_eq!(browser.rows().len(), 3);
+
+        assert!(browser.handle_key(Key::Char('c'), &mut out).unwrap());
+        assert!(!browser.handle_key(Key::Char('q'), &mut out).unwrap());
+    }
+}
@@ -0,0 +1,132 @@
+//! Recoverable issues found during the parsing, which are returned to the
+//! caller instead of aborting the parsing.
+
+use crate::oid::Oid;
+use std::{fmt, slice, vec};
+
+/// A single recoverable issue of the parsing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Warning {
+    /// A commit message does not follow the syntax and has been skipped
+    InvalidCommit {
+        /// The id of the skipped commit
+        oid: Oid,
+
+        /// The reason why the message could not be parsed
+        reason: String,
+
+        /// The first file changed by the commit, which locates its CI
+        /// annotations, if known
+        path: Option<String>,
+    },
+
+    /// A tag name is not valid UTF-8 and the tag has been ignored, where the
+    /// invalid bytes are replaced
+    InvalidTagName(String),
+
+    /// A commit is dated before one of its parents, which breaks the time
+    /// based sorting of the releases
+    ClockSkew {
+        /// The id of the commit
+        oid: Oid,
+
+        /// The id of the newer parent
+        parent: Oid,
+    },
+
+    /// The repository is a shallow clone, where the history and the tags
+    /// before its boundary are missing
+    ShallowClone,
+
+    /// A release does not contain any valid commit, since all its commits
+    /// are invalid or skipped, and has been omitted
+    EmptyRelease(String),
+
+    /// A referenced Azure Boards work item like "AB#1234" does not exist,
+    /// which is only validated if an API token is available
+    UnknownWorkItem(String),
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Warning::InvalidCommit {
+                ref oid,
+                ref reason,
+                ..
+            } => {
+                write!(f, "Skipping commit {}: {}", oid, reason)
+            }
+            Warning::InvalidTagName(ref name) => {
+                write!(f, "Ignoring tag '{}' with invalid name", name)
+            }
+            Warning::ClockSkew {
+                ref oid,
+                ref parent,
+            } => write!(
+                f,
+                "Commit {} is dated before its parent {}",
+                oid, parent
+            ),
+            Warning::ShallowClone => write!(
+                f,
+                "The repository is a shallow clone, the history and tags \
+                 before its boundary are missing"
+            ),
+            Warning::EmptyRelease(ref name) => write!(
+                f,
+                "Omitting release '{}' without any valid commit",
+                name
+            ),
+            Warning::UnknownWorkItem(ref reference) => write!(
+                f,
+                "The Azure Boards work item {} does not exist",
+                reference
+            ),
+        }
+    }
+}
+
+/// The warnings of a single parsing, in the order they occurred.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Warnings(Vec<Warning>);
+
+impl Warnings {
+    /// Returns an iterator over the warnings.
+    pub fn iter(&self) -> slice::Iter<'_, Warning> {
+        self.0.iter()
+    }
+
+    /// Returns the number of warnings.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the parsing succeeded without any warning.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[cfg(feature = "git")]
+    pub(crate) fn push(&mut self, warning: Warning) {
+        self.0.push(warning)
+    }
+}
+
+impl IntoIterator for Warnings {
+    type Item = Warning;
+    type IntoIter = vec::IntoIter<Warning>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Warnings {
+    type Item = &'a Warning;
+    type IntoIter = slice::Iter<'a, Warning>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
@@ -0,0 +1,154 @@
+//! Helpers for the display width of text within the terminal, where wide
+//! characters like CJK or emojis take two columns and ANSI color escape
+//! sequences take none.
+
+use unicode_width::UnicodeWidthChar;
+
+/// The character appended to truncated text.
+pub static ELLIPSIS: char = '…';
+
+/// Returns the display width of a single character.
+fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// Returns the display width of the text, ignoring ANSI escape sequences.
+pub fn display_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip the escape sequence up to its final letter
+            for c in &mut chars {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            width += char_width(c);
+        }
+    }
+    width
+}
+
+/// Truncates the text to the given display width including the trailing
+/// ellipsis. Characters are never split, so the result can be narrower if a
+/// wide character does not fit anymore.
+pub fn truncate(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_owned();
+    }
+    let mut result = String::new();
+    let mut current = 0;
+    for c in text.chars() {
+        let w = char_width(c);
+        if current + w + 1 > width {
+            break;
+        }
+        current += w;
+        result.push(c);
+    }
+    if width > 0 {
+        result.push(ELLIPSIS);
+    }
+    result
+}
+
+/// Wraps the text at whitespace into lines of the given display width. Words
+/// which are wider than the width will be split between their characters.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = vec![];
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word = word.to_owned();
+        loop {
+            let line_width = display_width(&line);
+            let separator = if line.is_empty() { 0 } else { 1 };
+            if line_width + separator + display_width(&word) <= width {
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(&word);
+                break;
+            }
+            if !line.is_empty() {
+                lines.push(line);
+                line = String::new();
+                continue;
+            }
+
+            // Split the word which does not fit into a single line
+            let mut current = 0;
+            let mut split = word.len();
+            for (index, c) in word.char_indices() {
+                current += char_width(c);
+                if current > width && index > 0 {
+                    split = index;
+                    break;
+                }
+            }
+            lines.push(word[..split].to_owned());
+            word = word[split..].to_owned();
+            if word.is_empty() {
+                break;
+            }
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Returns the amount of terminal rows needed to display the text, where
+/// lines longer than the terminal width will be wrapped.
+pub fn rows(text: &str, terminal_width: usize) -> usize {
+    text.lines()
+        .map(|line| {
+            let width = display_width(line);
+            if width == 0 || terminal_width == 0 {
+                1
+            } else {
+                width.div_ceil(terminal_width)
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn width_of_text() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("✨ a"), 4);
+        assert_eq!(display_width("\x1b[32mabc\x1b[0m"), 3);
+    }
+
+    #[test]
+    fn truncate_text() {
+        assert_eq!(truncate("abc", 3), "abc");
+        assert_eq!(truncate("abcdef", 4), "abc…");
+        assert_eq!(truncate("日本語テキスト", 6), "日本…");
+        assert_eq!(truncate("日本語", 4), "日…");
+        assert_eq!(truncate("abc", 0), "");
+    }
+
+    #[test]
+    fn wrap_text() {
+        assert_eq!(wrap("a b c", 3), vec!["a b", "c"]);
+        assert_eq!(wrap("some longer text", 6), vec!["some", "longer", "text"]);
+        assert_eq!(wrap("abcdefgh", 3), vec!["abc", "def", "gh"]);
+        assert_eq!(wrap("日本語 テキスト", 6), vec!["日本語", "テキス", "ト"]);
+        assert_eq!(wrap("", 3), vec![""]);
+    }
+
+    #[test]
+    fn rows_of_text() {
+        assert_eq!(rows("abc\n\ndef", 10), 3);
+        assert_eq!(rows("日本語テキスト", 10), 2);
+    }
+}